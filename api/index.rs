@@ -1,5 +1,18 @@
 use vercel_runtime::{run, Body, Error, Request, Response, StatusCode};
 use serde_json::{json, Value};
+use rustohebru::dictionary_search::TermQuery;
+use rustohebru::knowledge_sharing::KnowledgeManager;
+use rustohebru::permissions::{Action, PermissionError, PermissionPolicy};
+use rustohebru::technical_dictionary::TechnicalDictionary;
+
+// קובץ המילון לחיפוש; ניתן לשינוי במשתנה סביבה
+const DICTIONARY_PATH: &str = "technical_dictionary.json";
+// מאגר משתפי הפעולה וקובץ ההרשאות, לבדיקת התפקיד של מבצע הבקשה
+const KNOWLEDGE_DB: &str = "knowledge.db";
+const PERMISSIONS_FILE: &str = "permissions.json";
+// מפתח הגישה של המשתמש: "Authorization: Bearer <מפתח>"
+const AUTH_HEADER: &str = "Authorization";
+const AUTH_SCHEME: &str = "Bearer ";
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
@@ -7,6 +20,10 @@ async fn main() -> Result<(), Error> {
 }
 
 pub async fn handler(req: Request) -> Result<Response<Body>, Error> {
+    if req.uri().path().ends_with("/dictionary/search") {
+        return search_dictionary(&req);
+    }
+
     let body: Value = match req.body() {
         Body::Text(text) => serde_json::from_str(text)?,
         _ => json!({}),
@@ -23,4 +40,73 @@ pub async fn handler(req: Request) -> Result<Response<Body>, Error> {
         }).to_string()))?;
 
     Ok(response)
-} 
\ No newline at end of file
+}
+
+// חיפוש במילון: גוף הבקשה הוא שאילתה בפורמט JSON, כל השדות אופציונליים
+fn search_dictionary(req: &Request) -> Result<Response<Body>, Error> {
+    if let Some(denied) = authorize(req, Action::ViewTerms)? {
+        return Ok(denied);
+    }
+
+    let query: TermQuery = match req.body() {
+        Body::Text(text) if !text.trim().is_empty() => match serde_json::from_str(text) {
+            Ok(query) => query,
+            Err(e) => return json_response(StatusCode::BAD_REQUEST, json!({ "error": format!("שאילתה לא תקינה: {}", e) })),
+        },
+        _ => TermQuery::default(),
+    };
+
+    let path = std::env::var("DICTIONARY_PATH").unwrap_or_else(|_| DICTIONARY_PATH.to_string());
+    let dictionary = match TechnicalDictionary::new(path) {
+        Ok(dictionary) => dictionary,
+        Err(e) => return json_response(StatusCode::INTERNAL_SERVER_ERROR, json!({ "error": e.to_string() })),
+    };
+
+    json_response(StatusCode::OK, serde_json::to_value(dictionary.search(&query))?)
+}
+
+// זיהוי מבצע הבקשה לפי מפתח הגישה ובדיקת הרשאתו; מחזיר תשובת שגיאה אם הבקשה נדחתה.
+// בממשק ה-HTTP אין תפקיד התחלה: רק משתפי פעולה רשומים עם מפתח מורשים
+fn authorize(req: &Request, action: Action) -> Result<Option<Response<Body>>, Error> {
+    let Some(api_key) = req
+        .headers()
+        .get(AUTH_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix(AUTH_SCHEME))
+        .filter(|key| !key.trim().is_empty())
+    else {
+        return json_response(StatusCode::UNAUTHORIZED, json!({ "error": "חסר מפתח גישה" })).map(Some);
+    };
+
+    let policy_path = std::env::var("PERMISSIONS_PATH").unwrap_or_else(|_| PERMISSIONS_FILE.to_string());
+    let db_path = std::env::var("KNOWLEDGE_DB_PATH").unwrap_or_else(|_| KNOWLEDGE_DB.to_string());
+    let manager = PermissionPolicy::load_or_default(std::path::Path::new(&policy_path)).and_then(|policy| {
+        Ok(KnowledgeManager::open(std::path::Path::new(&db_path))?.with_policy(policy.with_bootstrap_role(None)))
+    });
+    let manager = match manager {
+        Ok(manager) => manager,
+        Err(e) => return json_response(StatusCode::INTERNAL_SERVER_ERROR, json!({ "error": e.to_string() })).map(Some),
+    };
+    let Some(user_id) = manager.authenticate(api_key) else {
+        return json_response(StatusCode::UNAUTHORIZED, json!({ "error": "מפתח גישה לא תקין" })).map(Some);
+    };
+
+    match manager.authorize(user_id, action) {
+        Ok(()) => Ok(None),
+        Err(e) => {
+            let status = match e.downcast_ref::<PermissionError>() {
+                Some(PermissionError::UnknownUser { .. }) => StatusCode::UNAUTHORIZED,
+                Some(PermissionError::Denied { .. }) => StatusCode::FORBIDDEN,
+                None => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            json_response(status, json!({ "error": e.to_string() })).map(Some)
+        }
+    }
+}
+
+fn json_response(status: StatusCode, body: Value) -> Result<Response<Body>, Error> {
+    Ok(Response::builder()
+        .status(status)
+        .header("Content-Type", "application/json")
+        .body(Body::Text(body.to_string()))?)
+}
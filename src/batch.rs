@@ -0,0 +1,356 @@
+use crate::translation::Translator;
+use crate::language::Language;
+use crate::streaming::TranslationProgress;
+use anyhow::Result;
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
+
+const DEFAULT_WORKERS: usize = 4;
+const TOP_REPETITIONS: usize = 10;
+
+/// סטטיסטיקת חזרות בתרגום אצווה
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatchStatistics {
+    /// מספר המקטעים שהתקבלו, כולל ריקים
+    pub total_segments: usize,
+    /// מקטעים ריקים שלא נשלחו לתרגום
+    pub empty_segments: usize,
+    /// מקטעים ייחודיים לאחר נרמול - אלה שתורגמו בפועל
+    pub unique_segments: usize,
+    /// מופעים שקיבלו תרגום ממקטע זהה שכבר תורגם
+    pub repeated_segments: usize,
+    /// מילים בכל המקטעים לעומת מילים שתורגמו בפועל
+    pub total_words: usize,
+    pub unique_words: usize,
+    /// המקטעים החוזרים ביותר ומספר מופעיהם
+    pub most_repeated: Vec<(String, usize)>,
+}
+
+impl BatchStatistics {
+    /// שיעור המקטעים הלא-ריקים שנחסך בזכות איחוד חזרות
+    pub fn repetition_ratio(&self) -> f64 {
+        let non_empty = self.total_segments - self.empty_segments;
+        if non_empty == 0 {
+            0.0
+        } else {
+            self.repeated_segments as f64 / non_empty as f64
+        }
+    }
+}
+
+/// תוצאת תרגום אצווה - תרגום לכל מקטע, לפי סדר הקלט
+#[derive(Debug, Clone)]
+pub struct BatchOutput {
+    pub translations: Vec<String>,
+    pub statistics: BatchStatistics,
+}
+
+/// מתרגם רשימות מקטעים חוזרים (למשל תאי BOM) במקביל, תוך איחוד מקטעים זהים
+pub struct BatchTranslator {
+    translator: Arc<Translator>,
+    workers: usize,
+}
+
+impl BatchTranslator {
+    pub fn new(translator: Arc<Translator>) -> Self {
+        Self {
+            translator,
+            workers: DEFAULT_WORKERS,
+        }
+    }
+
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// תרגום אצווה בזוג שפות קבוע
+    pub fn translate_batch(
+        &self,
+        segments: &[String],
+        source_lang: &str,
+        target_lang: &str,
+    ) -> Result<BatchOutput> {
+        let (source, target) = Translator::resolve_languages(source_lang, target_lang)?;
+        self.run(
+            segments,
+            |text| {
+                self.translator
+                    .translate_detailed(text, source.code(), target.code())
+                    .map(|output| output.text)
+                    .unwrap_or_else(|_| text.to_string())
+            },
+            &CancellationToken::new(),
+            &|_| {},
+        )
+    }
+
+    /// תרגום אצווה עם זיהוי שפה לכל מקטע ייחודי, כמו בתרגום תא בודד
+    pub fn translate_batch_detected(&self, segments: &[String]) -> Result<BatchOutput> {
+        self.translate_batch_detected_with_progress(segments, &CancellationToken::new(), &|_| {})
+    }
+
+    /// כמו `translate_batch_detected`, עם דיווח התקדמות לפי המקטעים הייחודיים;
+    /// מחזיר שגיאה אם בוטל
+    pub fn translate_batch_detected_with_progress(
+        &self,
+        segments: &[String],
+        cancel: &CancellationToken,
+        on_progress: &(dyn Fn(&TranslationProgress) + Sync),
+    ) -> Result<BatchOutput> {
+        self.run(
+            segments,
+            |text| match Language::detect(text) {
+                Some(_) => self.translator
+                    .translate(text)
+                    .unwrap_or_else(|_| text.to_string()),
+                // מספרים, קודים וסימנים נשארים כמות שהם
+                None => text.to_string(),
+            },
+            cancel,
+            on_progress,
+        )
+    }
+
+    fn run<F>(
+        &self,
+        segments: &[String],
+        translate: F,
+        cancel: &CancellationToken,
+        on_progress: &(dyn Fn(&TranslationProgress) + Sync),
+    ) -> Result<BatchOutput>
+    where
+        F: Fn(&str) -> String + Sync,
+    {
+        let mut statistics = BatchStatistics {
+            total_segments: segments.len(),
+            ..Default::default()
+        };
+
+        // איסוף ואיחוד מקטעים זהים לאחר נרמול
+        let mut unique: Vec<String> = Vec::new();
+        let mut unique_index: HashMap<String, usize> = HashMap::new();
+        let mut occurrences: Vec<usize> = Vec::new();
+        let mut slots: Vec<Option<usize>> = Vec::with_capacity(segments.len());
+
+        for segment in segments {
+            let normalized = normalize_segment(segment);
+            if normalized.is_empty() {
+                statistics.empty_segments += 1;
+                slots.push(None);
+                continue;
+            }
+
+            statistics.total_words += normalized.split(' ').count();
+            let index = *unique_index.entry(normalized.clone()).or_insert_with(|| {
+                unique.push(normalized);
+                occurrences.push(0);
+                unique.len() - 1
+            });
+            occurrences[index] += 1;
+            slots.push(Some(index));
+        }
+
+        statistics.unique_segments = unique.len();
+        statistics.repeated_segments = occurrences.iter().map(|count| count - 1).sum();
+        statistics.unique_words = unique.iter().map(|s| s.split(' ').count()).sum();
+
+        let mut repeated: Vec<(String, usize)> = unique
+            .iter()
+            .zip(occurrences.iter())
+            .filter(|(_, count)| **count > 1)
+            .map(|(segment, count)| (segment.clone(), *count))
+            .collect();
+        repeated.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        repeated.truncate(TOP_REPETITIONS);
+        statistics.most_repeated = repeated;
+
+        // תרגום הסט הייחודי במאגר עובדים מוגבל
+        let translated = self.translate_unique(&unique, &translate, cancel, on_progress);
+        if cancel.is_cancelled() {
+            anyhow::bail!("תרגום האצווה בוטל");
+        }
+
+        // פיזור התרגומים חזרה לפי סדר הקלט
+        let translations = segments
+            .iter()
+            .zip(slots)
+            .map(|(segment, slot)| match slot {
+                Some(index) => translated[index].clone(),
+                None => segment.clone(),
+            })
+            .collect();
+
+        Ok(BatchOutput {
+            translations,
+            statistics,
+        })
+    }
+
+    fn translate_unique<F>(
+        &self,
+        unique: &[String],
+        translate: &F,
+        cancel: &CancellationToken,
+        on_progress: &(dyn Fn(&TranslationProgress) + Sync),
+    ) -> Vec<String>
+    where
+        F: Fn(&str) -> String + Sync,
+    {
+        let next = AtomicUsize::new(0);
+        let workers = self.workers.min(unique.len()).max(1);
+        // מקטע שלא תורגם נשאר בטקסט המקור ולא כתא ריק
+        let mut translated = unique.to_vec();
+        let progress = Mutex::new(TranslationProgress {
+            segments_total: unique.len(),
+            words_total: unique.iter().map(|s| s.split(' ').count()).sum(),
+            ..Default::default()
+        });
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        while !cancel.is_cancelled() {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            if index >= unique.len() {
+                                break;
+                            }
+                            let source = &unique[index];
+                            // קריסה בתרגום מקטע אחד אינה מפילה את שאר העבודה
+                            let text = panic::catch_unwind(AssertUnwindSafe(|| translate(source)))
+                                .unwrap_or_else(|_| source.clone());
+                            done.push((index, text));
+
+                            if let Ok(mut progress) = progress.lock() {
+                                progress.segments_done += 1;
+                                progress.words_done += source.split(' ').count();
+                                on_progress(&progress);
+                            }
+                        }
+                        done
+                    })
+                })
+                .collect();
+
+            for handle in handles {
+                match handle.join() {
+                    Ok(done) => {
+                        for (index, text) in done {
+                            translated[index] = text;
+                        }
+                    }
+                    Err(payload) => panic::resume_unwind(payload),
+                }
+            }
+        });
+
+        translated
+    }
+}
+
+/// נרמול מקטע לצורך זיהוי חזרות: רווחים, סימני כיווניות ורווח קשיח
+pub fn normalize_segment(text: &str) -> String {
+    text.chars()
+        .filter(|c| !matches!(c, '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}'))
+        .map(|c| if c == '\u{00A0}' { ' ' } else { c })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::technical_dictionary::TechnicalDictionary;
+    use crate::technical_terms::create_initial_terms;
+    use crate::standards::create_initial_standards;
+    use std::sync::Mutex;
+
+    fn create_batch_translator() -> BatchTranslator {
+        let translator = Translator::new(
+            Arc::new(create_initial_terms()),
+            Arc::new(create_initial_standards()),
+            Arc::new(Mutex::new(TechnicalDictionary::in_memory())),
+        );
+        BatchTranslator::new(Arc::new(translator)).with_workers(2)
+    }
+
+    #[test]
+    fn test_normalize_segment() {
+        assert_eq!(normalize_segment("  ראש   ספרינקלר\u{200F} "), "ראש ספרינקלר");
+        assert_eq!(normalize_segment("לחץ\u{00A0}עבודה"), "לחץ עבודה");
+    }
+
+    #[test]
+    fn test_batch_deduplication() {
+        let batch = create_batch_translator();
+        let segments: Vec<String> = vec!["ראש ספרינקלר", "", "ראש  ספרינקלר", "לחץ עבודה", "ראש ספרינקלר"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let output = batch.translate_batch(&segments, "he", "ru").unwrap();
+
+        assert_eq!(output.translations.len(), 5);
+        assert_eq!(output.translations[0], "ороситель/спринклер");
+        assert_eq!(output.translations[1], "");
+        assert_eq!(output.translations[2], output.translations[0]);
+        assert_eq!(output.translations[3], "рабочее давление");
+
+        let stats = &output.statistics;
+        assert_eq!(stats.empty_segments, 1);
+        assert_eq!(stats.unique_segments, 2);
+        assert_eq!(stats.repeated_segments, 2);
+        assert_eq!(stats.most_repeated, vec![("ראש ספרינקלר".to_string(), 3)]);
+        assert!((stats.repetition_ratio() - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_batch_progress_and_cancellation() {
+        let batch = create_batch_translator();
+        let segments: Vec<String> = vec!["תקין", "תקין", "לחץ עבודה"].into_iter().map(String::from).collect();
+
+        let updates = Mutex::new(Vec::new());
+        batch
+            .translate_batch_detected_with_progress(&segments, &CancellationToken::new(), &|progress| {
+                updates.lock().unwrap().push(progress.clone());
+            })
+            .unwrap();
+        let updates = updates.into_inner().unwrap();
+        assert_eq!(updates.len(), 2);
+        assert!(updates[1].is_finished());
+        assert_eq!(updates[1].words_done, 3);
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        assert!(batch.translate_batch_detected_with_progress(&segments, &cancel, &|_| {}).is_err());
+    }
+
+    #[test]
+    fn test_panicking_segment_keeps_source() {
+        let batch = create_batch_translator();
+        let segments: Vec<String> = vec!["תקין", "קורס"].into_iter().map(String::from).collect();
+
+        let output = batch
+            .run(
+                &segments,
+                |text| {
+                    if text == "קורס" {
+                        panic!("שגיאה במקטע");
+                    }
+                    text.to_uppercase()
+                },
+                &CancellationToken::new(),
+                &|_| {},
+            )
+            .unwrap();
+        assert_eq!(output.translations[1], "קורס");
+    }
+}
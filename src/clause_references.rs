@@ -0,0 +1,429 @@
+use crate::language::Language;
+use crate::standards::{StandardCitation, StandardsDatabase};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Serialize, Deserialize};
+
+// מספר המילים המרבי בין הפניה לסעיף לבין ציון התקן שאחריה
+const MAX_CONNECTOR_WORDS: usize = 3;
+
+// אותיות נספחים לפי הסדר בכל שפה; ברוסית מדלגים על Ё, З, Й, О, Ч, Ъ, Ы, Ь (ГОСТ 2.105)
+const HEBREW_LETTERS: &[&str] = &["א", "ב", "ג", "ד", "ה", "ו", "ז", "ח", "ט", "י"];
+const RUSSIAN_LETTERS: &[&str] = &["А", "Б", "В", "Г", "Д", "Е", "Ж", "И", "К", "Л"];
+const ENGLISH_LETTERS: &[&str] = &["A", "B", "C", "D", "E", "F", "G", "H", "I", "J"];
+
+lazy_static! {
+    // "בהתאם לסעיף 5.2.1", "בטבלה 3", "נספח א'"
+    static ref HEBREW_PART: Regex = Regex::new(
+        r#"\b[ובלמש]{0,2}(סעיפים|סעיף|ס["״]ק|טבלה|טבלת|איור|תרשים|נספח)\s+(\d+(?:\.\d+)*|[א-ת](?:['׳]|\b))"#
+    ).unwrap();
+    // "согласно п. 5.2.1", "табл. 3", "приложение А"
+    static ref RUSSIAN_PART: Regex = Regex::new(
+        r"\b((?i:пп?\.|подпункт[а-я]*|пункт[а-я]*|раздел[а-я]*|табл\.|таблиц[а-я]*|рис\.|рисун[а-я]*|прил\.|приложени[а-я]*))\s*(\d+(?:\.\d+)*|[А-Я]\b)"
+    ).unwrap();
+    // "clause 5.2.1", "§ 5.2", "Table 3", "Annex A"
+    static ref ENGLISH_PART: Regex = Regex::new(
+        r"(?i)(§|\b(?:clause|section|table|figure|fig\.|annex|appendix))\s*(\d+(?:\.\d+)*|[A-Z]\b)"
+    ).unwrap();
+}
+
+/// סוג הרכיב שאליו מפנים בתקן
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceKind {
+    Clause,
+    Table,
+    Figure,
+    Annex,
+}
+
+/// הפניה מובנית לרכיב בתקן: "סעיף 5.2.1 בתקן NFPA 13"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClauseReference {
+    pub kind: ReferenceKind,
+    /// מספר הסעיף, הטבלה או האיור; בנספחים - האות בשפת המקור
+    pub number: String,
+    /// התקן המצוטט, כולל המהדורה אם צוינה
+    pub standard: Option<StandardCitation>,
+    pub text: String,
+    /// מיקום בבתים בטקסט
+    pub start: usize,
+    pub end: usize,
+}
+
+/// אופן הצגת כותרת הסעיף מתוך מסד התקנים
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TitleMode {
+    #[default]
+    Omit,
+    /// בסוגריים אחרי ההפניה
+    Inline,
+    /// כהערת שוליים ממוספרת
+    Footnote,
+}
+
+/// הפניה מוכנה להצגה בשפת היעד; הכותרת משמשת גם כחלונית עזר בממשק
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RenderedReference {
+    pub text: String,
+    pub title: Option<String>,
+}
+
+/// טקסט שבו ההפניות הוחלפו בצורתן בשפת היעד
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LocalizedReferences {
+    pub text: String,
+    pub references: Vec<ClauseReference>,
+    pub footnotes: Vec<String>,
+}
+
+/// איתור הפניות לסעיפים, טבלאות, איורים ונספחים, וקישורן לתקן המצוטט
+pub fn parse_references(text: &str, lang: Language, standards: &StandardsDatabase) -> Vec<ClauseReference> {
+    let pattern: &Regex = match lang {
+        Language::Hebrew => &HEBREW_PART,
+        Language::Russian => &RUSSIAN_PART,
+        Language::English => &ENGLISH_PART,
+    };
+    let citations = standards.find_citations(text);
+
+    let mut references: Vec<ClauseReference> = pattern
+        .captures_iter(text)
+        .filter_map(|captures| {
+            let whole = captures.get(0)?;
+            Some(ClauseReference {
+                kind: reference_kind(&captures[1], lang),
+                number: captures[2].trim_end_matches(|c| c == '\'' || c == '׳').to_string(),
+                standard: None,
+                text: whole.as_str().to_string(),
+                start: whole.start(),
+                end: whole.end(),
+            })
+        })
+        .collect();
+
+    // התקן אחרי ההפניה: "סעיף 5.2.1 בתקן NFPA 13", "п. 5.2.1 СП 5.13130"
+    let mut bound_citations = Vec::new();
+    for reference in references.iter_mut() {
+        let following = citations
+            .iter()
+            .find(|c| c.start >= reference.end && is_connector_gap(&text[reference.end..c.start], lang));
+        if let Some(citation) = following {
+            reference.end = citation.end;
+            reference.text = text[reference.start..reference.end].to_string();
+            reference.standard = Some(citation.clone());
+            bound_citations.push(citation.start);
+        }
+    }
+
+    // או לפניה, כשהתקן אינו שייך כבר להפניה אחרת: "NFPA 13, clause 5.2.1"
+    for reference in references.iter_mut().filter(|r| r.standard.is_none()) {
+        let preceding = citations.iter().rev().find(|c| {
+            !bound_citations.contains(&c.start)
+                && c.end <= reference.start
+                && text[c.end..reference.start].trim().trim_matches(',').trim().is_empty()
+        });
+        if let Some(citation) = preceding {
+            reference.start = citation.start;
+            reference.text = text[reference.start..reference.end].to_string();
+            reference.standard = Some(citation.clone());
+        }
+    }
+
+    // "סעיף 5.2.1 וטבלה 3 בתקן NFPA 13" - התקן משותף להפניות המחוברות
+    for index in (0..references.len().saturating_sub(1)).rev() {
+        if references[index].standard.is_some() {
+            continue;
+        }
+        let next = &references[index + 1];
+        if next.standard.is_some() && is_conjunction_gap(&text[references[index].end..next.start]) {
+            references[index].standard = next.standard.clone();
+        }
+    }
+
+    references
+}
+
+/// הצגת הפניה בשפת היעד לפי המוסכמות של כל שפה
+pub fn render_reference(reference: &ClauseReference, target: Language, standards: &StandardsDatabase) -> RenderedReference {
+    let number = match reference.kind {
+        ReferenceKind::Annex => annex_letter(&reference.number, target),
+        _ => reference.number.clone(),
+    };
+    let label = kind_label(reference.kind, target);
+
+    let text = match reference.standard.as_ref().map(|c| standard_designation(c, target, standards)) {
+        Some(designation) => match target {
+            Language::Hebrew => format!("{} {} בתקן {}", label, number, designation),
+            Language::Russian => format!("{} {} {}", label, number, designation),
+            Language::English => format!("{} {} of {}", label, number, designation),
+        },
+        None => format!("{} {}", label, number),
+    };
+
+    let title = match (&reference.standard, reference.kind) {
+        (Some(citation), ReferenceKind::Clause) => standards
+            .clause_title(&citation.code, &reference.number, target)
+            .map(str::to_string),
+        _ => None,
+    };
+
+    RenderedReference { text, title }
+}
+
+/// החלפת כל ההפניות בטקסט בצורתן בשפת היעד, עם כותרות הסעיפים לפי הבחירה
+pub fn localize_references(
+    text: &str,
+    source: Language,
+    target: Language,
+    standards: &StandardsDatabase,
+    titles: TitleMode,
+) -> LocalizedReferences {
+    let references = parse_references(text, source, standards);
+    let mut result = LocalizedReferences::default();
+    let mut cursor = 0;
+
+    for reference in &references {
+        if reference.start < cursor {
+            continue;
+        }
+        // תקן משותף מוצג פעם אחת, בהפניה שבה הוא כתוב
+        let rendered = match &reference.standard {
+            Some(citation) if citation.start < reference.start || citation.end > reference.end => {
+                render_reference(&ClauseReference { standard: None, ..reference.clone() }, target, standards)
+            }
+            _ => render_reference(reference, target, standards),
+        };
+        result.text.push_str(&text[cursor..reference.start]);
+        result.text.push_str(&rendered.text);
+
+        if let Some(title) = rendered.title {
+            match titles {
+                TitleMode::Omit => {}
+                TitleMode::Inline => result.text.push_str(&format!(" ({})", quote_title(&title, target))),
+                TitleMode::Footnote => {
+                    result.footnotes.push(title);
+                    result.text.push_str(&format!("[{}]", result.footnotes.len()));
+                }
+            }
+        }
+        cursor = reference.end;
+    }
+    result.text.push_str(&text[cursor..]);
+    result.references = references;
+    result
+}
+
+/// מספור מחדש של `count` סימוני הערות השוליים של מקטע, כשלפניו כבר `offset` הערות במסמך
+pub fn renumber_footnotes(text: &str, count: usize, offset: usize) -> String {
+    if offset == 0 {
+        return text.to_string();
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    for number in 1..=count {
+        let marker = format!("[{}]", number);
+        let Some(position) = rest.find(&marker) else {
+            break;
+        };
+        result.push_str(&rest[..position]);
+        result.push_str(&format!("[{}]", number + offset));
+        rest = &rest[position + marker.len()..];
+    }
+    result.push_str(rest);
+    result
+}
+
+fn reference_kind(keyword: &str, lang: Language) -> ReferenceKind {
+    let keyword = keyword.to_lowercase();
+    match lang {
+        Language::Hebrew => {
+            if keyword.starts_with("טבל") {
+                ReferenceKind::Table
+            } else if keyword.starts_with("איור") || keyword.starts_with("תרשים") {
+                ReferenceKind::Figure
+            } else if keyword.starts_with("נספח") {
+                ReferenceKind::Annex
+            } else {
+                ReferenceKind::Clause
+            }
+        }
+        Language::Russian => {
+            if keyword.starts_with("табл") {
+                ReferenceKind::Table
+            } else if keyword.starts_with("рис") {
+                ReferenceKind::Figure
+            } else if keyword.starts_with("прил") {
+                ReferenceKind::Annex
+            } else {
+                ReferenceKind::Clause
+            }
+        }
+        Language::English => {
+            if keyword.starts_with("table") {
+                ReferenceKind::Table
+            } else if keyword.starts_with("fig") {
+                ReferenceKind::Figure
+            } else if keyword.starts_with("annex") || keyword.starts_with("appendix") {
+                ReferenceKind::Annex
+            } else {
+                ReferenceKind::Clause
+            }
+        }
+    }
+}
+
+fn kind_label(kind: ReferenceKind, lang: Language) -> &'static str {
+    match (lang, kind) {
+        (Language::Hebrew, ReferenceKind::Clause) => "סעיף",
+        (Language::Hebrew, ReferenceKind::Table) => "טבלה",
+        (Language::Hebrew, ReferenceKind::Figure) => "איור",
+        (Language::Hebrew, ReferenceKind::Annex) => "נספח",
+        (Language::Russian, ReferenceKind::Clause) => "п.",
+        (Language::Russian, ReferenceKind::Table) => "табл.",
+        (Language::Russian, ReferenceKind::Figure) => "рис.",
+        (Language::Russian, ReferenceKind::Annex) => "прил.",
+        (Language::English, ReferenceKind::Clause) => "clause",
+        (Language::English, ReferenceKind::Table) => "Table",
+        (Language::English, ReferenceKind::Figure) => "Figure",
+        (Language::English, ReferenceKind::Annex) => "Annex",
+    }
+}
+
+// אות הנספח לפי מיקומה בסדר האותיות: נספח ב' ← прил. Б ← Annex B
+fn annex_letter(letter: &str, target: Language) -> String {
+    let upper = letter.to_uppercase();
+    let position = [HEBREW_LETTERS, RUSSIAN_LETTERS, ENGLISH_LETTERS]
+        .iter()
+        .find_map(|letters| letters.iter().position(|l| *l == upper));
+
+    match (position, target) {
+        (Some(position), Language::Hebrew) => format!("{}'", HEBREW_LETTERS[position]),
+        (Some(position), Language::Russian) => RUSSIAN_LETTERS[position].to_string(),
+        (Some(position), Language::English) => ENGLISH_LETTERS[position].to_string(),
+        (None, _) => letter.to_string(),
+    }
+}
+
+// ציון תקן ישראלי בטקסט שאינו עברי: הכינוי הראשון ללא אותיות עבריות ("SI 1596")
+fn standard_designation(citation: &StandardCitation, target: Language, standards: &StandardsDatabase) -> String {
+    let is_hebrew = |text: &str| text.chars().any(|c| ('\u{05D0}'..='\u{05EA}').contains(&c));
+    if target == Language::Hebrew || !is_hebrew(&citation.text) {
+        return citation.text.clone();
+    }
+
+    let designation = standards
+        .get_standard(&citation.code)
+        .and_then(|standard| standard.aliases.iter().find(|alias| !is_hebrew(alias)).cloned())
+        .unwrap_or_else(|| citation.code.clone());
+    match citation.year {
+        Some(year) => format!("{}:{}", designation, year),
+        None => designation,
+    }
+}
+
+fn quote_title(title: &str, lang: Language) -> String {
+    match lang {
+        Language::Russian => format!("«{}»", title),
+        _ => format!("\"{}\"", title),
+    }
+}
+
+fn is_connector_gap(gap: &str, lang: Language) -> bool {
+    let connectors: &[&str] = match lang {
+        // "ב" ו"ל" הן אותיות שימוש שנפרדו מציון התקן: "בת"י 1596"
+        Language::Hebrew => &["ב", "ל", "בתקן", "לתקן", "התקן", "תקן", "של", "שבתקן", "מתקן"],
+        Language::Russian => &["в", "из", "по", "стандарта", "свода", "правил"],
+        Language::English => &["of", "in", "the", "to"],
+    };
+    let words: Vec<String> = gap
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect();
+    words.len() <= MAX_CONNECTOR_WORDS && words.iter().all(|w| connectors.contains(&w.as_str()))
+}
+
+fn is_conjunction_gap(gap: &str) -> bool {
+    let gap = gap.trim().trim_matches(',').trim();
+    gap.is_empty() || matches!(gap.to_lowercase().as_str(), "и" | "and" | "or" | "или" | "או")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standards::{create_initial_standards, Standard, StandardClause};
+
+    fn standards() -> StandardsDatabase {
+        let mut db = create_initial_standards();
+        db.add_standard(Standard {
+            code: "SP5.13130".to_string(),
+            name_he: "קובץ כללים 5.13130".to_string(),
+            name_ru: "СП 5.13130".to_string(),
+            country: "רוסיה".to_string(),
+            description_he: None,
+            description_ru: None,
+            related_standards: Vec::new(),
+            issuing_body: None,
+            aliases: vec!["СП 5.13130".to_string()],
+            editions: Vec::new(),
+            clauses: vec![StandardClause {
+                number: "5.2.1".to_string(),
+                title_he: Some("דרישות כלליות".to_string()),
+                title_ru: Some("Общие требования".to_string()),
+                title_en: Some("General requirements".to_string()),
+            }],
+        });
+        db
+    }
+
+    #[test]
+    fn test_parse_hebrew_reference() {
+        let db = standards();
+        let references = parse_references("בהתאם לסעיף 5.2.1 בתקן NFPA 13:2019", Language::Hebrew, &db);
+
+        assert_eq!(references.len(), 1);
+        let reference = &references[0];
+        assert_eq!(reference.kind, ReferenceKind::Clause);
+        assert_eq!(reference.number, "5.2.1");
+        assert_eq!(reference.text, "לסעיף 5.2.1 בתקן NFPA 13:2019");
+        let citation = reference.standard.as_ref().unwrap();
+        assert_eq!((citation.code.as_str(), citation.year), ("NFPA13", Some(2019)));
+    }
+
+    #[test]
+    fn test_shared_standard_and_annex() {
+        let db = standards();
+        let references = parse_references("см. табл. 3 и п. 5.2.1 СП 5.13130, прил. Б", Language::Russian, &db);
+
+        assert_eq!(references.len(), 3);
+        assert_eq!(references[0].kind, ReferenceKind::Table);
+        assert_eq!(references[0].standard.as_ref().unwrap().code, "SP5.13130");
+        assert_eq!(references[2].kind, ReferenceKind::Annex);
+        assert_eq!(render_reference(&references[2], Language::Hebrew, &db).text, "נספח ב'");
+    }
+
+    #[test]
+    fn test_localize_with_titles() {
+        let db = standards();
+
+        let localized = localize_references("согласно п. 5.2.1 СП 5.13130", Language::Russian, Language::Hebrew, &db, TitleMode::Footnote);
+        assert_eq!(localized.text, "согласно סעיף 5.2.1 בתקן СП 5.13130[1]");
+        assert_eq!(localized.footnotes, vec!["דרישות כלליות".to_string()]);
+
+        let localized = localize_references("לפי סעיף 5.2.1 בתקן СП 5.13130", Language::Hebrew, Language::English, &db, TitleMode::Inline);
+        assert_eq!(localized.text, "לפי clause 5.2.1 of СП 5.13130 (\"General requirements\")");
+
+        let rendered = localize_references("טבלה 2 בת\"י 1596", Language::Hebrew, Language::Russian, &db, TitleMode::Omit);
+        assert_eq!(rendered.text, "табл. 2 SI 1596");
+    }
+
+    #[test]
+    fn test_annex_letter_is_uppercase() {
+        let db = standards();
+        let references = parse_references("прил. Б и раздел в целом", Language::Russian, &db);
+        assert_eq!(references.len(), 1);
+        assert_eq!(references[0].number, "Б");
+
+        assert_eq!(renumber_footnotes("п. 5.2.1[1], табл. 3[2]", 2, 3), "п. 5.2.1[4], табл. 3[5]");
+    }
+}
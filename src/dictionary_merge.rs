@@ -0,0 +1,345 @@
+//! מיזוג תלת-כיווני של מילונים ברמת השדה, מול הגרסה המשותפת האחרונה.
+//! שינויים בשדות שונים של אותו מונח מתמזגים אוטומטית; שינוי שונה באותו שדה הופך לסתירה
+//! שנפתרת באחד מסוגי `ResolutionType`.
+
+use std::collections::{BTreeSet, HashMap};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::knowledge_sharing::ResolutionType;
+use crate::technical_dictionary::TechnicalTerm;
+
+/// השדות שמתמזגים; מונה השימוש, ציון האמון וזמן העדכון אינם תוכן ואינם מתמזגים
+pub const MERGE_FIELDS: &[&str] = &[
+    "target",
+    "english",
+    "domain",
+    "context",
+    "usage_examples",
+    "variants",
+    "definition",
+    "notes",
+    "metadata.verified",
+    "metadata.source_references",
+];
+
+/// שם השדה בסתירה על המונח כולו: נמחק בצד אחד ושונה בצד השני
+pub const WHOLE_TERM: &str = "term";
+
+/// סתירה בשדה אחד של מונח; ערך חסר פירושו שהמונח אינו קיים באותו צד
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldConflict {
+    pub conflict_id: String,
+    pub term_id: String,
+    pub field: String,
+    /// הערך בגרסה המשותפת
+    pub ancestor: Option<Value>,
+    /// הערך במילון שאליו ממזגים
+    pub ours: Option<Value>,
+    /// הערך במילון הממוזג
+    pub theirs: Option<Value>,
+    pub detected_at: DateTime<Utc>,
+}
+
+impl FieldConflict {
+    /// הערך שנבחר לפי סוג הפתרון:
+    /// `KeepBase` שומר את הערך במילון שאליו ממזגים, `AcceptChanges` מקבל את הערך הממוזג,
+    /// `Merge` מאחד רשימות משני הצדדים, ו-`Custom` משתמש בערך שהמשתמש סיפק.
+    /// `None` פירושו שהמונח צריך להימחק.
+    pub fn resolve(&self, resolution: &ResolutionType, custom: Option<Value>) -> Result<Option<Value>> {
+        match resolution {
+            ResolutionType::KeepBase => Ok(self.ours.clone()),
+            ResolutionType::AcceptChanges => Ok(self.theirs.clone()),
+            ResolutionType::Merge => match (&self.ours, &self.theirs) {
+                (Some(Value::Array(ours)), Some(Value::Array(theirs))) => {
+                    let mut merged = ours.clone();
+                    merged.extend(theirs.iter().filter(|item| !ours.contains(item)).cloned());
+                    Ok(Some(Value::Array(merged)))
+                }
+                _ => anyhow::bail!("לא ניתן לאחד את השדה \"{}\" של \"{}\": רק רשימות ניתנות לאיחוד", self.field, self.term_id),
+            },
+            ResolutionType::Custom => custom
+                .map(Some)
+                .with_context(|| format!("חסר ערך לפתרון ידני של \"{}\" ב-\"{}\"", self.field, self.term_id)),
+        }
+    }
+}
+
+/// שינוי שהמיזוג מחיל על מונח במילון שאליו ממזגים
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MergedField {
+    pub term_id: String,
+    pub field: String,
+    pub old_value: Option<Value>,
+    pub new_value: Option<Value>,
+}
+
+/// תוצאת המיזוג: מונחים לכתיבה, מונחים למחיקה, השינויים שהוחלו והסתירות שנותרו
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ThreeWayMerge {
+    pub updated_terms: Vec<TechnicalTerm>,
+    pub removed_terms: Vec<String>,
+    pub applied: Vec<MergedField>,
+    pub conflicts: Vec<FieldConflict>,
+}
+
+impl ThreeWayMerge {
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+}
+
+/// מיזוג `theirs` לתוך `ours` מול הגרסה המשותפת `ancestor`
+pub fn merge_terms(
+    ancestor: &HashMap<String, TechnicalTerm>,
+    ours: &[TechnicalTerm],
+    theirs: &[TechnicalTerm],
+) -> Result<ThreeWayMerge> {
+    let ours: HashMap<&str, &TechnicalTerm> = ours.iter().map(|term| (term.source.as_str(), term)).collect();
+    let theirs: HashMap<&str, &TechnicalTerm> = theirs.iter().map(|term| (term.source.as_str(), term)).collect();
+    let ids: BTreeSet<&str> = ancestor
+        .keys()
+        .map(String::as_str)
+        .chain(ours.keys().copied())
+        .chain(theirs.keys().copied())
+        .collect();
+
+    let mut merge = ThreeWayMerge::default();
+    let now = Utc::now();
+
+    for id in ids {
+        let base = ancestor.get(id).map(to_json).transpose()?;
+        let mine = ours.get(id).copied().map(to_json).transpose()?;
+        let other = theirs.get(id).copied().map(to_json).transpose()?;
+
+        match (&base, &mine, &other) {
+            // אין מה למזג: רק אצלנו, או נמחק בשני הצדדים
+            (None, Some(_), None) | (None, None, None) | (Some(_), None, None) => {}
+            // מונח חדש אצלם
+            (None, None, Some(_)) => {
+                let term = theirs[id].clone();
+                merge.applied.push(MergedField {
+                    term_id: id.to_string(),
+                    field: WHOLE_TERM.to_string(),
+                    old_value: None,
+                    new_value: Some(term_field(&term, WHOLE_TERM)?),
+                });
+                merge.updated_terms.push(term);
+            }
+            // נמחק בצד אחד: המחיקה מתקבלת רק אם הצד השני לא שינה את המונח
+            (Some(base_value), None, Some(other_value)) => {
+                if !same_content(base_value, other_value) {
+                    merge.conflicts.push(conflict(id, WHOLE_TERM, base.clone(), None, other.clone(), now));
+                }
+            }
+            (Some(base_value), Some(mine_value), None) => {
+                if same_content(base_value, mine_value) {
+                    merge.removed_terms.push(id.to_string());
+                    merge.applied.push(MergedField {
+                        term_id: id.to_string(),
+                        field: WHOLE_TERM.to_string(),
+                        old_value: Some(term_field(ours[id], WHOLE_TERM)?),
+                        new_value: None,
+                    });
+                } else {
+                    merge.conflicts.push(conflict(id, WHOLE_TERM, base.clone(), mine.clone(), None, now));
+                }
+            }
+            // קיים בשני הצדדים: מיזוג שדה אחר שדה; מונח שנוסף בשני הצדדים נחשב כבעל בסיס ריק
+            (_, Some(mine_value), Some(other_value)) => {
+                let mut merged = mine_value.clone();
+                let mut changed = false;
+                for field in MERGE_FIELDS {
+                    let base_field = base.as_ref().map(|value| field_value(value, field));
+                    let mine_field = field_value(mine_value, field);
+                    let other_field = field_value(other_value, field);
+
+                    if mine_field == other_field || base_field.as_ref() == Some(&other_field) {
+                        continue;
+                    }
+                    if base_field.as_ref() == Some(&mine_field) {
+                        set_field(&mut merged, field, other_field.clone())?;
+                        merge.applied.push(MergedField {
+                            term_id: id.to_string(),
+                            field: field.to_string(),
+                            old_value: Some(mine_field),
+                            new_value: Some(other_field),
+                        });
+                        changed = true;
+                    } else {
+                        merge.conflicts.push(conflict(id, field, base_field, Some(mine_field), Some(other_field), now));
+                    }
+                }
+                if changed {
+                    let mut term: TechnicalTerm = serde_json::from_value(merged)?;
+                    term.metadata.last_updated = now;
+                    merge.updated_terms.push(term);
+                }
+            }
+        }
+    }
+
+    Ok(merge)
+}
+
+/// החלת ערך שנבחר בפתרון סתירה על המונח; `None` במקום מונח פירושו שהמונח אינו קיים
+pub fn apply_resolution(
+    conflict: &FieldConflict,
+    current: Option<TechnicalTerm>,
+    value: Option<Value>,
+) -> Result<Option<TechnicalTerm>> {
+    if conflict.field == WHOLE_TERM {
+        return value.map(|value| Ok(serde_json::from_value(value)?)).transpose();
+    }
+    let term = current.with_context(|| format!("המונח \"{}\" לא נמצא במילון", conflict.term_id))?;
+    let mut json = to_json(&term)?;
+    set_field(&mut json, &conflict.field, value.unwrap_or(Value::Null))?;
+    let mut term: TechnicalTerm = serde_json::from_value(json)
+        .with_context(|| format!("הערך שנבחר לשדה \"{}\" אינו תקין", conflict.field))?;
+    term.metadata.last_updated = Utc::now();
+    Ok(Some(term))
+}
+
+/// העתקת שדות התוכן של `source` אל `term`; נתוני השימוש של `term` נשמרים
+pub fn copy_content(term: &TechnicalTerm, source: &TechnicalTerm) -> Result<TechnicalTerm> {
+    let (mut json, source) = (to_json(term)?, to_json(source)?);
+    for field in MERGE_FIELDS {
+        set_field(&mut json, field, field_value(&source, field))?;
+    }
+    Ok(serde_json::from_value(json)?)
+}
+
+/// ערך השדה במונח, לרישום בהיסטוריה; עבור המונח כולו - התרגום
+pub fn term_field(term: &TechnicalTerm, field: &str) -> Result<Value> {
+    if field == WHOLE_TERM {
+        return Ok(Value::String(term.target.clone()));
+    }
+    Ok(field_value(&to_json(term)?, field))
+}
+
+fn conflict(
+    term_id: &str,
+    field: &str,
+    ancestor: Option<Value>,
+    ours: Option<Value>,
+    theirs: Option<Value>,
+    detected_at: DateTime<Utc>,
+) -> FieldConflict {
+    FieldConflict {
+        conflict_id: uuid::Uuid::new_v4().to_string(),
+        term_id: term_id.to_string(),
+        field: field.to_string(),
+        ancestor,
+        ours,
+        theirs,
+        detected_at,
+    }
+}
+
+fn to_json(term: &TechnicalTerm) -> Result<Value> {
+    serde_json::to_value(term).context("המרת מונח למיזוג נכשלה")
+}
+
+// "metadata.verified" -> "/metadata/verified"
+fn pointer(field: &str) -> String {
+    format!("/{}", field.replace('.', "/"))
+}
+
+fn field_value(term: &Value, field: &str) -> Value {
+    term.pointer(&pointer(field)).cloned().unwrap_or(Value::Null)
+}
+
+fn set_field(term: &mut Value, field: &str, value: Value) -> Result<()> {
+    let slot = term
+        .pointer_mut(&pointer(field))
+        .with_context(|| format!("שדה לא מוכר במיזוג: {}", field))?;
+    *slot = value;
+    Ok(())
+}
+
+// השוואה בשדות התוכן בלבד, כך ששימוש במונח אינו נחשב לשינוי
+fn same_content(a: &Value, b: &Value) -> bool {
+    MERGE_FIELDS.iter().all(|field| field_value(a, field) == field_value(b, field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn term(source: &str, target: &str) -> TechnicalTerm {
+        TechnicalTerm::for_test(source, target, "general")
+    }
+
+    fn ancestor(terms: &[TechnicalTerm]) -> HashMap<String, TechnicalTerm> {
+        terms.iter().map(|term| (term.source.clone(), term.clone())).collect()
+    }
+
+    #[test]
+    fn test_non_overlapping_fields_merge() {
+        let base = term("מגוף", "задвижка");
+        let mut ours = base.clone();
+        ours.domain = "plumbing".to_string();
+        let mut theirs = base.clone();
+        theirs.english = Some("Gate Valve".to_string());
+        theirs.metadata.usage_count = 7;
+
+        let merge = merge_terms(&ancestor(&[base]), &[ours], &[theirs]).unwrap();
+        assert!(!merge.has_conflicts());
+        assert_eq!(merge.updated_terms.len(), 1);
+        let merged = &merge.updated_terms[0];
+        assert_eq!(merged.domain, "plumbing");
+        assert_eq!(merged.english.as_deref(), Some("Gate Valve"));
+        // מונה השימוש אינו שדה תוכן
+        assert_eq!(merged.metadata.usage_count, 0);
+    }
+
+    #[test]
+    fn test_same_field_conflict_and_resolution() {
+        let base = term("מגוף", "задвижка");
+        let mut ours = base.clone();
+        ours.target = "запорный клапан".to_string();
+        ours.notes = vec!["ГОСТ 5762".to_string()];
+        let mut theirs = base.clone();
+        theirs.target = "шибер".to_string();
+        theirs.notes = vec!["NFPA 13".to_string()];
+
+        let merge = merge_terms(&ancestor(&[base]), &[ours.clone()], &[theirs]).unwrap();
+        assert_eq!(merge.conflicts.len(), 2);
+        let target = merge.conflicts.iter().find(|c| c.field == "target").unwrap();
+        let notes = merge.conflicts.iter().find(|c| c.field == "notes").unwrap();
+
+        let value = target.resolve(&ResolutionType::AcceptChanges, None).unwrap();
+        let resolved = apply_resolution(target, Some(ours.clone()), value).unwrap().unwrap();
+        assert_eq!(resolved.target, "шибер");
+
+        let value = notes.resolve(&ResolutionType::Merge, None).unwrap();
+        let resolved = apply_resolution(notes, Some(resolved), value).unwrap().unwrap();
+        assert_eq!(resolved.notes, vec!["ГОСТ 5762".to_string(), "NFPA 13".to_string()]);
+
+        assert!(target.resolve(&ResolutionType::Merge, None).is_err());
+        assert!(target.resolve(&ResolutionType::Custom, None).is_err());
+    }
+
+    #[test]
+    fn test_additions_and_deletions() {
+        let kept = term("ברז", "кран");
+        let edited = term("מגוף", "задвижка");
+        let mut edited_theirs = edited.clone();
+        edited_theirs.definition = Some("אביזר סגירה".to_string());
+
+        // אצלנו: "ברז" נמחק ו"מגוף" נמחק; אצלם: "מגוף" שונה ו"צנרת" נוספה
+        let merge = merge_terms(
+            &ancestor(&[kept.clone(), edited]),
+            &[],
+            &[kept, edited_theirs, term("צנרת", "трубопровод")],
+        )
+        .unwrap();
+
+        assert_eq!(merge.updated_terms.len(), 1);
+        assert_eq!(merge.updated_terms[0].source, "צנרת");
+        assert_eq!(merge.conflicts.len(), 1);
+        assert_eq!(merge.conflicts[0].field, WHOLE_TERM);
+        assert!(merge.conflicts[0].ours.is_none());
+        assert!(merge.removed_terms.is_empty());
+    }
+}
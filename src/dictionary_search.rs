@@ -0,0 +1,360 @@
+//! חיפוש במילון הטכני: התאמה לפי תחילית, תת-מחרוזת או תבנית, סינון, מיון ודפדוף
+
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use crate::language::{Language, Multilingual};
+use crate::morphology::utils::{normalize_final_letters, strip_niqqud};
+use crate::standards::{create_initial_standards, StandardsDatabase};
+use crate::technical_dictionary::TechnicalTerm;
+
+lazy_static! {
+    // מאגר התקנים המובנה, לזיהוי ציוני תקן בהערות המונחים
+    static ref KNOWN_STANDARDS: StandardsDatabase = create_initial_standards();
+}
+
+/// גודל עמוד ברירת המחדל
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+/// גודל העמוד המרבי, כדי ששאילתה אחת לא תחזיר את כל המילון
+pub const MAX_PAGE_SIZE: usize = 500;
+
+/// אופן התאמת טקסט השאילתה לצורות המונח
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchMode {
+    Exact,
+    Prefix,
+    #[default]
+    Substring,
+    /// `*` לרצף תווים כלשהו ו-`?` לתו אחד
+    Wildcard,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortField {
+    #[default]
+    Source,
+    Target,
+    Domain,
+    LastUpdated,
+    Confidence,
+    UsageCount,
+}
+
+/// שאילתה על המילון; שדה ריק אינו מסנן
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TermQuery {
+    pub text: String,
+    pub mode: MatchMode,
+    /// השפה שבה מחפשים; ללא ערך - בכל השפות
+    pub lang: Option<Language>,
+    pub domain: Option<String>,
+    /// ציון של תקן ממאגר התקנים המובנה שהמונח מפנה אליו, בכל צורת כתיבה: "NFPA 13", "ГОСТ 3262"
+    pub standard: Option<String>,
+    pub verified: Option<bool>,
+    pub updated_after: Option<DateTime<Utc>>,
+    pub updated_before: Option<DateTime<Utc>>,
+    pub sort: SortField,
+    pub descending: bool,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl Default for TermQuery {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            mode: MatchMode::default(),
+            lang: None,
+            domain: None,
+            standard: None,
+            verified: None,
+            updated_after: None,
+            updated_before: None,
+            sort: SortField::default(),
+            descending: false,
+            offset: 0,
+            limit: DEFAULT_PAGE_SIZE,
+        }
+    }
+}
+
+impl TermQuery {
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_mode(mut self, mode: MatchMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_lang(mut self, lang: Language) -> Self {
+        self.lang = Some(lang);
+        self
+    }
+
+    pub fn with_domain(mut self, domain: &str) -> Self {
+        self.domain = Some(domain.to_string());
+        self
+    }
+
+    pub fn with_standard(mut self, standard: &str) -> Self {
+        self.standard = Some(standard.to_string());
+        self
+    }
+
+    pub fn with_verified(mut self, verified: bool) -> Self {
+        self.verified = Some(verified);
+        self
+    }
+
+    pub fn with_date_range(mut self, after: Option<DateTime<Utc>>, before: Option<DateTime<Utc>>) -> Self {
+        self.updated_after = after;
+        self.updated_before = before;
+        self
+    }
+
+    pub fn with_sort(mut self, sort: SortField, descending: bool) -> Self {
+        self.sort = sort;
+        self.descending = descending;
+        self
+    }
+
+    pub fn with_page(mut self, offset: usize, limit: usize) -> Self {
+        self.offset = offset;
+        self.limit = limit;
+        self
+    }
+
+    /// האם המונח עונה על הטקסט ועל כל המסננים
+    pub fn matches(&self, term: &TechnicalTerm) -> bool {
+        if let Some(domain) = &self.domain {
+            if !term.domain.eq_ignore_ascii_case(domain.trim()) {
+                return false;
+            }
+        }
+        if self.verified.map_or(false, |verified| term.metadata.verified != verified) {
+            return false;
+        }
+        if self.updated_after.map_or(false, |after| term.metadata.last_updated < after) {
+            return false;
+        }
+        if self.updated_before.map_or(false, |before| term.metadata.last_updated > before) {
+            return false;
+        }
+        if let Some(standard) = &self.standard {
+            if !references_standard(term, standard) {
+                return false;
+            }
+        }
+
+        let pattern = normalize_search(&self.text);
+        if pattern.is_empty() {
+            return true;
+        }
+        searchable_forms(term, self.lang).any(|form| self.match_form(&pattern, &normalize_search(form)))
+    }
+
+    fn match_form(&self, pattern: &str, form: &str) -> bool {
+        match self.mode {
+            MatchMode::Exact => form == pattern,
+            MatchMode::Prefix => form.starts_with(pattern),
+            MatchMode::Substring => form.contains(pattern),
+            MatchMode::Wildcard => wildcard_match(pattern, form),
+        }
+    }
+
+    /// סינון, מיון וחיתוך העמוד המבוקש
+    pub fn apply(&self, terms: Vec<TechnicalTerm>) -> SearchPage {
+        let mut found: Vec<TechnicalTerm> = terms.into_iter().filter(|term| self.matches(term)).collect();
+        let now = Utc::now();
+
+        found.sort_by(|a, b| {
+            let ordering = match self.sort {
+                SortField::Source => normalize_search(&a.source).cmp(&normalize_search(&b.source)),
+                SortField::Target => normalize_search(&a.target).cmp(&normalize_search(&b.target)),
+                SortField::Domain => a.domain.cmp(&b.domain),
+                SortField::LastUpdated => a.metadata.last_updated.cmp(&b.metadata.last_updated),
+                SortField::Confidence => a
+                    .metadata
+                    .confidence_at(now)
+                    .partial_cmp(&b.metadata.confidence_at(now))
+                    .unwrap_or(std::cmp::Ordering::Equal),
+                SortField::UsageCount => a.metadata.usage_count.cmp(&b.metadata.usage_count),
+            };
+            let ordering = if self.descending { ordering.reverse() } else { ordering };
+            // סדר יציב בין דפים גם כששדה המיון שווה
+            ordering.then_with(|| a.source.cmp(&b.source))
+        });
+
+        let total = found.len();
+        let limit = self.limit.clamp(1, MAX_PAGE_SIZE);
+        let terms = found.into_iter().skip(self.offset).take(limit).collect();
+        SearchPage {
+            total,
+            offset: self.offset,
+            limit,
+            terms,
+        }
+    }
+}
+
+/// עמוד תוצאות
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchPage {
+    /// מספר המונחים שעונים על השאילתה, בכל הדפים
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    pub terms: Vec<TechnicalTerm>,
+}
+
+impl SearchPage {
+    pub fn has_more(&self) -> bool {
+        self.offset + self.terms.len() < self.total
+    }
+}
+
+/// נרמול לחיפוש: אותיות קטנות, ללא ניקוד וטעמים, אותיות סופיות כרגילות, ё כ-е ורווחים מכווצים
+pub fn normalize_search(text: &str) -> String {
+    let text = normalize_final_letters(&strip_niqqud(text)).to_lowercase().replace('ё', "е");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// הצורות שבהן מחפשים: הצורה הראשית והצורות החלופיות
+fn searchable_forms(term: &TechnicalTerm, lang: Option<Language>) -> impl Iterator<Item = &str> {
+    let in_lang = move |candidate: Language| lang.map_or(true, |lang| lang == candidate);
+    let main = Language::ALL
+        .into_iter()
+        .filter(move |&candidate| in_lang(candidate))
+        .filter_map(move |candidate| term.text_in(candidate));
+    let variants = term
+        .variants
+        .iter()
+        .filter(move |variant| in_lang(variant.lang))
+        .map(|variant| variant.text.as_str());
+    main.chain(variants)
+}
+
+// הפניה לתקן בהערות, בהקשר, בהגדרה או במקורות. ההשוואה נעשית על ציוני התקן שזוהו בטקסט,
+// כך ש-NFPA 13 אינו NFPA 130 ומילים רגילות אינן מתפרשות כציון
+fn references_standard(term: &TechnicalTerm, standard: &str) -> bool {
+    if standard.trim().is_empty() {
+        return true;
+    }
+    let Some(wanted) = KNOWN_STANDARDS.find_by_designation(standard.trim()) else {
+        return false;
+    };
+    term.notes
+        .iter()
+        .chain(term.context.iter())
+        .chain(term.metadata.source_references.iter())
+        .chain(term.definition.iter())
+        .any(|text| KNOWN_STANDARDS.find_citations(text).iter().any(|citation| citation.code == wanted.code))
+}
+
+/// התאמת תבנית עם `*` ו-`?` לכל המחרוזת
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // מיקום הכוכבית האחרונה ומיקום הטקסט שהיא התחילה לכסות
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::technical_dictionary::{TermVariant, UsageStatus};
+
+    fn term(source: &str, target: &str, domain: &str, notes: &[&str], verified: bool) -> TechnicalTerm {
+        let mut term = TechnicalTerm::for_test(source, target, domain);
+        term.notes = notes.iter().map(|note| note.to_string()).collect();
+        term.metadata.verified = verified;
+        term
+    }
+
+    fn sample() -> Vec<TechnicalTerm> {
+        let mut sprinkler = term("רֹאשׁ ספרינקלר", "ороситель", "fire_protection", &["NFPA 13"], true);
+        sprinkler.variants.push(TermVariant {
+            text: "спринклёр".to_string(),
+            lang: Language::Russian,
+            status: UsageStatus::Admitted,
+        });
+        vec![
+            sprinkler,
+            term("צינור אספקה", "питающий трубопровод", "plumbing", &["ГОСТ 3262-75"], false),
+            term("צינור ניקוז", "дренажный трубопровод", "plumbing", &["NFPA 130"], true),
+        ]
+    }
+
+    #[test]
+    fn test_match_modes_and_normalization() {
+        let page = TermQuery::new("ראש").with_mode(MatchMode::Prefix).apply(sample());
+        assert_eq!(page.total, 1);
+
+        let page = TermQuery::new("спринклер").with_lang(Language::Russian).apply(sample());
+        assert_eq!(page.terms[0].target, "ороситель");
+
+        let page = TermQuery::new("*трубопровод").with_mode(MatchMode::Wildcard).apply(sample());
+        assert_eq!(page.total, 2);
+        assert!(TermQuery::new("צינור").with_lang(Language::Russian).apply(sample()).terms.is_empty());
+    }
+
+    #[test]
+    fn test_filters() {
+        let page = TermQuery::new("").with_standard("NFPA13").apply(sample());
+        assert_eq!(page.total, 1);
+
+        let page = TermQuery::new("").with_standard("GOST 3262").apply(sample());
+        assert_eq!(page.terms[0].source, "צינור אספקה");
+
+        // "שתי 1596" בהערה חופשית אינו ציון של ת"י 1596
+        let counted = term("ספרינקלר", "ороситель", "fire_protection", &["שתי 1596 יחידות"], true);
+        assert_eq!(TermQuery::new("").with_standard("ת\"י 1596").apply(vec![counted]).total, 0);
+        let cited = term("ספרינקלר", "ороситель", "fire_protection", &["לפי ת\"י 1596"], true);
+        assert_eq!(TermQuery::new("").with_standard("ת\"י 1596").apply(vec![cited]).total, 1);
+
+        let page = TermQuery::new("צינור").with_domain("plumbing").with_verified(true).apply(sample());
+        assert_eq!(page.terms.len(), 1);
+        assert_eq!(page.terms[0].source, "צינור ניקוז");
+    }
+
+    #[test]
+    fn test_sort_and_paging() {
+        let page = TermQuery::new("")
+            .with_sort(SortField::Target, true)
+            .with_page(0, 2)
+            .apply(sample());
+        assert_eq!(page.total, 3);
+        assert_eq!(page.terms[0].target, "питающий трубопровод");
+        assert!(page.has_more());
+
+        let page = TermQuery::new("").with_sort(SortField::Target, true).with_page(2, 2).apply(sample());
+        assert_eq!(page.terms.len(), 1);
+        assert!(!page.has_more());
+    }
+}
@@ -1,10 +1,25 @@
 use crate::translation::Translator;
-use crate::file_processor::{FileProcessor, FileType, ExcelSheet};
+use crate::streaming::{StreamingTranslator, TranslationProgress};
+use crate::batch::{BatchTranslator, BatchStatistics};
+use crate::language::Language;
+use crate::file_processor::{FileProcessor, FileType, ExcelSheet, ExcelCell};
+use crate::quality_control::{QualityControl, ValidationReport};
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 use anyhow::{Result, Context};
+use tokio_util::sync::CancellationToken;
+
+pub type ProgressCallback = Arc<dyn Fn(&TranslationProgress) + Send + Sync>;
 
 pub struct DocumentProcessor {
-    translator: Translator,
+    translator: Arc<Translator>,
+    streaming: StreamingTranslator,
+    batch: Arc<BatchTranslator>,
+    cancel: CancellationToken,
+    on_progress: Option<ProgressCallback>,
+    last_batch_statistics: Mutex<Option<BatchStatistics>>,
+    quality_control: QualityControl,
+    last_validation: Mutex<Option<ValidationReport>>,
 }
 
 #[derive(Debug)]
@@ -17,7 +32,78 @@ pub enum DocumentType {
 
 impl DocumentProcessor {
     pub fn new(translator: Translator) -> Self {
-        Self { translator }
+        let translator = Arc::new(translator);
+        Self {
+            streaming: StreamingTranslator::new(translator.clone()),
+            batch: Arc::new(BatchTranslator::new(translator.clone())),
+            translator,
+            cancel: CancellationToken::new(),
+            on_progress: None,
+            last_batch_statistics: Mutex::new(None),
+            quality_control: QualityControl::new(),
+            last_validation: Mutex::new(None),
+        }
+    }
+
+    /// מספר העובדים בתרגום גיליונות
+    pub fn with_batch_workers(mut self, workers: usize) -> Self {
+        self.batch = Arc::new(BatchTranslator::new(self.translator.clone()).with_workers(workers));
+        self
+    }
+
+    /// סטטיסטיקת החזרות של הגיליון האחרון שעובד
+    pub fn last_batch_statistics(&self) -> Option<BatchStatistics> {
+        self.last_batch_statistics.lock().ok().and_then(|stats| stats.clone())
+    }
+
+    /// דוח בקרת האיכות של מסמך הטקסט האחרון שתורגם
+    pub fn last_validation(&self) -> Option<ValidationReport> {
+        self.last_validation.lock().ok().and_then(|report| report.clone())
+    }
+
+    /// אסימון ביטול משותף לכל המסמכים שיעובדו
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// קריאה חוזרת לעדכוני התקדמות בתרגום מסמכים וגיליונות
+    pub fn with_progress(mut self, on_progress: ProgressCallback) -> Self {
+        self.on_progress = Some(on_progress);
+        self
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+    }
+
+    // תרגום תוכן ארוך מקטע אחר מקטע; בביטול לא נכתב קובץ פלט
+    async fn translate_content(&self, content: &str) -> Result<String> {
+        let source = Language::detect(content)
+            .context("לא ניתן לזהות את שפת המקור")?;
+        let on_progress = self.on_progress.clone();
+
+        let output = self.streaming
+            .translate_with_progress(
+                content,
+                source.code(),
+                source.default_target().code(),
+                self.cancel.clone(),
+                |progress| {
+                    if let Some(callback) = &on_progress {
+                        callback(progress);
+                    }
+                },
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("שגיאת תרגום: {:?}", e))?;
+
+        let report = self.translator.quality_check(&self.quality_control, content, &output).await?;
+        if let Ok(mut last) = self.last_validation.lock() {
+            *last = Some(report);
+        }
+
+        Ok(output.text)
     }
 
     pub async fn process_document<P: AsRef<Path>>(
@@ -43,102 +129,89 @@ impl DocumentProcessor {
 
     async fn process_pdf<P: AsRef<Path>>(&self, input_path: P, output_path: P) -> Result<()> {
         let content = FileProcessor::read_pdf(&input_path)?;
-        let translated_content = self.translator
-            .translate(&content)
-            .map_err(|e| anyhow::anyhow!("שגיאת תרגום: {:?}", e))?;
+        let translated_content = self.translate_content(&content).await?;
         FileProcessor::write_pdf(&translated_content, output_path.as_ref())?;
         Ok(())
     }
 
     async fn process_docx<P: AsRef<Path>>(&self, input_path: P, output_path: P) -> Result<()> {
         let content = FileProcessor::read_docx(&input_path)?;
-        let translated_content = self.translator
-            .translate(&content)
-            .map_err(|e| anyhow::anyhow!("שגיאת תרגום: {:?}", e))?;
+        let translated_content = self.translate_content(&content).await?;
         FileProcessor::write_docx(&translated_content, output_path.as_ref())?;
         Ok(())
     }
 
     async fn process_txt<P: AsRef<Path>>(&self, input_path: P, output_path: P) -> Result<()> {
         let content = FileProcessor::read_txt(&input_path)?;
-        let translated_content = self.translator
-            .translate(&content)
-            .map_err(|e| anyhow::anyhow!("שגיאת תרגום: {:?}", e))?;
+        let translated_content = self.translate_content(&content).await?;
         FileProcessor::write_txt(&translated_content, output_path.as_ref())?;
         Ok(())
     }
 
     async fn process_excel<P: AsRef<Path>>(&self, input_path: P, output_path: P) -> Result<()> {
         let sheets = FileProcessor::read_excel(&input_path)?;
-        let mut translated_sheets = Vec::new();
-        
-        for sheet in sheets {
-            let mut translated_cells = Vec::new();
-            
-            for cell in sheet.cells {
-                let translated_value = if !cell.value.trim().is_empty() {
-                    self.translator
-                        .translate(&cell.value)
-                        .unwrap_or_else(|_| cell.value.clone())
-                } else {
-                    cell.value.clone()
-                };
-                
-                translated_cells.push(ExcelSheet {
-                    name: sheet.name.clone(),
-                    cells: vec![ExcelCell {
-                        value: translated_value,
-                        row: cell.row,
-                        col: cell.col,
-                        is_formula: cell.is_formula,
-                    }],
-                });
-            }
-            
-            translated_sheets.push(ExcelSheet {
-                name: sheet.name,
-                cells: translated_cells.into_iter().flat_map(|s| s.cells).collect(),
-            });
-        }
-        
+        let translated_sheets = self.translate_sheets(sheets).await?;
         FileProcessor::write_excel(&translated_sheets, output_path.as_ref())?;
         Ok(())
     }
 
     async fn process_csv<P: AsRef<Path>>(&self, input_path: P, output_path: P) -> Result<()> {
         let sheets = FileProcessor::read_csv(&input_path)?;
-        let mut translated_sheets = Vec::new();
-        
-        for sheet in sheets {
-            let mut translated_cells = Vec::new();
-            
-            for cell in sheet.cells {
-                let translated_value = if !cell.value.trim().is_empty() {
-                    self.translator
-                        .translate(&cell.value)
-                        .unwrap_or_else(|_| cell.value.clone())
-                } else {
-                    cell.value.clone()
-                };
-                
-                translated_cells.push(ExcelCell {
-                    value: translated_value,
-                    row: cell.row,
-                    col: cell.col,
-                    is_formula: cell.is_formula,
-                });
-            }
-            
-            translated_sheets.push(ExcelSheet {
-                name: sheet.name,
-                cells: translated_cells,
-            });
-        }
-        
+        let translated_sheets = self.translate_sheets(sheets).await?;
         FileProcessor::write_csv(&translated_sheets[0], output_path.as_ref())?;
         Ok(())
     }
 
+    // תרגום כל התאים של כל הגיליונות כאצווה אחת, כך שתיאור חוזר מתורגם פעם אחת
+    async fn translate_sheets(&self, sheets: Vec<ExcelSheet>) -> Result<Vec<ExcelSheet>> {
+        // נוסחאות אינן נשלחות לתרגום
+        let values: Vec<String> = sheets
+            .iter()
+            .flat_map(|sheet| sheet.cells.iter())
+            .map(|cell| if cell.is_formula { String::new() } else { cell.value.clone() })
+            .collect();
+
+        let batch = self.batch.clone();
+        let cancel = self.cancel.clone();
+        let on_progress = self.on_progress.clone();
+        let output = tokio::task::spawn_blocking(move || {
+            batch.translate_batch_detected_with_progress(&values, &cancel, &|progress| {
+                if let Some(callback) = &on_progress {
+                    callback(progress);
+                }
+            })
+        })
+        .await
+        .context("תרגום האצווה נכשל")??;
+
+        let mut translations = output.translations.into_iter();
+        let translated_sheets = sheets
+            .into_iter()
+            .map(|sheet| ExcelSheet {
+                name: sheet.name,
+                cells: sheet
+                    .cells
+                    .into_iter()
+                    .map(|cell| {
+                        let translated = translations.next().unwrap_or_default();
+                        ExcelCell {
+                            value: if cell.is_formula { cell.value } else { translated },
+                            row: cell.row,
+                            col: cell.col,
+                            is_formula: cell.is_formula,
+                        }
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        if let Ok(mut stats) = self.last_batch_statistics.lock() {
+            *stats = Some(output.statistics);
+        }
+
+        Ok(translated_sheets)
+    }
+
     async fn process_technical_doc<P: AsRef<Path>>(
         &self,
         input_path: P,
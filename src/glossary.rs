@@ -0,0 +1,353 @@
+use crate::language::{Language, Multilingual};
+use crate::quality_control::find_term_occurrences;
+use anyhow::{Result, Context};
+use serde::{Serialize, Deserialize};
+use std::fs;
+use std::path::Path;
+
+/// רשומה במילון פרויקט
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlossaryEntry {
+    pub source: String,
+    pub target: String,
+    pub source_lang: Language,
+    pub target_lang: Language,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+impl Multilingual for GlossaryEntry {
+    fn forms(&self) -> Vec<(Language, &str)> {
+        vec![(self.source_lang, self.source.as_str()), (self.target_lang, self.target.as_str())]
+    }
+}
+
+impl GlossaryEntry {
+    /// המונח הנדרש בכיוון התרגום המבוקש, אם הרשומה מכסה אותו
+    pub fn pair_for(&self, source: Language, target: Language) -> Option<(&str, &str)> {
+        Some((self.text_in(source)?, self.text_in(target)?))
+    }
+}
+
+/// מידת האכיפה של מילון פרויקט
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GlossaryEnforcement {
+    /// המילון גובר על המילון הכללי כשיש התאמה מלאה
+    Preferred,
+    /// מונחי היעד חייבים להופיע בפלט בכל מקום שמונח המקור הופיע
+    Forced,
+}
+
+/// מילון מונחים של לקוח או פרויקט
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectGlossary {
+    pub name: String,
+    /// עדיפות - מספר גבוה גובר
+    pub priority: u32,
+    pub enforcement: GlossaryEnforcement,
+    pub entries: Vec<GlossaryEntry>,
+}
+
+impl ProjectGlossary {
+    pub fn new(name: String, priority: u32, enforcement: GlossaryEnforcement) -> Self {
+        Self {
+            name,
+            priority,
+            enforcement,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn add_entry(&mut self, entry: GlossaryEntry) {
+        self.entries.push(entry);
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("קריאת מילון הפרויקט נכשלה: {}", path.as_ref().display()))?;
+        serde_json::from_str(&content).context("פענוח מילון הפרויקט נכשל")
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&path, content)
+            .with_context(|| format!("שמירת מילון הפרויקט נכשלה: {}", path.as_ref().display()))
+    }
+
+    /// התאמה מלאה של מקטע לרשומה במילון זה בלבד
+    pub fn lookup(&self, text: &str, source: Language, target: Language) -> Option<&str> {
+        let text = text.trim().to_lowercase();
+        self.entries.iter().find_map(|entry| match entry.pair_for(source, target) {
+            Some((found, required)) if found.to_lowercase() == text => Some(required),
+            _ => None,
+        })
+    }
+}
+
+/// תוצאת בדיקת מונח מחייב במקטע
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GlossaryIssueKind {
+    /// תרגום המילון הכללי הוחלף במונח הנדרש
+    Enforced { replaced: String },
+    /// מונח המקור הופיע אך מונח היעד הנדרש חסר בפלט
+    Missing,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlossaryIssue {
+    pub glossary: String,
+    pub source_term: String,
+    pub required_target: String,
+    /// מיקום (בבתים) של מונח המקור בתוך המקטע
+    pub source_position: usize,
+    pub kind: GlossaryIssueKind,
+}
+
+/// מילונים מרובדים מעל המילון הטכני ומסד המונחים, לפי עדיפות
+#[derive(Debug, Clone, Default)]
+pub struct GlossaryStack {
+    glossaries: Vec<ProjectGlossary>,
+}
+
+impl GlossaryStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, glossary: ProjectGlossary) {
+        self.glossaries.push(glossary);
+        self.glossaries.sort_by(|a, b| b.priority.cmp(&a.priority));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.glossaries.is_empty()
+    }
+
+    pub fn glossaries(&self) -> &[ProjectGlossary] {
+        &self.glossaries
+    }
+
+    /// התאמה מלאה של מקטע, מהמילון בעל העדיפות הגבוהה ביותר
+    pub fn lookup(&self, text: &str, source: Language, target: Language) -> Option<(&str, String)> {
+        self.glossaries.iter().find_map(|glossary| {
+            glossary
+                .lookup(text, source, target)
+                .map(|required| (glossary.name.as_str(), required.to_string()))
+        })
+    }
+
+    /// אכיפת מונחים מחייבים על מקטע מתורגם.
+    /// `general` מחזיר את תרגום המילון הכללי של מונח, כדי להחליפו במונח הנדרש.
+    pub fn enforce<F>(
+        &self,
+        source_text: &str,
+        target_text: &mut String,
+        source: Language,
+        target: Language,
+        general: F,
+    ) -> Vec<GlossaryIssue>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut issues = Vec::new();
+        // מונח שכבר נבדק במילון בעדיפות גבוהה יותר לא ייבדק שוב
+        let mut seen: Vec<String> = Vec::new();
+
+        for glossary in self.glossaries.iter().filter(|g| g.enforcement == GlossaryEnforcement::Forced) {
+            for entry in &glossary.entries {
+                let Some((term, required)) = entry.pair_for(source, target) else {
+                    continue;
+                };
+                let term_lower = term.to_lowercase();
+                if term_lower.is_empty() || seen.contains(&term_lower) {
+                    continue;
+                }
+
+                let positions: Vec<usize> = find_term_occurrences(source_text, term)
+                    .into_iter()
+                    .map(|(start, _)| start)
+                    .collect();
+                if positions.is_empty() {
+                    continue;
+                }
+                seen.push(term_lower);
+
+                // כל מופע של מונח המקור מחייב מופע משלו של מונח היעד
+                for (occurrence, position) in positions.into_iter().enumerate() {
+                    let required_ranges = find_term_occurrences(target_text, required);
+                    if required_ranges.len() > occurrence {
+                        continue;
+                    }
+
+                    // ניסיון החלפה של התרגום הכללי של המונח במונח הנדרש, כמילה שלמה
+                    let replaced = general(term).and_then(|general| {
+                        let range = general_occurrence(target_text, &general, &required_ranges, occurrence)?;
+                        Some((general, range))
+                    });
+
+                    let kind = match replaced {
+                        Some((replaced, (start, end))) => {
+                            target_text.replace_range(start..end, required);
+                            GlossaryIssueKind::Enforced { replaced }
+                        }
+                        None => GlossaryIssueKind::Missing,
+                    };
+
+                    issues.push(GlossaryIssue {
+                        glossary: glossary.name.clone(),
+                        source_term: term.to_string(),
+                        required_target: required.to_string(),
+                        source_position: position,
+                        kind,
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+}
+
+// מופע התרגום הכללי שמתאים למופע `occurrence` של מונח המקור: לפי מספרו הסידורי בין
+// מופעי התרגום הכללי והמונח הנדרש בתרגום. מופע שנמצא בתוך המונח הנדרש אינו מוחלף
+fn general_occurrence(
+    target_text: &str,
+    general: &str,
+    required_ranges: &[(usize, usize)],
+    occurrence: usize,
+) -> Option<(usize, usize)> {
+    let overlaps = |(start, end): (usize, usize)| required_ranges.iter().any(|&(s, e)| start < e && s < end);
+    let ranges: Vec<(usize, usize)> = find_term_occurrences(target_text, general)
+        .into_iter()
+        .filter(|range| !overlaps(*range))
+        .collect();
+    let ordinal = |start: usize| {
+        required_ranges.iter().filter(|(s, _)| *s < start).count() + ranges.iter().filter(|(s, _)| *s < start).count()
+    };
+    ranges
+        .iter()
+        .copied()
+        .find(|(start, _)| ordinal(*start) >= occurrence)
+        .or_else(|| ranges.last().copied())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn customer_glossary(enforcement: GlossaryEnforcement) -> ProjectGlossary {
+        let mut glossary = ProjectGlossary::new("customer".to_string(), 10, enforcement);
+        glossary.add_entry(GlossaryEntry {
+            source: "ראש ספרינקלר".to_string(),
+            target: "спринклерный ороситель".to_string(),
+            source_lang: Language::Hebrew,
+            target_lang: Language::Russian,
+            note: None,
+        });
+        glossary
+    }
+
+    #[test]
+    fn test_lookup_priority() {
+        let mut stack = GlossaryStack::new();
+        let mut low = ProjectGlossary::new("general".to_string(), 1, GlossaryEnforcement::Preferred);
+        low.add_entry(GlossaryEntry {
+            source: "ראש ספרינקלר".to_string(),
+            target: "ороситель".to_string(),
+            source_lang: Language::Hebrew,
+            target_lang: Language::Russian,
+            note: None,
+        });
+        stack.push(low);
+        stack.push(customer_glossary(GlossaryEnforcement::Preferred));
+
+        let (name, target) = stack.lookup("ראש ספרינקלר", Language::Hebrew, Language::Russian).unwrap();
+        assert_eq!(name, "customer");
+        assert_eq!(target, "спринклерный ороситель");
+
+        // הרשומה משמשת גם בכיוון ההפוך
+        let (_, target) = stack.lookup("ороситель", Language::Russian, Language::Hebrew).unwrap();
+        assert_eq!(target, "ראש ספרינקלר");
+    }
+
+    #[test]
+    fn test_forced_enforcement() {
+        let mut stack = GlossaryStack::new();
+        stack.push(customer_glossary(GlossaryEnforcement::Forced));
+
+        let mut target = "заменить ороситель/спринклер".to_string();
+        let issues = stack.enforce(
+            "להחליף ראש ספרינקלר",
+            &mut target,
+            Language::Hebrew,
+            Language::Russian,
+            |_| Some("ороситель/спринклер".to_string()),
+        );
+        assert_eq!(target, "заменить спринклерный ороситель");
+        assert!(matches!(issues[0].kind, GlossaryIssueKind::Enforced { .. }));
+
+        let mut target = "заменить головку".to_string();
+        let issues = stack.enforce(
+            "להחליף ראש ספרינקלר",
+            &mut target,
+            Language::Hebrew,
+            Language::Russian,
+            |_| None,
+        );
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, GlossaryIssueKind::Missing);
+        assert_eq!(issues[0].source_position, "להחליף ".len());
+    }
+
+    #[test]
+    fn test_enforcement_matches_whole_words() {
+        let mut glossary = ProjectGlossary::new("customer".to_string(), 10, GlossaryEnforcement::Forced);
+        glossary.add_entry(GlossaryEntry {
+            source: "ברז".to_string(),
+            target: "кран".to_string(),
+            source_lang: Language::Hebrew,
+            target_lang: Language::Russian,
+            note: None,
+        });
+        let mut stack = GlossaryStack::new();
+        stack.push(glossary);
+
+        // "ברז" בתוך "ברזל" אינו מופע של המונח
+        let mut target = "стальная труба".to_string();
+        let issues = stack.enforce("צינור ברזל", &mut target, Language::Hebrew, Language::Russian, |_| None);
+        assert!(issues.is_empty());
+
+        // עם אות שימוש המונח נמצא, והמיקום הוא בטקסט המקורי
+        let mut target = "открыть вентиль".to_string();
+        let issues = stack.enforce("לפתוח את הברז", &mut target, Language::Hebrew, Language::Russian, |_| {
+            Some("вентиль".to_string())
+        });
+        assert_eq!(target, "открыть кран");
+        assert_eq!(issues[0].source_position, "לפתוח את ה".len());
+    }
+
+    #[test]
+    fn test_replacement_matches_whole_words() {
+        let mut glossary = ProjectGlossary::new("customer".to_string(), 10, GlossaryEnforcement::Forced);
+        glossary.add_entry(GlossaryEntry {
+            source: "מגוף".to_string(),
+            target: "задвижка".to_string(),
+            source_lang: Language::Hebrew,
+            target_lang: Language::Russian,
+            note: None,
+        });
+        let mut stack = GlossaryStack::new();
+        stack.push(glossary);
+
+        // "кран" בתוך "экран" אינו התרגום הכללי
+        let mut target = "экран и кран".to_string();
+        let issues = stack.enforce("מסך ומגוף", &mut target, Language::Hebrew, Language::Russian, |_| Some("кран".to_string()));
+        assert_eq!(target, "экран и задвижка");
+        assert!(matches!(issues[0].kind, GlossaryIssueKind::Enforced { .. }));
+
+        // המופע השני במקור מחליף את המופע השני בתרגום
+        let mut target = "задвижка, затем кран".to_string();
+        stack.enforce("מגוף ואז מגוף", &mut target, Language::Hebrew, Language::Russian, |_| Some("кран".to_string()));
+        assert_eq!(target, "задвижка, затем задвижка");
+    }
+}
@@ -0,0 +1,628 @@
+//! ייבוא מילוני מונחים מגיליונות (CSV או Excel) עם כותרות חופשיות.
+//! המשתמש ממפה עמודות לשדות, מריץ ייבוא יבש שמפיק הבדלים, ורק אז מחיל אותם דרך מעקב השינויים.
+
+use std::collections::HashMap;
+use std::path::Path;
+use anyhow::{Context, Result};
+use calamine::{open_workbook_auto, DataType, Reader};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use crate::knowledge_sharing::{ChangeType, KnowledgeManager, TermChange};
+use crate::permissions::Action;
+use crate::language::Language;
+use crate::technical_dictionary::{TechnicalDictionary, TechnicalTerm, TermMetadata, UsageStatus};
+use crate::term_usage::UsageStats;
+
+// ציון אמון למונח מיובא שטרם אומת
+const IMPORTED_CONFIDENCE: f64 = 0.6;
+// קידומת הערה שמציינת תקן, כמו בייצוא TBX
+const STANDARD_NOTE_PREFIX: &str = "standard: ";
+
+/// גיליון כפי שנקרא מהקובץ: שורת כותרות ושורות נתונים
+#[derive(Debug, Clone, Default)]
+pub struct SpreadsheetTable {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl SpreadsheetTable {
+    /// קריאת CSV או הגיליון הראשון בקובץ Excel, לפי הסיומת
+    pub fn load(path: &Path) -> Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase);
+        match extension.as_deref() {
+            Some("xlsx") | Some("xls") | Some("ods") => Self::load_workbook(path),
+            _ => Self::load_csv(path),
+        }
+    }
+
+    pub fn load_csv(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("קריאת הגיליון נכשלה: {}", path.display()))?;
+        Self::parse_csv(&content)
+    }
+
+    pub fn parse_csv(content: &str) -> Result<Self> {
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_reader(content.trim_start_matches('\u{feff}').as_bytes());
+        let headers = reader
+            .headers()
+            .context("קריאת שורת הכותרות נכשלה")?
+            .iter()
+            .map(|header| header.trim().to_string())
+            .collect();
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            let record = record.context("קריאת שורה בגיליון נכשלה")?;
+            rows.push(record.iter().map(|cell| cell.trim().to_string()).collect());
+        }
+        Ok(Self { headers, rows })
+    }
+
+    fn load_workbook(path: &Path) -> Result<Self> {
+        let mut workbook = open_workbook_auto(path)
+            .with_context(|| format!("פתיחת קובץ ה-Excel נכשלה: {}", path.display()))?;
+        let range = workbook
+            .worksheet_range_at(0)
+            .context("בקובץ אין גיליונות")?
+            .with_context(|| format!("קריאת הגיליון נכשלה: {}", path.display()))?;
+
+        let mut rows = range.rows().map(|row| {
+            row.iter()
+                .map(|cell| match cell {
+                    DataType::Empty => String::new(),
+                    DataType::String(s) => s.trim().to_string(),
+                    other => other.to_string(),
+                })
+                .collect::<Vec<String>>()
+        });
+        let headers = rows.next().unwrap_or_default();
+        Ok(Self {
+            headers,
+            rows: rows.collect(),
+        })
+    }
+
+    pub fn column(&self, header: &str) -> Option<usize> {
+        self.headers.iter().position(|h| h.trim() == header.trim())
+    }
+}
+
+/// מיפוי כותרות הגיליון לשדות המונח
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnMapping {
+    pub source: String,
+    pub target: String,
+    #[serde(default)]
+    pub english: Option<String>,
+    #[serde(default)]
+    pub domain: Option<String>,
+    /// כמה עמודות יכולות להפוך להערות
+    #[serde(default)]
+    pub notes: Vec<String>,
+    /// עמודת תקנים; כמה תקנים בתא מופרדים בפסיק או בנקודה-פסיק
+    #[serde(default)]
+    pub standards: Option<String>,
+    /// תחום למונחים ללא עמודת תחום או עם תא ריק
+    #[serde(default = "default_domain")]
+    pub default_domain: String,
+}
+
+fn default_domain() -> String {
+    "general".to_string()
+}
+
+impl ColumnMapping {
+    pub fn new(source: &str, target: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            target: target.to_string(),
+            english: None,
+            domain: None,
+            notes: Vec::new(),
+            standards: None,
+            default_domain: default_domain(),
+        }
+    }
+
+    pub fn with_english(mut self, header: &str) -> Self {
+        self.english = Some(header.to_string());
+        self
+    }
+
+    pub fn with_domain(mut self, header: &str) -> Self {
+        self.domain = Some(header.to_string());
+        self
+    }
+
+    pub fn with_notes(mut self, header: &str) -> Self {
+        self.notes.push(header.to_string());
+        self
+    }
+
+    pub fn with_standards(mut self, header: &str) -> Self {
+        self.standards = Some(header.to_string());
+        self
+    }
+
+    pub fn with_default_domain(mut self, domain: &str) -> Self {
+        self.default_domain = domain.to_string();
+        self
+    }
+
+    /// הצעת מיפוי לפי כותרות מוכרות בעברית, ברוסית ובאנגלית; המשתמש מאשר או מתקן אותה
+    pub fn suggest(headers: &[String]) -> Option<Self> {
+        let find = |names: &[&str]| {
+            headers
+                .iter()
+                .find(|header| names.iter().any(|name| header.trim().to_lowercase() == *name))
+                .cloned()
+        };
+        let source = find(&["מונח", "עברית", "תיאור", "term", "hebrew", "source"])?;
+        let target = find(&["תרגום", "רוסית", "russian", "target", "термин", "перевод"])?;
+
+        let mut mapping = Self::new(&source, &target);
+        mapping.english = find(&["אנגלית", "english"]);
+        mapping.domain = find(&["תחום", "domain", "область"]);
+        mapping.standards = find(&["תקן", "תקנים", "standard", "standards", "стандарт"]);
+        mapping.notes = find(&["הערות", "הערה", "notes", "примечание"]).into_iter().collect();
+        Some(mapping)
+    }
+
+    // אינדקסי העמודות בגיליון; כותרת ממופה שאינה קיימת היא שגיאה
+    fn resolve(&self, table: &SpreadsheetTable) -> Result<ResolvedColumns> {
+        let required = |header: &str| {
+            table
+                .column(header)
+                .with_context(|| format!("העמודה \"{}\" לא נמצאה בגיליון", header))
+        };
+        let optional = |header: &Option<String>| header.as_deref().map(|header| required(header)).transpose();
+        Ok(ResolvedColumns {
+            source: required(&self.source)?,
+            target: required(&self.target)?,
+            english: optional(&self.english)?,
+            domain: optional(&self.domain)?,
+            notes: self.notes.iter().map(|header| required(header)).collect::<Result<_>>()?,
+            standards: optional(&self.standards)?,
+        })
+    }
+}
+
+struct ResolvedColumns {
+    source: usize,
+    target: usize,
+    english: Option<usize>,
+    domain: Option<usize>,
+    notes: Vec<usize>,
+    standards: Option<usize>,
+}
+
+/// שינוי בשדה אחד של מונח קיים
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+/// מונח קיים שהגיליון מוסיף לו מידע בלי לשנות את התרגום
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangedTerm {
+    pub row: usize,
+    pub term: TechnicalTerm,
+    pub changes: Vec<FieldChange>,
+}
+
+/// מונח שהתרגום שלו בגיליון סותר את המילון או שורה קודמת בגיליון
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportConflict {
+    pub row: usize,
+    pub source: String,
+    pub existing_target: String,
+    pub incoming_target: String,
+    pub reason: String,
+}
+
+/// תוצאת ייבוא יבש: מה ישתנה במילון אם הייבוא יאושר
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportPlan {
+    pub new_terms: Vec<(usize, TechnicalTerm)>,
+    pub changed: Vec<ChangedTerm>,
+    pub conflicts: Vec<ImportConflict>,
+    pub unchanged: Vec<String>,
+    /// מספר שורה (מ-1, לא כולל כותרות) וסיבת הדילוג
+    pub skipped: Vec<(usize, String)>,
+}
+
+impl ImportPlan {
+    pub fn is_empty(&self) -> bool {
+        self.new_terms.is_empty() && self.changed.is_empty()
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} מונחים חדשים, {} מונחים שישתנו, {} סתירות, {} ללא שינוי, {} שורות שדולגו",
+            self.new_terms.len(),
+            self.changed.len(),
+            self.conflicts.len(),
+            self.unchanged.len(),
+            self.skipped.len()
+        )
+    }
+
+    /// ההבדלים כגיליון: שורה לכל מונח חדש, לכל שדה שמשתנה ולכל סתירה
+    pub fn save_diff_csv(&self, path: &Path) -> Result<()> {
+        let mut writer = csv::Writer::from_path(path)
+            .with_context(|| format!("יצירת קובץ ההבדלים נכשלה: {}", path.display()))?;
+
+        writer.write_record(["kind", "row", "source", "field", "old", "new"])?;
+        for (row, term) in &self.new_terms {
+            writer.write_record(&["new", &row.to_string(), &term.source, "target", "", &term.target])?;
+        }
+        for changed in &self.changed {
+            for change in &changed.changes {
+                writer.write_record(&[
+                    "changed",
+                    &changed.row.to_string(),
+                    &changed.term.source,
+                    &change.field,
+                    change.old_value.as_deref().unwrap_or_default(),
+                    change.new_value.as_deref().unwrap_or_default(),
+                ])?;
+            }
+        }
+        for conflict in &self.conflicts {
+            writer.write_record(&[
+                "conflict",
+                &conflict.row.to_string(),
+                &conflict.source,
+                "target",
+                &conflict.existing_target,
+                &conflict.incoming_target,
+            ])?;
+        }
+        writer
+            .flush()
+            .with_context(|| format!("כתיבת קובץ ההבדלים נכשלה: {}", path.display()))?;
+        Ok(())
+    }
+
+    /// החלת המונחים החדשים והשינויים על המילון ורישום כל שינוי במעקב השינויים, בטרנזקציה אחת.
+    /// סתירות אינן מוחלות; יש לפתור אותן בגיליון או בבקשת סקירה.
+    pub fn commit(
+        &self,
+        dictionary: &TechnicalDictionary,
+        knowledge_manager: &mut KnowledgeManager,
+        changed_by: &str,
+    ) -> Result<usize> {
+        knowledge_manager.authorize(changed_by, Action::ImportGlossary)?;
+        let now = Utc::now();
+        let change = |change_type, field: &str, old_value: Option<String>, new_value: Option<String>| TermChange {
+            timestamp: now,
+            changed_by: changed_by.to_string(),
+            change_type,
+            old_value,
+            new_value,
+            field: field.to_string(),
+        };
+
+        let mut terms = Vec::new();
+        let mut changes = Vec::new();
+        for (_, term) in &self.new_terms {
+            changes.push((
+                term.source.clone(),
+                change(ChangeType::Addition, "target", None, Some(term.target.clone())),
+            ));
+            terms.push(term.clone());
+        }
+        for changed in &self.changed {
+            for field in &changed.changes {
+                changes.push((
+                    changed.term.source.clone(),
+                    change(ChangeType::Modification, &field.field, field.old_value.clone(), field.new_value.clone()),
+                ));
+            }
+            terms.push(changed.term.clone());
+        }
+
+        let count = terms.len();
+        knowledge_manager.apply_term_changes(dictionary, terms, changes)?;
+        Ok(count)
+    }
+}
+
+/// ייבוא גיליון מונחים לפי מיפוי עמודות
+pub struct GlossaryImporter {
+    mapping: ColumnMapping,
+    source_lang: Language,
+    target_lang: Language,
+    // שם הקובץ נרשם במקורות של כל מונח חדש
+    source_name: String,
+}
+
+impl GlossaryImporter {
+    pub fn new(mapping: ColumnMapping, source_lang: Language, target_lang: Language) -> Self {
+        Self {
+            mapping,
+            source_lang,
+            target_lang,
+            source_name: "spreadsheet".to_string(),
+        }
+    }
+
+    pub fn with_source_name(mut self, name: &str) -> Self {
+        self.source_name = name.to_string();
+        self
+    }
+
+    /// ייבוא יבש: משווה את הגיליון למילון בלי לשנות דבר
+    pub fn plan(&self, table: &SpreadsheetTable, dictionary: &TechnicalDictionary) -> Result<ImportPlan> {
+        let columns = self.mapping.resolve(table)?;
+        let mut plan = ImportPlan::default();
+        // שורה ראשונה לכל מונח מקור בגיליון, לזיהוי כפילויות סותרות
+        let mut seen: HashMap<String, (usize, String)> = HashMap::new();
+
+        for (index, row) in table.rows.iter().enumerate() {
+            let row_number = index + 1;
+            let cell = |column: usize| row.get(column).map(|value| value.trim()).unwrap_or_default();
+            let optional = |column: Option<usize>| {
+                column.map(cell).filter(|value| !value.is_empty()).map(str::to_string)
+            };
+
+            let source = cell(columns.source);
+            if source.is_empty() {
+                if row.iter().any(|value| !value.trim().is_empty()) {
+                    plan.skipped.push((row_number, "חסר מונח מקור".to_string()));
+                }
+                continue;
+            }
+            let target = cell(columns.target);
+            if target.is_empty() {
+                plan.skipped.push((row_number, format!("חסר תרגום ל-\"{}\"", source)));
+                continue;
+            }
+
+            if let Some((first_row, first_target)) = seen.get(source) {
+                if first_target != target {
+                    plan.conflicts.push(ImportConflict {
+                        row: row_number,
+                        source: source.to_string(),
+                        existing_target: first_target.clone(),
+                        incoming_target: target.to_string(),
+                        reason: format!("סותר את שורה {} בגיליון", first_row),
+                    });
+                }
+                continue;
+            }
+            seen.insert(source.to_string(), (row_number, target.to_string()));
+
+            let english = optional(columns.english);
+            let domain = optional(columns.domain);
+            let mut notes: Vec<String> = columns
+                .notes
+                .iter()
+                .map(|&column| cell(column))
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+                .collect();
+            if let Some(standards) = optional(columns.standards) {
+                notes.extend(
+                    standards
+                        .split(|c| c == ',' || c == ';')
+                        .map(str::trim)
+                        .filter(|standard| !standard.is_empty())
+                        .map(|standard| format!("{}{}", STANDARD_NOTE_PREFIX, standard)),
+                );
+            }
+
+            match dictionary.get_term(source) {
+                None => plan.new_terms.push((row_number, self.new_term(source, target, english, domain, notes))),
+                Some(existing) => {
+                    // צורה חלופית מותרת בשפת היעד אינה סתירה; צורה מיושנת או אסורה - כן
+                    let reason = match existing.status_of(target, self.target_lang) {
+                        None => Some("התרגום שונה מהתרגום במילון".to_string()),
+                        Some(status) if status.is_discouraged() => {
+                            Some(format!("התרגום מסומן במילון כצורה שאין להשתמש בה ({:?})", status))
+                        }
+                        Some(_) => None,
+                    };
+                    if let Some(reason) = reason {
+                        plan.conflicts.push(ImportConflict {
+                            row: row_number,
+                            source: source.to_string(),
+                            existing_target: existing.target.clone(),
+                            incoming_target: target.to_string(),
+                            reason,
+                        });
+                        continue;
+                    }
+                    let (term, changes) = merge_into(existing, english, domain, notes);
+                    if changes.is_empty() {
+                        plan.unchanged.push(source.to_string());
+                    } else {
+                        plan.changed.push(ChangedTerm { row: row_number, term, changes });
+                    }
+                }
+            }
+        }
+
+        Ok(plan)
+    }
+
+    fn new_term(
+        &self,
+        source: &str,
+        target: &str,
+        english: Option<String>,
+        domain: Option<String>,
+        notes: Vec<String>,
+    ) -> TechnicalTerm {
+        let mut term = TechnicalTerm {
+            source: source.to_string(),
+            target: target.to_string(),
+            source_lang: self.source_lang,
+            target_lang: self.target_lang,
+            english,
+            domain: domain.unwrap_or_else(|| self.mapping.default_domain.clone()),
+            context: Vec::new(),
+            usage_examples: Vec::new(),
+            variants: Vec::new(),
+            definition: None,
+            notes,
+            metadata: TermMetadata {
+                confidence_score: IMPORTED_CONFIDENCE,
+                last_updated: Utc::now(),
+                usage_count: 0,
+                verified: false,
+                source_references: vec![format!("import:{}", self.source_name)],
+                usage: UsageStats::default(),
+            },
+        };
+        // מונח מיובא מותר לשימוש, ומועדף רק לאחר סקירה
+        term.set_status(target, self.target_lang, UsageStatus::Admitted);
+        term
+    }
+}
+
+// השלמת מונח קיים: אנגלית ותחום מתעדכנים כשהם שונים, הערות ותקנים חדשים מתווספים
+fn merge_into(
+    existing: TechnicalTerm,
+    english: Option<String>,
+    domain: Option<String>,
+    notes: Vec<String>,
+) -> (TechnicalTerm, Vec<FieldChange>) {
+    let mut term = existing;
+    let mut changes = Vec::new();
+
+    if let Some(english) = english.filter(|english| term.english.as_deref() != Some(english.as_str())) {
+        changes.push(FieldChange {
+            field: "english".to_string(),
+            old_value: term.english.replace(english.clone()),
+            new_value: Some(english),
+        });
+    }
+    if let Some(domain) = domain.filter(|domain| *domain != term.domain) {
+        changes.push(FieldChange {
+            field: "domain".to_string(),
+            old_value: Some(std::mem::replace(&mut term.domain, domain.clone())),
+            new_value: Some(domain),
+        });
+    }
+    for note in notes {
+        if term.notes.contains(&note) {
+            continue;
+        }
+        let field = if note.starts_with(STANDARD_NOTE_PREFIX) { "standards" } else { "notes" };
+        changes.push(FieldChange {
+            field: field.to_string(),
+            old_value: None,
+            new_value: Some(note.clone()),
+        });
+        term.notes.push(note);
+    }
+
+    if !changes.is_empty() {
+        term.metadata.last_updated = Utc::now();
+    }
+    (term, changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHEET: &str = "\u{feff}מונח,רוסית,תחום,הערות,תקן\n\
+        ראש ספרינקלר,ороситель,fire_protection,K-factor 5.6,NFPA 13\n\
+        צנרת אספקה,питающий трубопровод,plumbing,,ГОСТ 3262-75; NFPA 13\n\
+        מגוף שליטה,задвижка,fire_protection,,\n\
+        לחץ עבודה,,general,,\n\
+        ראש ספרינקלר,спринклер,fire_protection,,\n";
+
+    fn dictionary() -> TechnicalDictionary {
+        let dictionary = TechnicalDictionary::in_memory();
+        let importer = GlossaryImporter::new(ColumnMapping::new("מונח", "רוסית"), Language::Hebrew, Language::Russian);
+        dictionary.insert_terms(vec![
+            importer.new_term("ראש ספרינקלר", "ороситель", None, Some("fire_protection".to_string()), Vec::new()),
+            importer.new_term("מגוף שליטה", "контрольно-сигнальный клапан", None, None, Vec::new()),
+        ]).unwrap();
+        dictionary
+    }
+
+    #[test]
+    fn test_suggested_mapping() {
+        let table = SpreadsheetTable::parse_csv(SHEET).unwrap();
+        let mapping = ColumnMapping::suggest(&table.headers).unwrap();
+        assert_eq!(mapping.source, "מונח");
+        assert_eq!(mapping.target, "רוסית");
+        assert_eq!(mapping.standards.as_deref(), Some("תקן"));
+        assert_eq!(mapping.notes, vec!["הערות".to_string()]);
+
+        assert!(ColumnMapping::new("מונח", "סינית").resolve(&table).is_err());
+    }
+
+    #[test]
+    fn test_dry_run_diff() {
+        let table = SpreadsheetTable::parse_csv(SHEET).unwrap();
+        let mapping = ColumnMapping::suggest(&table.headers).unwrap();
+        let dictionary = dictionary();
+        let plan = GlossaryImporter::new(mapping, Language::Hebrew, Language::Russian)
+            .plan(&table, &dictionary)
+            .unwrap();
+
+        assert_eq!(plan.new_terms.len(), 1);
+        let (_, pipe) = &plan.new_terms[0];
+        assert_eq!(pipe.domain, "plumbing");
+        assert!(pipe.notes.contains(&"standard: NFPA 13".to_string()));
+
+        assert_eq!(plan.changed.len(), 1);
+        assert_eq!(plan.changed[0].changes.len(), 2);
+
+        // סתירה מול המילון וסתירה בין שורות בגיליון
+        assert_eq!(plan.conflicts.len(), 2);
+        assert_eq!(plan.conflicts[0].existing_target, "контрольно-сигнальный клапан");
+        assert_eq!(plan.conflicts[1].row, 5);
+        assert_eq!(plan.skipped.len(), 1);
+
+        // הייבוא היבש אינו משנה את המילון
+        assert!(dictionary.get_term("צנרת אספקה").is_none());
+    }
+
+    #[test]
+    fn test_commit_tracks_changes() {
+        let table = SpreadsheetTable::parse_csv(SHEET).unwrap();
+        let mapping = ColumnMapping::suggest(&table.headers).unwrap();
+        let dictionary = dictionary();
+        let plan = GlossaryImporter::new(mapping, Language::Hebrew, Language::Russian)
+            .plan(&table, &dictionary)
+            .unwrap();
+
+        let mut knowledge_manager = KnowledgeManager::new();
+        assert_eq!(plan.commit(&dictionary, &mut knowledge_manager, "engineer").unwrap(), 2);
+        assert_eq!(
+            dictionary.get_term("צנרת אספקה").map(|term| term.target),
+            Some("питающий трубопровод".to_string())
+        );
+        let history = knowledge_manager.get_term_history("ראש ספרינקלר").unwrap();
+        assert_eq!(history.changes.len(), 2);
+        // הסתירה לא הוחלה
+        assert_eq!(dictionary.get_term("מגוף שליטה").unwrap().target, "контрольно-сигнальный клапан");
+    }
+
+    #[test]
+    fn test_discouraged_target_is_conflict() {
+        let dictionary = TechnicalDictionary::in_memory();
+        let importer = GlossaryImporter::new(ColumnMapping::new("מונח", "רוסית"), Language::Hebrew, Language::Russian);
+        let mut term = importer.new_term("ראש ספרינקלר", "ороситель", None, None, Vec::new());
+        term.set_status("спринклер", Language::Russian, UsageStatus::Forbidden);
+        dictionary.insert_terms(vec![term]).unwrap();
+
+        let table = SpreadsheetTable::parse_csv("מונח,רוסית\nראש ספרינקלר,спринклер\n").unwrap();
+        let plan = importer.plan(&table, &dictionary).unwrap();
+        assert_eq!(plan.conflicts.len(), 1);
+        assert!(plan.conflicts[0].reason.contains("Forbidden"));
+        assert!(plan.changed.is_empty() && plan.unchanged.is_empty());
+    }
+}
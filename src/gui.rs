@@ -3,31 +3,262 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 use anyhow::Result;
 use chrono::Utc;
-use crate::translation_engine::TranslationEngine;
+use tokio_util::sync::CancellationToken;
 use crate::quality_control::{QualityControl, IssueSeverity};
+use crate::streaming::{StreamingTranslator, TranslationStream, StreamEvent, TranslationProgress};
+use crate::language::Language;
+use crate::technical_dictionary::TechnicalDictionary;
+use crate::dictionary_search::{MatchMode, SearchPage, SortField, TermQuery};
+use crate::knowledge_sharing::CollaboratorRole;
+use crate::permissions::{Action, PermissionPolicy};
 
 pub struct ModernGui {
-    translation_engine: Arc<TranslationEngine>,
+    streaming_translator: Arc<StreamingTranslator>,
     quality_control: Arc<QualityControl>,
     source_text: String,
     target_text: String,
     source_lang: String,
     target_lang: String,
+    active_stream: Option<TranslationStream>,
+    progress: Option<TranslationProgress>,
+    status: Option<String>,
+    dictionary: Option<Arc<std::sync::Mutex<TechnicalDictionary>>>,
+    search: TermQuery,
+    // שדות טקסט למסננים; ריק - ללא סינון
+    search_domain: String,
+    search_standard: String,
+    search_results: Option<SearchPage>,
+    // תפקיד המשתמש בממשק, לפי מדיניות ההרשאות
+    role: CollaboratorRole,
+    policy: PermissionPolicy,
 }
 
 impl ModernGui {
-    pub fn new(translation_engine: Arc<TranslationEngine>, quality_control: Arc<QualityControl>) -> Self {
+    /// הממשק מוגבל לפעולות שמותרות לתפקיד של המשתמש
+    pub fn new(
+        streaming_translator: Arc<StreamingTranslator>,
+        quality_control: Arc<QualityControl>,
+        role: CollaboratorRole,
+        policy: PermissionPolicy,
+    ) -> Self {
         Self {
-            translation_engine,
+            streaming_translator,
             quality_control,
             source_text: String::new(),
             target_text: String::new(),
             source_lang: "he".to_string(),
             target_lang: "ru".to_string(),
+            active_stream: None,
+            progress: None,
+            status: None,
+            dictionary: None,
+            search: TermQuery::default(),
+            search_domain: String::new(),
+            search_standard: String::new(),
+            search_results: None,
+            role,
+            policy,
+        }
+    }
+
+    fn allowed(&self, action: Action) -> bool {
+        self.policy.allows(&self.role, action)
+    }
+
+    /// הצגת חלונית החיפוש במילון הטכני
+    pub fn with_dictionary(mut self, dictionary: Arc<std::sync::Mutex<TechnicalDictionary>>) -> Self {
+        self.dictionary = Some(dictionary);
+        self
+    }
+
+    fn start_translation(&mut self) {
+        self.target_text.clear();
+        self.progress = None;
+
+        match self.streaming_translator.translate_stream(
+            &self.source_text,
+            &self.source_lang,
+            &self.target_lang,
+            CancellationToken::new(),
+        ) {
+            Ok(stream) => {
+                self.active_stream = Some(stream);
+                self.status = None;
+            }
+            Err(e) => self.status = Some(format!("שגיאת תרגום: {}", e)),
+        }
+    }
+
+    // קליטת מקטעים שתורגמו מאז הציור הקודם
+    fn poll_translation(&mut self) {
+        let Some(stream) = self.active_stream.as_mut() else {
+            return;
+        };
+
+        while let Some(event) = stream.try_next() {
+            match event {
+                StreamEvent::Segment { segment, progress, .. } => {
+                    if !self.target_text.is_empty() {
+                        self.target_text.push(' ');
+                    }
+                    self.target_text.push_str(&segment.target);
+                    self.target_text.push('.');
+                    self.progress = Some(progress);
+                }
+                StreamEvent::Completed(output) => {
+                    self.target_text = output.text;
+                    self.status = Some("התרגום הושלם".to_string());
+                    self.active_stream = None;
+                    return;
+                }
+                StreamEvent::Cancelled(_) => {
+                    self.status = Some("התרגום בוטל".to_string());
+                    self.active_stream = None;
+                    return;
+                }
+            }
+        }
+    }
+
+    // התרגום כפי שנערך ואושר בידי המשתמש נרשם בסטטיסטיקת השימוש של המונחים
+    fn accept_translation(&mut self) {
+        let Some(dictionary) = &self.dictionary else {
+            return;
+        };
+        let result = dictionary
+            .lock()
+            .map_err(|_| anyhow::anyhow!("המילון הטכני אינו זמין"))
+            .and_then(|dictionary| dictionary.learn_from_usage(&self.source_text, &self.target_text));
+        self.status = Some(match result {
+            Ok(()) => "התרגום אושר".to_string(),
+            Err(e) => format!("שגיאה ברישום השימוש: {}", e),
+        });
+    }
+
+    fn run_search(&mut self) {
+        let Some(dictionary) = &self.dictionary else {
+            return;
+        };
+        let filter = |text: &str| Some(text.trim().to_string()).filter(|text| !text.is_empty());
+        self.search.domain = filter(&self.search_domain);
+        self.search.standard = filter(&self.search_standard);
+
+        match dictionary.lock() {
+            Ok(dictionary) => self.search_results = Some(dictionary.search(&self.search)),
+            Err(_) => self.status = Some("המילון הטכני אינו זמין".to_string()),
+        }
+    }
+
+    fn search_panel(&mut self, ui: &mut egui::Ui) {
+        let mut run = false;
+
+        ui.horizontal(|ui| {
+            run |= ui.text_edit_singleline(&mut self.search.text).lost_focus();
+            egui::ComboBox::from_id_source("search_mode")
+                .selected_text(match self.search.mode {
+                    MatchMode::Exact => "מדויק",
+                    MatchMode::Prefix => "תחילית",
+                    MatchMode::Substring => "מכיל",
+                    MatchMode::Wildcard => "תבנית (* ?)",
+                })
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.search.mode, MatchMode::Exact, "מדויק");
+                    ui.selectable_value(&mut self.search.mode, MatchMode::Prefix, "תחילית");
+                    ui.selectable_value(&mut self.search.mode, MatchMode::Substring, "מכיל");
+                    ui.selectable_value(&mut self.search.mode, MatchMode::Wildcard, "תבנית (* ?)");
+                });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("שפה:");
+            ui.radio_value(&mut self.search.lang, None, "הכול");
+            ui.radio_value(&mut self.search.lang, Some(Language::Hebrew), "עברית");
+            ui.radio_value(&mut self.search.lang, Some(Language::Russian), "רוסית");
+            ui.radio_value(&mut self.search.lang, Some(Language::English), "אנגלית");
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("תחום:");
+            ui.text_edit_singleline(&mut self.search_domain);
+            ui.label("תקן:");
+            ui.text_edit_singleline(&mut self.search_standard);
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("אימות:");
+            ui.radio_value(&mut self.search.verified, None, "הכול");
+            ui.radio_value(&mut self.search.verified, Some(true), "מאומתים");
+            ui.radio_value(&mut self.search.verified, Some(false), "לא מאומתים");
+
+            egui::ComboBox::from_id_source("search_sort")
+                .selected_text(sort_label(self.search.sort))
+                .show_ui(ui, |ui| {
+                    for field in [
+                        SortField::Source,
+                        SortField::Target,
+                        SortField::Domain,
+                        SortField::LastUpdated,
+                        SortField::Confidence,
+                        SortField::UsageCount,
+                    ] {
+                        ui.selectable_value(&mut self.search.sort, field, sort_label(field));
+                    }
+                });
+            ui.checkbox(&mut self.search.descending, "יורד");
+        });
+
+        ui.horizontal(|ui| {
+            if ui.button("חפש").clicked() {
+                self.search.offset = 0;
+                run = true;
+            }
+            let has_previous = self.search.offset > 0;
+            if ui.add_enabled(has_previous, egui::Button::new("הקודם")).clicked() {
+                self.search.offset = self.search.offset.saturating_sub(self.search.limit);
+                run = true;
+            }
+            let has_more = self.search_results.as_ref().map_or(false, SearchPage::has_more);
+            if ui.add_enabled(has_more, egui::Button::new("הבא")).clicked() {
+                self.search.offset += self.search.limit;
+                run = true;
+            }
+        });
+
+        if run {
+            self.run_search();
+        }
+
+        if let Some(page) = &self.search_results {
+            ui.label(format!(
+                "{}-{} מתוך {}",
+                (page.offset + 1).min(page.total),
+                page.offset + page.terms.len(),
+                page.total
+            ));
+            egui::Grid::new("search_results").striped(true).show(ui, |ui| {
+                for term in &page.terms {
+                    ui.label(&term.source);
+                    ui.label(&term.target);
+                    ui.label(&term.domain);
+                    ui.label(if term.metadata.verified { "✓" } else { "" });
+                    ui.end_row();
+                }
+            });
         }
     }
 }
 
+fn sort_label(field: SortField) -> &'static str {
+    match field {
+        SortField::Source => "מונח מקור",
+        SortField::Target => "תרגום",
+        SortField::Domain => "תחום",
+        SortField::LastUpdated => "עדכון אחרון",
+        SortField::Confidence => "ציון אמון",
+        SortField::UsageCount => "שימושים",
+    }
+}
+
 impl eframe::App for ModernGui {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::CentralPanel::default().show(ctx, |ui| {
@@ -37,30 +268,66 @@ impl eframe::App for ModernGui {
                 ui.label("שפת מקור:");
                 ui.radio_value(&mut self.source_lang, "he".to_string(), "עברית");
                 ui.radio_value(&mut self.source_lang, "ru".to_string(), "רוסית");
+                ui.radio_value(&mut self.source_lang, "en".to_string(), "אנגלית");
             });
 
             ui.horizontal(|ui| {
                 ui.label("שפת יעד:");
                 ui.radio_value(&mut self.target_lang, "ru".to_string(), "רוסית");
                 ui.radio_value(&mut self.target_lang, "he".to_string(), "עברית");
+                ui.radio_value(&mut self.target_lang, "en".to_string(), "אנגלית");
             });
 
             ui.text_edit_multiline(&mut self.source_text);
 
-            if ui.button("תרגם").clicked() {
-                let engine = self.translation_engine.clone();
-                let source_text = self.source_text.clone();
-                let source_lang = self.source_lang.clone();
-                let target_lang = self.target_lang.clone();
-
-                tokio::spawn(async move {
-                    if let Ok(translated) = engine.translate(&source_text, &source_lang, &target_lang).await {
-                        // TODO: עדכון התרגום בממשק
+            ui.horizontal(|ui| {
+                let running = self.active_stream.is_some();
+                if ui.add_enabled(!running, egui::Button::new("תרגם")).clicked() {
+                    self.start_translation();
+                }
+                if ui.add_enabled(running, egui::Button::new("בטל")).clicked() {
+                    if let Some(stream) = &self.active_stream {
+                        stream.cancel();
                     }
-                });
+                }
+            });
+
+            self.poll_translation();
+
+            if let Some(progress) = &self.progress {
+                ui.add(egui::ProgressBar::new(progress.fraction()).text(format!(
+                    "{}/{} מקטעים, {}/{} מילים",
+                    progress.segments_done,
+                    progress.segments_total,
+                    progress.words_done,
+                    progress.words_total
+                )));
+            }
+            if let Some(status) = &self.status {
+                ui.label(status);
             }
 
             ui.text_edit_multiline(&mut self.target_text);
+            if self.dictionary.is_some() && self.allowed(Action::EditTerm) {
+                let done = self.active_stream.is_none() && !self.target_text.is_empty();
+                if ui.add_enabled(done, egui::Button::new("אשר תרגום")).clicked() {
+                    self.accept_translation();
+                }
+            }
+
+            if self.dictionary.is_some() {
+                ui.separator();
+                if self.allowed(Action::ViewTerms) {
+                    ui.collapsing("חיפוש במילון", |ui| self.search_panel(ui));
+                } else {
+                    ui.label("אין הרשאה לצפייה במילון");
+                }
+            }
+
+            // המשך ציור כל עוד מגיעים מקטעים
+            if self.active_stream.is_some() {
+                ctx.request_repaint();
+            }
         });
     }
 } 
\ No newline at end of file
@@ -1,8 +1,17 @@
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
 use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc, Duration};
+use rusqlite::Connection;
+use serde_json::Value;
 use crate::technical_dictionary::{TechnicalTerm, TechnicalDictionary};
-use anyhow::Result;
+use crate::term_usage::UsageOutcome;
+use crate::dictionary_merge::{self, FieldConflict, ThreeWayMerge, WHOLE_TERM};
+use crate::knowledge_store::{self, KnowledgeStore, ACTIVITY_LOG_CAPACITY};
+use crate::permissions::{hash_api_key, Action, PermissionPolicy};
+use crate::review_workflow::ReviewQuorum;
+use crate::version_diff::VersionDiff;
+use anyhow::{Context, Result};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DictionaryVersion {
@@ -53,9 +62,12 @@ pub struct CollaboratorInfo {
     pub last_active: DateTime<Utc>,
     pub current_activity: Option<CollaboratorActivity>,
     pub edit_history: VecDeque<TermChange>,
+    /// גיבוב מפתח הגישה לממשק ה-HTTP; `None` - אין למשתמש מפתח
+    #[serde(default)]
+    pub api_key_hash: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum CollaboratorRole {
     Admin,
     Editor,
@@ -104,9 +116,18 @@ pub struct ReviewRequest {
     pub reviewers: Vec<String>,
     pub status: ReviewStatus,
     pub comments: Vec<ReviewComment>,
+    /// מונח חדש שהוצע לבדיקה ועדיין לא נוסף למילון
+    #[serde(default)]
+    pub proposed_term: Option<TechnicalTerm>,
+    /// הסוקרים שאישרו בסבב הנוכחי; מתאפס כשנדרשים תיקונים
+    #[serde(default)]
+    pub approvals: Vec<String>,
+    /// הסוקרים שהחלטתם כבר נרשמה בסטטיסטיקת השימוש של המונח
+    #[serde(default)]
+    pub usage_recorded_by: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ReviewStatus {
     Pending,
     InReview,
@@ -130,6 +151,9 @@ pub struct ConflictResolution {
     pub timestamp: DateTime<Utc>,
     pub resolution_type: ResolutionType,
     pub comments: String,
+    /// השדה שבו נפתרה הסתירה, בסתירות מיזוג ברמת השדה
+    #[serde(default)]
+    pub field: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -147,8 +171,14 @@ pub struct KnowledgeManager {
     edit_locks: HashMap<String, EditLock>,
     review_requests: HashMap<String, ReviewRequest>,
     conflict_resolutions: Vec<ConflictResolution>,
+    // סתירות מיזוג שממתינות להכרעה, לפי מזהה
+    field_conflicts: HashMap<String, FieldConflict>,
     last_sync: DateTime<Utc>,
     activity_log: VecDeque<CollaboratorActivity>,
+    // מאגר SQLite; `None` למצב בזיכרון בלבד
+    store: Option<KnowledgeStore>,
+    policy: PermissionPolicy,
+    review_quorum: ReviewQuorum,
 }
 
 impl KnowledgeManager {
@@ -160,8 +190,99 @@ impl KnowledgeManager {
             edit_locks: HashMap::new(),
             review_requests: HashMap::new(),
             conflict_resolutions: Vec::new(),
+            field_conflicts: HashMap::new(),
             last_sync: Utc::now(),
-            activity_log: VecDeque::with_capacity(1000),
+            activity_log: VecDeque::with_capacity(ACTIVITY_LOG_CAPACITY),
+            store: None,
+            policy: PermissionPolicy::default(),
+            review_quorum: ReviewQuorum::default(),
+        }
+    }
+
+    /// טעינת המצב ממאגר SQLite; כל שינוי מכאן והלאה נשמר בו
+    pub fn open(path: &Path) -> Result<Self> {
+        Self::with_store(KnowledgeStore::open(path)?)
+    }
+
+    pub fn with_store(store: KnowledgeStore) -> Result<Self> {
+        let snapshot = store.load()?;
+        Ok(Self {
+            versions: snapshot.versions,
+            change_history: snapshot.change_history,
+            collaborators: snapshot.collaborators,
+            edit_locks: snapshot.edit_locks,
+            review_requests: snapshot.review_requests,
+            conflict_resolutions: snapshot.conflict_resolutions,
+            field_conflicts: snapshot.field_conflicts,
+            last_sync: snapshot.last_sync.unwrap_or_else(Utc::now),
+            activity_log: snapshot.activity_log,
+            store: Some(store),
+            policy: PermissionPolicy::default(),
+            review_quorum: ReviewQuorum::default(),
+        })
+    }
+
+    /// החלפת מדיניות ההרשאות (ברירת המחדל: `PermissionPolicy::default`)
+    pub fn with_policy(mut self, policy: PermissionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn policy(&self) -> &PermissionPolicy {
+        &self.policy
+    }
+
+    /// מספר האישורים הנדרש לפי תחום (ברירת המחדל: אישור אחד)
+    pub fn with_review_quorum(mut self, review_quorum: ReviewQuorum) -> Self {
+        self.review_quorum = review_quorum;
+        self
+    }
+
+    /// בדיקת הרשאה לפי התפקיד הרשום של המשתמש; נקראת לפני כל שינוי.
+    /// שגיאת הדחייה היא `PermissionError`, וניתן לזהות אותה ב-`downcast_ref`.
+    pub fn authorize(&self, user_id: &str, action: Action) -> Result<()> {
+        let role = self.collaborators.get(user_id).map(|collaborator| &collaborator.role);
+        self.policy
+            .check(user_id, role, !self.collaborators.is_empty(), action)
+            .map_err(Into::into)
+    }
+
+    /// התפקיד שבו פועל המשתמש בבדיקת ההרשאות; `None` - משתמש לא רשום
+    pub fn effective_role(&self, user_id: &str) -> Option<CollaboratorRole> {
+        let role = self.collaborators.get(user_id).map(|collaborator| &collaborator.role);
+        self.policy.effective_role(role, !self.collaborators.is_empty()).cloned()
+    }
+
+    /// הנפקת מפתח גישה למשתף פעולה רשום. המפתח מוחזר פעם אחת בלבד ומחליף מפתח קודם
+    pub fn issue_api_key(&mut self, issued_by: &str, user_id: &str) -> Result<String> {
+        self.authorize(issued_by, Action::ManageCollaborators)?;
+        let mut collaborator = self
+            .collaborators
+            .get(user_id)
+            .cloned()
+            .with_context(|| format!("{} אינו רשום כמשתף פעולה", user_id))?;
+        let key = format!("rh_{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple());
+        collaborator.api_key_hash = Some(hash_api_key(&key));
+
+        self.persist(|conn| knowledge_store::put_collaborator(conn, &collaborator))?;
+        self.collaborators.insert(user_id.to_string(), collaborator);
+        Ok(key)
+    }
+
+    /// המשתמש שמפתח הגישה שייך לו
+    pub fn authenticate(&self, api_key: &str) -> Option<&str> {
+        let hash = hash_api_key(api_key);
+        self.collaborators
+            .values()
+            .find(|collaborator| collaborator.api_key_hash.as_deref() == Some(hash.as_str()))
+            .map(|collaborator| collaborator.user_id.as_str())
+    }
+
+    // כתיבה למאגר בטרנזקציה אחת, לפני שהשינוי נרשם בזיכרון
+    fn persist(&mut self, f: impl FnOnce(&Connection) -> Result<()>) -> Result<()> {
+        match self.store.as_mut() {
+            Some(store) => store.write(f),
+            None => Ok(()),
         }
     }
 
@@ -172,24 +293,30 @@ impl KnowledgeManager {
         created_by: String,
         description: String,
     ) -> Result<()> {
+        self.authorize(&created_by, Action::CreateVersion)?;
+        let terms = dictionary.all_terms();
         let version = DictionaryVersion {
             version_id: version_id.clone(),
             created_at: Utc::now(),
             created_by,
             description,
-            terms: dictionary.get_all_terms_map()?,
-            tags: dictionary.get_all_tags_set()?,
+            tags: terms.iter().map(|term| term.domain.clone()).collect(),
+            terms: terms.into_iter().map(|term| (term.source.clone(), term)).collect(),
         };
 
+        self.persist(|conn| knowledge_store::put_version(conn, &version))?;
         self.versions.insert(version_id, version);
         Ok(())
     }
 
+    /// מיזוג מילון אחר לתוך מילון הבסיס; המונח החדש יותר גובר
     pub fn merge_dictionaries(
-        &self,
-        base_dict: &mut TechnicalDictionary,
+        &mut self,
+        base_dict: &TechnicalDictionary,
         other_dict: &TechnicalDictionary,
+        merged_by: &str,
     ) -> Result<DictionaryMergeReport> {
+        self.authorize(merged_by, Action::MergeDictionaries)?;
         let mut report = DictionaryMergeReport {
             added_terms: Vec::new(),
             updated_terms: Vec::new(),
@@ -197,32 +324,223 @@ impl KnowledgeManager {
             timestamp: Utc::now(),
         };
 
-        for (term_id, other_term) in other_dict.get_all_terms_map()? {
-            match base_dict.get_term(&term_id) {
+        for other_term in other_dict.all_terms() {
+            match base_dict.get_term(&other_term.source) {
                 Some(base_term) => {
-                    if base_term.last_updated < other_term.last_updated {
-                        // העדכון בדיקשנרי השני חדש יותר
-                        report.updated_terms.push(other_term.clone());
-                        base_dict.update_term(&term_id, other_term.clone())?;
-                    } else if base_term.last_updated > other_term.last_updated {
-                        // אין צורך לעדכן
-                        continue;
-                    } else {
+                    let (base_time, other_time) = (base_term.metadata.last_updated, other_term.metadata.last_updated);
+                    if base_time < other_time {
+                        // העדכון במילון השני חדש יותר
+                        report.updated_terms.push(other_term);
+                    } else if base_time == other_time && base_term.target != other_term.target {
                         // קונפליקט - אותו זמן עדכון
-                        report.conflicting_terms.push((base_term.clone(), other_term.clone()));
+                        report.conflicting_terms.push((base_term, other_term));
                     }
                 }
                 None => {
                     // מונח חדש
-                    report.added_terms.push(other_term.clone());
-                    base_dict.add_term(other_term.clone())?;
+                    report.added_terms.push(other_term);
                 }
             }
         }
 
+        let changes: Vec<(String, TermChange)> = report
+            .added_terms
+            .iter()
+            .map(|term| (term, ChangeType::Addition, None))
+            .chain(report.updated_terms.iter().map(|term| {
+                let old = base_dict.get_term(&term.source).map(|base| base.target);
+                (term, ChangeType::Modification, old)
+            }))
+            .map(|(term, change_type, old_value)| {
+                (term.source.clone(), TermChange {
+                    timestamp: report.timestamp,
+                    changed_by: merged_by.to_string(),
+                    change_type,
+                    old_value,
+                    new_value: Some(term.target.clone()),
+                    field: "target".to_string(),
+                })
+            })
+            .collect();
+        let merged: Vec<TechnicalTerm> = report.added_terms.iter().chain(&report.updated_terms).cloned().collect();
+
+        self.apply_term_changes(base_dict, merged, changes)?;
         Ok(report)
     }
 
+    /// מיזוג תלת-כיווני מול גרסה משותפת שמורה: שינויים בשדות שונים מתמזגים לתוך מילון הבסיס,
+    /// ושינויים שונים באותו שדה נשמרים כסתירות פתוחות עד `resolve_field_conflict`
+    pub fn merge_with_ancestor(
+        &mut self,
+        ancestor_version_id: &str,
+        base_dict: &TechnicalDictionary,
+        other_dict: &TechnicalDictionary,
+        merged_by: &str,
+    ) -> Result<ThreeWayMerge> {
+        self.authorize(merged_by, Action::MergeDictionaries)?;
+        let ancestor = self
+            .versions
+            .get(ancestor_version_id)
+            .with_context(|| format!("הגרסה המשותפת לא נמצאה: {}", ancestor_version_id))?;
+        let merge = dictionary_merge::merge_terms(&ancestor.terms, &base_dict.all_terms(), &other_dict.all_terms())?;
+
+        let timestamp = Utc::now();
+        let changes = merge
+            .applied
+            .iter()
+            .map(|applied| {
+                (applied.term_id.clone(), field_change(timestamp, merged_by, &applied.field, applied.old_value.as_ref(), applied.new_value.as_ref()))
+            })
+            .collect();
+        let conflicts = merge.conflicts.clone();
+        self.commit_terms(base_dict, merge.updated_terms.clone(), &merge.removed_terms, changes, |conn| {
+            for conflict in &conflicts {
+                knowledge_store::put_field_conflict(conn, conflict)?;
+            }
+            Ok(())
+        })?;
+        for conflict in conflicts {
+            self.field_conflicts.insert(conflict.conflict_id.clone(), conflict);
+        }
+        Ok(merge)
+    }
+
+    /// סתירות מיזוג שממתינות להכרעה, לפי מונח ושדה
+    pub fn pending_field_conflicts(&self) -> Vec<&FieldConflict> {
+        let mut conflicts: Vec<_> = self.field_conflicts.values().collect();
+        conflicts.sort_by(|a, b| (&a.term_id, &a.field).cmp(&(&b.term_id, &b.field)));
+        conflicts
+    }
+
+    /// הכרעה בסתירת מיזוג: החלת הערך שנבחר על המילון, רישום השינוי והפתרון וסגירת הסתירה.
+    /// `custom` נדרש רק ב-`ResolutionType::Custom`; בסתירה על המונח כולו הוא המונח המלא ב-JSON.
+    pub fn resolve_field_conflict(
+        &mut self,
+        dictionary: &TechnicalDictionary,
+        conflict_id: &str,
+        resolved_by: &str,
+        resolution_type: ResolutionType,
+        custom: Option<Value>,
+        comments: String,
+    ) -> Result<()> {
+        self.authorize(resolved_by, Action::ResolveConflict)?;
+        let conflict = self
+            .field_conflicts
+            .get(conflict_id)
+            .cloned()
+            .with_context(|| format!("סתירת המיזוג לא נמצאה: {}", conflict_id))?;
+        let value = conflict.resolve(&resolution_type, custom)?;
+        let current = dictionary.get_term(&conflict.term_id);
+        let old_value = current.as_ref().map(|term| dictionary_merge::term_field(term, &conflict.field)).transpose()?;
+        let resolved = dictionary_merge::apply_resolution(&conflict, current, value)?;
+        let new_value = resolved.as_ref().map(|term| dictionary_merge::term_field(term, &conflict.field)).transpose()?;
+
+        let timestamp = Utc::now();
+        let change = field_change(timestamp, resolved_by, &conflict.field, old_value.as_ref(), new_value.as_ref());
+        let removed = if resolved.is_none() { vec![conflict.term_id.clone()] } else { Vec::new() };
+        let resolution = ConflictResolution {
+            term_id: conflict.term_id.clone(),
+            resolved_by: resolved_by.to_string(),
+            timestamp,
+            resolution_type,
+            comments,
+            field: Some(conflict.field.clone()),
+        };
+
+        self.commit_terms(
+            dictionary,
+            resolved.into_iter().collect(),
+            &removed,
+            vec![(conflict.term_id.clone(), change)],
+            |conn| {
+                knowledge_store::delete_field_conflict(conn, conflict_id)?;
+                knowledge_store::append_resolution(conn, &resolution)
+            },
+        )?;
+        self.field_conflicts.remove(conflict_id);
+        self.conflict_resolutions.push(resolution);
+        Ok(())
+    }
+
+    /// שמירת מונחים במילון יחד עם רישום השינויים שלהם. המילון משתנה רק אחרי שהרישום נשמר:
+    /// מונח לא תקין או רישום שנכשל אינם משאירים שינוי חלקי במילון.
+    pub fn apply_term_changes(
+        &mut self,
+        dictionary: &TechnicalDictionary,
+        terms: Vec<TechnicalTerm>,
+        changes: Vec<(String, TermChange)>,
+    ) -> Result<()> {
+        let authors: HashSet<&str> = changes.iter().map(|(_, change)| change.changed_by.as_str()).collect();
+        for author in authors {
+            self.authorize(author, Action::EditTerm)?;
+        }
+        self.commit_terms(dictionary, terms, &[], changes, |_| Ok(()))
+    }
+
+    /// עריכת מונח או הוספתו בשם משתמש, עם רישום השינוי בתרגום
+    pub fn update_term(&mut self, dictionary: &TechnicalDictionary, changed_by: &str, term: TechnicalTerm) -> Result<()> {
+        self.authorize(changed_by, Action::EditTerm)?;
+        let old_value = dictionary.get_term(&term.source).map(|old| old.target);
+        let change = TermChange {
+            timestamp: Utc::now(),
+            changed_by: changed_by.to_string(),
+            change_type: if old_value.is_some() { ChangeType::Modification } else { ChangeType::Addition },
+            old_value,
+            new_value: Some(term.target.clone()),
+            field: "target".to_string(),
+        };
+        self.commit_terms(dictionary, vec![term.clone()], &[], vec![(term.source, change)], |_| Ok(()))
+    }
+
+    /// מחיקת מונח בשם משתמש; מחזיר `false` אם המונח לא היה במילון
+    pub fn remove_term(&mut self, dictionary: &TechnicalDictionary, removed_by: &str, source: &str) -> Result<bool> {
+        self.authorize(removed_by, Action::DeleteTerm)?;
+        let Some(existing) = dictionary.get_term(source) else {
+            return Ok(false);
+        };
+        let change = TermChange {
+            timestamp: Utc::now(),
+            changed_by: removed_by.to_string(),
+            change_type: ChangeType::Deletion,
+            old_value: Some(existing.target),
+            new_value: None,
+            field: "target".to_string(),
+        };
+        self.commit_terms(dictionary, Vec::new(), &[source.to_string()], vec![(source.to_string(), change)], |_| Ok(()))?;
+        Ok(true)
+    }
+
+    // רישום השינויים וכתיבות נוספות למאגר בטרנזקציה אחת, ורק אחרי שהיא נשמרה -
+    // כתיבת המונחים ומחיקתם במילון. המונחים נבדקים מראש, כך שהמילון לא ידחה אותם אחרי הרישום
+    fn commit_terms(
+        &mut self,
+        dictionary: &TechnicalDictionary,
+        terms: Vec<TechnicalTerm>,
+        removed: &[String],
+        changes: Vec<(String, TermChange)>,
+        extra: impl FnOnce(&Connection) -> Result<()>,
+    ) -> Result<()> {
+        dictionary.validate_terms(&terms)?;
+        if let Some(store) = self.store.as_mut() {
+            store.write(|conn| {
+                for (term_id, change) in &changes {
+                    knowledge_store::append_change(conn, term_id, change)?;
+                }
+                extra(conn)
+            })?;
+        }
+        if !terms.is_empty() {
+            dictionary.insert_terms(terms)?;
+        }
+        for source in removed {
+            dictionary.remove_term(source)?;
+        }
+        for (term_id, change) in changes {
+            self.remember_change(term_id, change);
+        }
+        Ok(())
+    }
+
     pub fn track_change(
         &mut self,
         term_id: String,
@@ -231,7 +549,8 @@ impl KnowledgeManager {
         field: String,
         old_value: Option<String>,
         new_value: Option<String>,
-    ) {
+    ) -> Result<()> {
+        self.authorize(&changed_by, Action::EditTerm)?;
         let change = TermChange {
             timestamp: Utc::now(),
             changed_by,
@@ -241,6 +560,12 @@ impl KnowledgeManager {
             field,
         };
 
+        self.persist(|conn| knowledge_store::append_change(conn, &term_id, &change))?;
+        self.remember_change(term_id, change);
+        Ok(())
+    }
+
+    fn remember_change(&mut self, term_id: String, change: TermChange) {
         self.change_history
             .entry(term_id.clone())
             .or_insert_with(|| TermChangeHistory {
@@ -269,44 +594,126 @@ impl KnowledgeManager {
         &self,
         version1_id: &str,
         version2_id: &str,
-    ) -> Option<DictionaryMergeReport> {
-        let v1 = self.versions.get(version1_id)?;
-        let v2 = self.versions.get(version2_id)?;
+    ) -> Result<DictionaryMergeReport> {
+        let v1 = self
+            .versions
+            .get(version1_id)
+            .with_context(|| format!("הגרסה לא נמצאה: {}", version1_id))?;
+        let v2 = self
+            .versions
+            .get(version2_id)
+            .with_context(|| format!("הגרסה לא נמצאה: {}", version2_id))?;
+        let diff = VersionDiff::between(v1, v2)?;
 
         let mut report = DictionaryMergeReport {
-            added_terms: Vec::new(),
+            added_terms: diff.added,
             updated_terms: Vec::new(),
             conflicting_terms: Vec::new(),
             timestamp: Utc::now(),
         };
 
-        // מציאת מונחים שנוספו או עודכנו
-        for (term_id, term_v2) in &v2.terms {
-            match v1.terms.get(term_id) {
-                Some(term_v1) => {
-                    if term_v1 != term_v2 {
-                        report.updated_terms.push(term_v2.clone());
-                    }
-                }
-                None => {
-                    report.added_terms.push(term_v2.clone());
-                }
+        for term in &diff.modified {
+            let (term_v1, term_v2) = (&v1.terms[&term.term_id], &v2.terms[&term.term_id]);
+            if term_v1.metadata.last_updated == term_v2.metadata.last_updated {
+                // קונפליקט - תוכן שונה באותו זמן עדכון
+                report.conflicting_terms.push((term_v1.clone(), term_v2.clone()));
+            } else {
+                report.updated_terms.push(term_v2.clone());
             }
         }
 
-        // מציאת קונפליקטים
-        for (term_id, term_v1) in &v1.terms {
-            if let Some(term_v2) = v2.terms.get(term_id) {
-                if term_v1.last_updated == term_v2.last_updated && term_v1 != term_v2 {
-                    report.conflicting_terms.push((term_v1.clone(), term_v2.clone()));
-                }
+        Ok(report)
+    }
+
+    /// הבדלים ברמת השדה בין שתי גרסאות, לדוח Markdown או HTML
+    pub fn diff_versions(&self, from_version_id: &str, to_version_id: &str) -> Result<VersionDiff> {
+        let from = self
+            .versions
+            .get(from_version_id)
+            .with_context(|| format!("הגרסה לא נמצאה: {}", from_version_id))?;
+        let to = self
+            .versions
+            .get(to_version_id)
+            .with_context(|| format!("הגרסה לא נמצאה: {}", to_version_id))?;
+        VersionDiff::between(from, to)
+    }
+
+    /// שחזור המילון לגרסה קודמת. השחזור נשמר כגרסה חדשה `new_version_id` וכל שדה שהוחזר
+    /// נרשם בהיסטוריית השינויים, כך שגם השחזור עצמו ניתן לביטול. מחזיר את ההבדלים שהוחלו.
+    pub fn rollback_to_version(
+        &mut self,
+        dictionary: &TechnicalDictionary,
+        version_id: &str,
+        new_version_id: &str,
+        rolled_back_by: &str,
+    ) -> Result<VersionDiff> {
+        self.authorize(rolled_back_by, Action::RollbackVersion)?;
+        if self.versions.contains_key(new_version_id) {
+            anyhow::bail!("הגרסה {} כבר קיימת", new_version_id);
+        }
+        let target = self
+            .versions
+            .get(version_id)
+            .with_context(|| format!("הגרסה לא נמצאה: {}", version_id))?
+            .clone();
+        let current: HashMap<String, TechnicalTerm> = dictionary
+            .all_terms()
+            .into_iter()
+            .map(|term| (term.source.clone(), term))
+            .collect();
+        let diff = VersionDiff::between_terms("current", &current, version_id, &target.terms)?;
+
+        // רק שדות התוכן חוזרים לגרסה; נתוני השימוש והאמון של המונח הנוכחי נשמרים
+        let timestamp = Utc::now();
+        let mut restored_terms = HashMap::new();
+        for (id, term) in &target.terms {
+            let term = match current.get(id) {
+                Some(existing) => dictionary_merge::copy_content(existing, term)?,
+                None => term.clone(),
+            };
+            restored_terms.insert(id.clone(), term);
+        }
+        let mut restored = Vec::new();
+        let mut changes = Vec::new();
+        for term in &diff.added {
+            changes.push((term.source.clone(), field_change(timestamp, rolled_back_by, WHOLE_TERM, None, Some(&Value::String(term.target.clone())))));
+            restored.push(term.source.clone());
+        }
+        for term in &diff.modified {
+            for field in &term.fields {
+                changes.push((term.term_id.clone(), field_change(timestamp, rolled_back_by, &field.field, Some(&field.before), Some(&field.after))));
+            }
+            restored.push(term.term_id.clone());
+        }
+        for term in &diff.removed {
+            changes.push((term.source.clone(), field_change(timestamp, rolled_back_by, WHOLE_TERM, Some(&Value::String(term.target.clone())), None)));
+        }
+        // מונחים משוחזרים מקבלים זמן עדכון חדש, כדי שמיזוג לפי זמן עדכון יעדיף אותם
+        let mut terms = Vec::new();
+        for id in &restored {
+            if let Some(term) = restored_terms.get_mut(id) {
+                term.metadata.last_updated = timestamp;
+                terms.push(term.clone());
             }
         }
+        let removed: Vec<String> = diff.removed.iter().map(|term| term.source.clone()).collect();
 
-        Some(report)
+        let version = DictionaryVersion {
+            version_id: new_version_id.to_string(),
+            created_at: timestamp,
+            created_by: rolled_back_by.to_string(),
+            description: format!("שחזור לגרסה {}", version_id),
+            tags: restored_terms.values().map(|term| term.domain.clone()).collect(),
+            terms: restored_terms,
+        };
+        self.commit_terms(dictionary, terms, &removed, changes, |conn| knowledge_store::put_version(conn, &version))?;
+        self.versions.insert(version.version_id.clone(), version);
+        Ok(diff)
     }
 
-    pub fn add_collaborator(&mut self, user_id: String, name: String, role: CollaboratorRole) {
+    /// רישום משתף פעולה; את הראשון ניתן לרשום לפי `bootstrap_role` של המדיניות
+    pub fn add_collaborator(&mut self, added_by: &str, user_id: String, name: String, role: CollaboratorRole) -> Result<()> {
+        self.authorize(added_by, Action::ManageCollaborators)?;
         let collaborator = CollaboratorInfo {
             user_id: user_id.clone(),
             name,
@@ -314,22 +721,34 @@ impl KnowledgeManager {
             last_active: Utc::now(),
             current_activity: None,
             edit_history: VecDeque::with_capacity(100),
+            api_key_hash: None,
         };
+        self.persist(|conn| knowledge_store::put_collaborator(conn, &collaborator))?;
         self.collaborators.insert(user_id, collaborator);
+        Ok(())
     }
 
-    pub fn update_collaborator_activity(&mut self, user_id: &str, activity: CollaboratorActivity) {
-        if let Some(collaborator) = self.collaborators.get_mut(user_id) {
-            collaborator.last_active = Utc::now();
-            collaborator.current_activity = Some(activity.clone());
-            self.activity_log.push_front(activity);
-            if self.activity_log.len() > 1000 {
-                self.activity_log.pop_back();
-            }
+    pub fn update_collaborator_activity(&mut self, user_id: &str, activity: CollaboratorActivity) -> Result<()> {
+        let Some(mut collaborator) = self.collaborators.get(user_id).cloned() else {
+            return Ok(());
+        };
+        collaborator.last_active = Utc::now();
+        collaborator.current_activity = Some(activity.clone());
+
+        self.persist(|conn| {
+            knowledge_store::put_collaborator(conn, &collaborator)?;
+            knowledge_store::append_activity(conn, &activity)
+        })?;
+        self.collaborators.insert(user_id.to_string(), collaborator);
+        self.activity_log.push_front(activity);
+        if self.activity_log.len() > ACTIVITY_LOG_CAPACITY {
+            self.activity_log.pop_back();
         }
+        Ok(())
     }
 
     pub fn acquire_edit_lock(&mut self, term_id: String, user_id: String) -> Result<bool> {
+        self.authorize(&user_id, Action::LockTerm)?;
         // בדיקה אם המונח כבר נעול
         if let Some(lock) = self.edit_locks.get(&term_id) {
             if lock.expires_at > Utc::now() {
@@ -344,6 +763,7 @@ impl KnowledgeManager {
             locked_at: Utc::now(),
             expires_at: Utc::now() + Duration::minutes(30),
         };
+        self.persist(|conn| knowledge_store::put_edit_lock(conn, &lock))?;
         self.edit_locks.insert(term_id, lock);
         Ok(true)
     }
@@ -351,6 +771,7 @@ impl KnowledgeManager {
     pub fn release_edit_lock(&mut self, term_id: &str, user_id: &str) -> Result<bool> {
         if let Some(lock) = self.edit_locks.get(term_id) {
             if lock.locked_by == user_id {
+                self.persist(|conn| knowledge_store::delete_edit_lock(conn, term_id))?;
                 self.edit_locks.remove(term_id);
                 return Ok(true);
             }
@@ -360,24 +781,57 @@ impl KnowledgeManager {
 
     pub fn create_review_request(
         &mut self,
+        dictionary: &TechnicalDictionary,
         term_id: String,
         requested_by: String,
         reviewers: Vec<String>,
     ) -> Result<String> {
-        let request_id = uuid::Uuid::new_v4().to_string();
-        let request = ReviewRequest {
-            request_id: request_id.clone(),
-            term_id,
-            requested_by,
-            requested_at: Utc::now(),
-            reviewers,
-            status: ReviewStatus::Pending,
-            comments: Vec::new(),
-        };
-        self.review_requests.insert(request_id.clone(), request);
+        self.authorize(&requested_by, Action::RequestReview)?;
+        let domain = dictionary.get_term(&term_id).map(|term| term.domain);
+        self.check_quorum_reachable(&reviewers, domain.as_deref())?;
+        let request = new_review_request(term_id, requested_by, reviewers);
+        let request_id = request.request_id.clone();
+        self.save_review_request(request)?;
+        Ok(request_id)
+    }
+
+    /// הצעת מונח חדש לבדיקה; המונח נשמר בבקשה ואינו נוסף למילון עד לאישור
+    pub fn propose_term(
+        &mut self,
+        term: TechnicalTerm,
+        requested_by: String,
+        reviewers: Vec<String>,
+        evidence: String,
+    ) -> Result<String> {
+        self.authorize(&requested_by, Action::RequestReview)?;
+        self.check_quorum_reachable(&reviewers, Some(&term.domain))?;
+        let mut request = new_review_request(term.source.clone(), requested_by.clone(), reviewers);
+        request.proposed_term = Some(term);
+        request.comments.push(ReviewComment {
+            author: requested_by,
+            timestamp: Utc::now(),
+            content: evidence,
+            field: None,
+        });
+        let request_id = request.request_id.clone();
+        self.save_review_request(request)?;
         Ok(request_id)
     }
 
+    // בקשה עם פחות סוקרים מהמניין של התחום לא תוכל להיות מאושרת לעולם.
+    // רשימת סוקרים ריקה פתוחה לכל סוקר
+    fn check_quorum_reachable(&self, reviewers: &[String], domain: Option<&str>) -> Result<()> {
+        let required = self.review_quorum.required(domain);
+        if !reviewers.is_empty() && reviewers.len() < required {
+            anyhow::bail!("נדרשים לפחות {} סוקרים בתחום {}, הוגדרו {}", required, domain.unwrap_or("-"), reviewers.len());
+        }
+        Ok(())
+    }
+
+    pub fn get_review_request(&self, request_id: &str) -> Option<&ReviewRequest> {
+        self.review_requests.get(request_id)
+    }
+
     pub fn add_review_comment(
         &mut self,
         request_id: &str,
@@ -385,25 +839,178 @@ impl KnowledgeManager {
         content: String,
         field: Option<String>,
     ) -> Result<()> {
-        if let Some(request) = self.review_requests.get_mut(request_id) {
-            let comment = ReviewComment {
+        self.authorize(&author, Action::CommentReview)?;
+        self.update_review_request(request_id, |request| {
+            request.comments.push(ReviewComment {
                 author,
                 timestamp: Utc::now(),
                 content,
                 field,
-            };
-            request.comments.push(comment);
+            });
+        })
+    }
+
+    /// מעבר מצב ידני לפי מחזור החיים של הסקירה. אישור נעשה רק בהחלטות סוקרים;
+    /// המגיש רשאי להחזיר את בקשתו לסקירה אחרי תיקון
+    pub fn update_review_status(&mut self, request_id: &str, changed_by: &str, status: ReviewStatus) -> Result<()> {
+        let mut request = self.review_request_for_update(request_id)?;
+        let resubmission = request.requested_by == changed_by && status == ReviewStatus::InReview;
+        self.authorize(changed_by, if resubmission { Action::RequestReview } else { Action::ChangeReviewStatus })?;
+
+        if status == ReviewStatus::Approved {
+            anyhow::bail!("אישור בקשת סקירה נעשה בהחלטות הסוקרים בלבד");
         }
-        Ok(())
+        if request.status.is_final() {
+            anyhow::bail!("בקשת הסקירה {} כבר אושרה סופית", request_id);
+        }
+        if !request.status.can_transition_to(status) {
+            anyhow::bail!("מעבר לא חוקי בבקשת הסקירה {}: {:?} -> {:?}", request_id, request.status, status);
+        }
+        request.status = status;
+        self.save_review_request(request)
     }
 
-    pub fn update_review_status(&mut self, request_id: &str, status: ReviewStatus) -> Result<()> {
-        if let Some(request) = self.review_requests.get_mut(request_id) {
-            request.status = status;
+    /// החלטת סוקר: אישור, דחייה או בקשת תיקונים. החלטה על בקשה ממתינה פותחת את הסקירה.
+    /// כשמספר האישורים מגיע למניין של תחום המונח, המונח מסומן כמאומת במילון, השינוי נרשם
+    /// ונעילת העריכה משתחררת. מחזיר את מצב הבקשה אחרי ההחלטה.
+    pub fn record_review_decision(
+        &mut self,
+        dictionary: &TechnicalDictionary,
+        request_id: &str,
+        reviewer: String,
+        decision: ReviewStatus,
+        comment: String,
+    ) -> Result<ReviewStatus> {
+        self.authorize(&reviewer, Action::ReviewTerm)?;
+        let mut request = self.review_request_for_update(request_id)?;
+
+        if !decision.is_decision() {
+            anyhow::bail!("{:?} אינו החלטת סקירה", decision);
+        }
+        if !request.reviewers.is_empty() && !request.reviewers.contains(&reviewer) {
+            anyhow::bail!("{} אינו סוקר בבקשה {}", reviewer, request_id);
+        }
+        if request.requested_by == reviewer {
+            anyhow::bail!("{} אינו רשאי לסקור בקשה שהגיש", reviewer);
+        }
+        if request.status == ReviewStatus::Pending {
+            request.status = ReviewStatus::InReview;
+        }
+        if request.status != ReviewStatus::InReview {
+            anyhow::bail!("בקשת הסקירה {} אינה פתוחה להחלטות (מצב {:?})", request_id, request.status);
+        }
+
+        request.comments.push(ReviewComment {
+            author: reviewer.clone(),
+            timestamp: Utc::now(),
+            content: comment,
+            field: None,
+        });
+        // בבדיקת מונח קיים, החלטת הסוקר היא שימוש במונח ונרשמת פעם אחת לכל סוקר.
+        // בהצעה לשינוי, דחייה שומרת על המונח הקיים ולכן אינה נרשמת כלל
+        let mut used_term = None;
+        if request.proposed_term.is_none() && !request.usage_recorded_by.contains(&reviewer) {
+            if let Some(mut term) = dictionary.get_term(&request.term_id) {
+                let outcome = if decision == ReviewStatus::Approved { UsageOutcome::Accepted } else { UsageOutcome::Rejected };
+                term.metadata.record_usage(outcome, Utc::now());
+                request.usage_recorded_by.push(reviewer.clone());
+                used_term = Some(term);
+            }
+        }
+        match decision {
+            ReviewStatus::Approved => {
+                if !request.approvals.contains(&reviewer) {
+                    request.approvals.push(reviewer.clone());
+                }
+                let domain = request
+                    .proposed_term
+                    .as_ref()
+                    .map(|term| term.domain.clone())
+                    .or_else(|| dictionary.get_term(&request.term_id).map(|term| term.domain));
+                if request.approvals.len() >= self.review_quorum.required(domain.as_deref()) {
+                    request.status = ReviewStatus::Approved;
+                    self.finalize_approval(dictionary, request, used_term, &reviewer)?;
+                    return Ok(ReviewStatus::Approved);
+                }
+            }
+            // דחייה או בקשת תיקונים מאפסות את האישורים: הסקירה החוזרת מתחילה מאפס
+            _ => {
+                request.approvals.clear();
+                request.status = decision;
+            }
+        }
+
+        let status = request.status;
+        match used_term {
+            Some(term) => {
+                self.commit_terms(dictionary, vec![term], &[], Vec::new(), |conn| knowledge_store::put_review_request(conn, &request))?;
+                self.review_requests.insert(request.request_id.clone(), request);
+            }
+            None => self.save_review_request(request)?,
         }
+        Ok(status)
+    }
+
+    // אישור סופי: המונח נכתב למילון כמאומת, השינוי נרשם והנעילה משתחררת - בטרנזקציה אחת.
+    // `used_term` הוא המונח הקיים אחרי רישום ההחלטה האחרונה בסטטיסטיקת השימוש
+    fn finalize_approval(
+        &mut self,
+        dictionary: &TechnicalDictionary,
+        request: ReviewRequest,
+        used_term: Option<TechnicalTerm>,
+        approved_by: &str,
+    ) -> Result<()> {
+        let existing = used_term.or_else(|| dictionary.get_term(&request.term_id));
+        let mut term = match (&request.proposed_term, &existing) {
+            // מונח קיים מקבל רק את שדות התוכן של ההצעה; נתוני השימוש והאמון שלו נשמרים
+            (Some(proposed), Some(existing)) => dictionary_merge::copy_content(existing, proposed)?,
+            (Some(proposed), None) => proposed.clone(),
+            (None, Some(existing)) => existing.clone(),
+            (None, None) => anyhow::bail!("המונח {} לא נמצא במילון", request.term_id),
+        };
+        term.metadata.verified = true;
+        term.metadata.last_updated = Utc::now();
+
+        let change = TermChange {
+            timestamp: term.metadata.last_updated,
+            changed_by: approved_by.to_string(),
+            change_type: if existing.is_some() { ChangeType::Modification } else { ChangeType::Addition },
+            old_value: existing.map(|existing| existing.metadata.verified.to_string()),
+            new_value: Some(true.to_string()),
+            field: "metadata.verified".to_string(),
+        };
+        let term_id = request.term_id.clone();
+        self.commit_terms(dictionary, vec![term], &[], vec![(term_id.clone(), change)], |conn| {
+            knowledge_store::put_review_request(conn, &request)?;
+            knowledge_store::delete_edit_lock(conn, &term_id)
+        })?;
+        self.edit_locks.remove(&term_id);
+        self.review_requests.insert(request.request_id.clone(), request);
+        Ok(())
+    }
+
+    fn review_request_for_update(&self, request_id: &str) -> Result<ReviewRequest> {
+        self.review_requests
+            .get(request_id)
+            .cloned()
+            .with_context(|| format!("בקשת הסקירה לא נמצאה: {}", request_id))
+    }
+
+    fn save_review_request(&mut self, request: ReviewRequest) -> Result<()> {
+        self.persist(|conn| knowledge_store::put_review_request(conn, &request))?;
+        self.review_requests.insert(request.request_id.clone(), request);
         Ok(())
     }
 
+    // השינוי נעשה על עותק, כך שכתיבה שנכשלה אינה משאירה את הבקשה בזיכרון במצב חלקי
+    fn update_review_request(&mut self, request_id: &str, f: impl FnOnce(&mut ReviewRequest)) -> Result<()> {
+        let Some(mut request) = self.review_requests.get(request_id).cloned() else {
+            return Ok(());
+        };
+        f(&mut request);
+        self.save_review_request(request)
+    }
+
     pub fn resolve_conflict(
         &mut self,
         term_id: String,
@@ -411,13 +1018,16 @@ impl KnowledgeManager {
         resolution_type: ResolutionType,
         comments: String,
     ) -> Result<()> {
+        self.authorize(&resolved_by, Action::ResolveConflict)?;
         let resolution = ConflictResolution {
             term_id,
             resolved_by,
             timestamp: Utc::now(),
             resolution_type,
             comments,
+            field: None,
         };
+        self.persist(|conn| knowledge_store::append_resolution(conn, &resolution))?;
         self.conflict_resolutions.push(resolution);
         Ok(())
     }
@@ -452,7 +1062,8 @@ impl KnowledgeManager {
             .values()
             .filter(|r| {
                 r.reviewers.contains(&reviewer_id.to_string()) && 
-                matches!(r.status, ReviewStatus::Pending | ReviewStatus::InReview)
+                matches!(r.status, ReviewStatus::Pending | ReviewStatus::InReview) &&
+                !r.approvals.contains(&reviewer_id.to_string())
             })
             .collect()
     }
@@ -475,11 +1086,49 @@ impl KnowledgeManager {
             .collect()
     }
 
-    pub fn update_last_sync(&mut self) {
-        self.last_sync = Utc::now();
+    pub fn update_last_sync(&mut self) -> Result<()> {
+        let now = Utc::now();
+        self.persist(|conn| knowledge_store::set_last_sync(conn, now))?;
+        self.last_sync = now;
+        Ok(())
     }
 
     pub fn get_last_sync(&self) -> DateTime<Utc> {
         self.last_sync
     }
-} 
\ No newline at end of file
+}
+
+// שינוי בשדה אחד כרשומת היסטוריה; מחרוזות נשמרות כפי שהן וערכים אחרים כ-JSON
+fn field_change(timestamp: DateTime<Utc>, changed_by: &str, field: &str, old: Option<&Value>, new: Option<&Value>) -> TermChange {
+    let text = |value: &Value| match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+    TermChange {
+        timestamp,
+        changed_by: changed_by.to_string(),
+        change_type: match (old, new) {
+            (None, _) => ChangeType::Addition,
+            (_, None) => ChangeType::Deletion,
+            _ => ChangeType::Modification,
+        },
+        old_value: old.map(text),
+        new_value: new.map(text),
+        field: field.to_string(),
+    }
+}
+
+fn new_review_request(term_id: String, requested_by: String, reviewers: Vec<String>) -> ReviewRequest {
+    ReviewRequest {
+        request_id: uuid::Uuid::new_v4().to_string(),
+        term_id,
+        requested_by,
+        requested_at: Utc::now(),
+        reviewers,
+        status: ReviewStatus::Pending,
+        comments: Vec::new(),
+        proposed_term: None,
+        approvals: Vec::new(),
+        usage_recorded_by: Vec::new(),
+    }
+}
@@ -0,0 +1,395 @@
+//! אחסון מצב מנהל הידע בקובץ SQLite מקומי: גרסאות, היסטוריית שינויים, משתפי פעולה,
+//! נעילות עריכה, בקשות סקירה, סתירות מיזוג פתוחות ופתרונות סתירות.
+//! כל רשומה נשמרת כ-JSON לצד עמודות המפתח, כך שהוספת שדה למבנה אינה דורשת הגירה.
+
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use crate::dictionary_merge::FieldConflict;
+use crate::knowledge_sharing::{
+    CollaboratorActivity, CollaboratorInfo, ConflictResolution, DictionaryVersion, EditLock,
+    ReviewRequest, TermChange, TermChangeHistory,
+};
+
+/// מספר פעולות אחרונות שנשמרות ביומן הפעילות
+pub const ACTIVITY_LOG_CAPACITY: usize = 1000;
+
+// הגירה i מעבירה את הסכמה מגרסה i לגרסה i+1; גרסת הסכמה נשמרת ב-`PRAGMA user_version`
+const MIGRATIONS: &[&str] = &[
+    // גרסה 1: טבלאות המצב
+    "CREATE TABLE versions (
+        version_id TEXT PRIMARY KEY,
+        created_at TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE TABLE term_changes (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        term_id TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX term_changes_term ON term_changes (term_id);
+    CREATE TABLE collaborators (
+        user_id TEXT PRIMARY KEY,
+        data TEXT NOT NULL
+    );
+    CREATE TABLE edit_locks (
+        term_id TEXT PRIMARY KEY,
+        data TEXT NOT NULL
+    );
+    CREATE TABLE review_requests (
+        request_id TEXT PRIMARY KEY,
+        status TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE TABLE conflict_resolutions (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        term_id TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE TABLE activity_log (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        data TEXT NOT NULL
+    );
+    CREATE TABLE settings (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );",
+    // גרסה 2: סתירות מיזוג ברמת השדה שטרם נפתרו
+    "CREATE TABLE field_conflicts (
+        conflict_id TEXT PRIMARY KEY,
+        term_id TEXT NOT NULL,
+        data TEXT NOT NULL
+    );",
+];
+
+/// גרסת הסכמה הנוכחית של מאגר הידע
+pub const STORE_SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// כל המצב השמור, לטעינה בהפעלה
+#[derive(Debug, Default)]
+pub struct KnowledgeSnapshot {
+    pub versions: HashMap<String, DictionaryVersion>,
+    pub change_history: HashMap<String, TermChangeHistory>,
+    pub collaborators: HashMap<String, CollaboratorInfo>,
+    pub edit_locks: HashMap<String, EditLock>,
+    pub review_requests: HashMap<String, ReviewRequest>,
+    pub conflict_resolutions: Vec<ConflictResolution>,
+    pub field_conflicts: HashMap<String, FieldConflict>,
+    pub activity_log: VecDeque<CollaboratorActivity>,
+    pub last_sync: Option<DateTime<Utc>>,
+}
+
+pub struct KnowledgeStore {
+    conn: Connection,
+}
+
+impl KnowledgeStore {
+    /// פתיחת המאגר או יצירתו, והרצת ההגירות החסרות
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("פתיחת מאגר הידע נכשלה: {}", path.display()))?;
+        Self::init(conn)
+    }
+
+    /// מאגר בזיכרון בלבד, לבדיקות
+    pub fn in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        let mut store = Self { conn };
+        store.migrate()?;
+        Ok(store)
+    }
+
+    pub fn schema_version(&self) -> Result<u32> {
+        Ok(self.conn.pragma_query_value(None, "user_version", |row| row.get(0))?)
+    }
+
+    fn migrate(&mut self) -> Result<()> {
+        let version = self.schema_version()?;
+        if version > STORE_SCHEMA_VERSION {
+            anyhow::bail!(
+                "גרסת הסכמה של מאגר הידע ({}) חדשה מהגרסה הנתמכת ({})",
+                version,
+                STORE_SCHEMA_VERSION
+            );
+        }
+        for (index, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+            let tx = self.conn.transaction()?;
+            tx.execute_batch(migration)
+                .with_context(|| format!("הגירת מאגר הידע לגרסה {} נכשלה", index + 1))?;
+            tx.pragma_update(None, "user_version", (index + 1) as u32)?;
+            tx.commit()?;
+        }
+        Ok(())
+    }
+
+    /// הרצת כמה כתיבות בטרנזקציה אחת: כולן נשמרות, או אף אחת
+    pub fn write<T>(&mut self, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+        let tx = self.conn.transaction()?;
+        let value = f(&tx)?;
+        tx.commit().context("שמירת השינויים במאגר הידע נכשלה")?;
+        Ok(value)
+    }
+
+    pub fn load(&self) -> Result<KnowledgeSnapshot> {
+        let mut snapshot = KnowledgeSnapshot::default();
+
+        for version in self.load_all::<DictionaryVersion>("SELECT data FROM versions")? {
+            snapshot.versions.insert(version.version_id.clone(), version);
+        }
+        let changes = self.load_pairs::<TermChange>("SELECT term_id, data FROM term_changes ORDER BY id")?;
+        for (term_id, change) in changes {
+            snapshot
+                .change_history
+                .entry(term_id.clone())
+                .or_insert_with(|| TermChangeHistory { term_id, changes: Vec::new() })
+                .changes
+                .push(change);
+        }
+        for collaborator in self.load_all::<CollaboratorInfo>("SELECT data FROM collaborators")? {
+            snapshot.collaborators.insert(collaborator.user_id.clone(), collaborator);
+        }
+        for lock in self.load_all::<EditLock>("SELECT data FROM edit_locks")? {
+            snapshot.edit_locks.insert(lock.term_id.clone(), lock);
+        }
+        for request in self.load_all::<ReviewRequest>("SELECT data FROM review_requests")? {
+            snapshot.review_requests.insert(request.request_id.clone(), request);
+        }
+        snapshot.conflict_resolutions = self.load_all("SELECT data FROM conflict_resolutions ORDER BY id")?;
+        for conflict in self.load_all::<FieldConflict>("SELECT data FROM field_conflicts")? {
+            snapshot.field_conflicts.insert(conflict.conflict_id.clone(), conflict);
+        }
+        // היומן בזיכרון מסודר מהחדש לישן
+        snapshot.activity_log = self
+            .load_all(&format!("SELECT data FROM activity_log ORDER BY id DESC LIMIT {}", ACTIVITY_LOG_CAPACITY))?
+            .into();
+        snapshot.last_sync = self
+            .conn
+            .query_row("SELECT value FROM settings WHERE key = 'last_sync'", [], |row| row.get::<_, String>(0))
+            .optional()?
+            .map(|value| DateTime::parse_from_rfc3339(&value).map(|time| time.with_timezone(&Utc)))
+            .transpose()
+            .context("זמן הסנכרון האחרון במאגר הידע אינו תקין")?;
+
+        Ok(snapshot)
+    }
+
+    fn load_all<T: DeserializeOwned>(&self, sql: &str) -> Result<Vec<T>> {
+        let mut statement = self.conn.prepare(sql)?;
+        let rows = statement.query_map([], |row| row.get::<_, String>(0))?;
+        rows.map(|data| decode(&data?)).collect()
+    }
+
+    fn load_pairs<T: DeserializeOwned>(&self, sql: &str) -> Result<Vec<(String, T)>> {
+        let mut statement = self.conn.prepare(sql)?;
+        let rows = statement.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+        rows.map(|row| {
+            let (key, data) = row?;
+            Ok((key, decode(&data)?))
+        })
+        .collect()
+    }
+}
+
+// כתיבות בודדות; נקראות בתוך `KnowledgeStore::write`
+
+pub fn put_version(conn: &Connection, version: &DictionaryVersion) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO versions (version_id, created_at, data) VALUES (?1, ?2, ?3)",
+        params![version.version_id, version.created_at.to_rfc3339(), encode(version)?],
+    )?;
+    Ok(())
+}
+
+pub fn append_change(conn: &Connection, term_id: &str, change: &TermChange) -> Result<()> {
+    conn.execute(
+        "INSERT INTO term_changes (term_id, data) VALUES (?1, ?2)",
+        params![term_id, encode(change)?],
+    )?;
+    Ok(())
+}
+
+pub fn put_collaborator(conn: &Connection, collaborator: &CollaboratorInfo) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO collaborators (user_id, data) VALUES (?1, ?2)",
+        params![collaborator.user_id, encode(collaborator)?],
+    )?;
+    Ok(())
+}
+
+pub fn put_edit_lock(conn: &Connection, lock: &EditLock) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO edit_locks (term_id, data) VALUES (?1, ?2)",
+        params![lock.term_id, encode(lock)?],
+    )?;
+    Ok(())
+}
+
+pub fn delete_edit_lock(conn: &Connection, term_id: &str) -> Result<()> {
+    conn.execute("DELETE FROM edit_locks WHERE term_id = ?1", params![term_id])?;
+    Ok(())
+}
+
+pub fn put_review_request(conn: &Connection, request: &ReviewRequest) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO review_requests (request_id, status, data) VALUES (?1, ?2, ?3)",
+        params![request.request_id, format!("{:?}", request.status), encode(request)?],
+    )?;
+    Ok(())
+}
+
+pub fn append_resolution(conn: &Connection, resolution: &ConflictResolution) -> Result<()> {
+    conn.execute(
+        "INSERT INTO conflict_resolutions (term_id, data) VALUES (?1, ?2)",
+        params![resolution.term_id, encode(resolution)?],
+    )?;
+    Ok(())
+}
+
+pub fn put_field_conflict(conn: &Connection, conflict: &FieldConflict) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO field_conflicts (conflict_id, term_id, data) VALUES (?1, ?2, ?3)",
+        params![conflict.conflict_id, conflict.term_id, encode(conflict)?],
+    )?;
+    Ok(())
+}
+
+pub fn delete_field_conflict(conn: &Connection, conflict_id: &str) -> Result<()> {
+    conn.execute("DELETE FROM field_conflicts WHERE conflict_id = ?1", params![conflict_id])?;
+    Ok(())
+}
+
+/// הוספה ליומן הפעילות ומחיקת הרשומות שמעבר לקיבולת
+pub fn append_activity(conn: &Connection, activity: &CollaboratorActivity) -> Result<()> {
+    conn.execute("INSERT INTO activity_log (data) VALUES (?1)", params![encode(activity)?])?;
+    conn.execute(
+        "DELETE FROM activity_log WHERE id NOT IN (SELECT id FROM activity_log ORDER BY id DESC LIMIT ?1)",
+        params![ACTIVITY_LOG_CAPACITY as i64],
+    )?;
+    Ok(())
+}
+
+pub fn set_last_sync(conn: &Connection, time: DateTime<Utc>) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES ('last_sync', ?1)",
+        params![time.to_rfc3339()],
+    )?;
+    Ok(())
+}
+
+fn encode<T: Serialize>(value: &T) -> Result<String> {
+    serde_json::to_string(value).context("קידוד רשומה למאגר הידע נכשל")
+}
+
+fn decode<T: DeserializeOwned>(data: &str) -> Result<T> {
+    serde_json::from_str(data).context("רשומה במאגר הידע אינה תקינה")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge_sharing::ChangeType;
+
+    fn change(field: &str) -> TermChange {
+        TermChange {
+            timestamp: Utc::now(),
+            changed_by: "editor".to_string(),
+            change_type: ChangeType::Modification,
+            old_value: None,
+            new_value: Some("задвижка".to_string()),
+            field: field.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_migrations_applied_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("knowledge_store.db");
+
+        let store = KnowledgeStore::open(&path).unwrap();
+        assert_eq!(store.schema_version().unwrap(), STORE_SCHEMA_VERSION);
+        drop(store);
+
+        // פתיחה חוזרת אינה מריצה את ההגירות שוב
+        let store = KnowledgeStore::open(&path).unwrap();
+        assert!(store.load().unwrap().versions.is_empty());
+    }
+
+    #[test]
+    fn test_failed_write_rolls_back() {
+        let mut store = KnowledgeStore::in_memory().unwrap();
+        store.write(|conn| append_change(conn, "מגוף", &change("target"))).unwrap();
+
+        let result: Result<()> = store.write(|conn| {
+            append_change(conn, "מגוף", &change("domain"))?;
+            anyhow::bail!("נכשל באמצע")
+        });
+        assert!(result.is_err());
+
+        let snapshot = store.load().unwrap();
+        assert_eq!(snapshot.change_history["מגוף"].changes.len(), 1);
+    }
+
+    #[test]
+    fn test_manager_state_survives_restart() {
+        use crate::knowledge_sharing::{KnowledgeManager, ReviewStatus};
+        use crate::technical_dictionary::TechnicalDictionary;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("knowledge_manager.db");
+
+        let request_id = {
+            let mut manager = KnowledgeManager::open(&path).unwrap();
+            manager
+                .track_change("מגוף".to_string(), "editor".to_string(), ChangeType::Addition, "target".to_string(), None, Some("задвижка".to_string()))
+                .unwrap();
+            assert!(manager.acquire_edit_lock("מגוף".to_string(), "editor".to_string()).unwrap());
+            let dictionary = TechnicalDictionary::in_memory();
+            let request_id = manager
+                .create_review_request(&dictionary, "מגוף".to_string(), "editor".to_string(), vec!["reviewer".to_string()])
+                .unwrap();
+            manager
+                .record_review_decision(&dictionary, &request_id, "reviewer".to_string(), ReviewStatus::NeedsChanges, "חסר תקן".to_string())
+                .unwrap();
+            request_id
+        };
+
+        let manager = KnowledgeManager::open(&path).unwrap();
+        assert_eq!(manager.get_term_history("מגוף").unwrap().changes.len(), 1);
+        let request = manager.get_review_request(&request_id).unwrap();
+        assert_eq!(request.status, ReviewStatus::NeedsChanges);
+        assert_eq!(request.comments.len(), 1);
+    }
+
+    #[test]
+    fn test_api_key_survives_restart() {
+        use crate::knowledge_sharing::{CollaboratorRole, KnowledgeManager};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("knowledge_keys.db");
+
+        let key = {
+            let mut manager = KnowledgeManager::open(&path).unwrap();
+            manager.add_collaborator("admin", "admin".to_string(), "מנהל".to_string(), CollaboratorRole::Admin).unwrap();
+            manager.add_collaborator("admin", "dana".to_string(), "דנה".to_string(), CollaboratorRole::Viewer).unwrap();
+            assert!(manager.issue_api_key("dana", "dana").is_err());
+            manager.issue_api_key("admin", "dana").unwrap()
+        };
+
+        // רק הגיבוב נשמר במאגר
+        let stored = std::fs::read(&path).unwrap();
+        assert!(!stored.windows(key.len()).any(|window| window == key.as_bytes()));
+
+        let manager = KnowledgeManager::open(&path).unwrap();
+        assert_eq!(manager.authenticate(&key), Some("dana"));
+        assert_eq!(manager.authenticate("rh_wrong"), None);
+        assert_eq!(manager.effective_role("dana"), Some(CollaboratorRole::Viewer));
+        assert_eq!(manager.effective_role("stranger"), None);
+    }
+}
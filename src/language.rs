@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// שפות הנתמכות בצינור התרגום
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Language {
+    /// עברית
+    #[serde(rename = "he")]
+    Hebrew,
+    /// רוסית
+    #[serde(rename = "ru")]
+    Russian,
+    /// אנגלית - משמשת גם כשפת ציר
+    #[serde(rename = "en")]
+    English,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::Hebrew, Language::Russian, Language::English];
+
+    /// שפת הציר לתרגום עקיף
+    pub const PIVOT: Language = Language::English;
+
+    /// קוד ISO 639-1 של השפה
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::Hebrew => "he",
+            Language::Russian => "ru",
+            Language::English => "en",
+        }
+    }
+
+    /// מפענח קוד שפה (639-1 או 639-3)
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_lowercase().as_str() {
+            "he" | "iw" | "heb" => Some(Language::Hebrew),
+            "ru" | "rus" => Some(Language::Russian),
+            "en" | "eng" => Some(Language::English),
+            _ => None,
+        }
+    }
+
+    /// האם השפה נכתבת מימין לשמאל
+    pub fn is_rtl(&self) -> bool {
+        matches!(self, Language::Hebrew)
+    }
+
+    /// שפת היעד ברירת המחדל כאשר המשתמש לא בחר יעד
+    pub fn default_target(&self) -> Language {
+        match self {
+            Language::Hebrew => Language::Russian,
+            Language::Russian => Language::Hebrew,
+            Language::English => Language::Hebrew,
+        }
+    }
+
+    /// בודק אם תו שייך לכתב של השפה
+    pub fn owns_char(&self, c: char) -> bool {
+        match self {
+            Language::Hebrew => ('\u{05D0}'..='\u{05EA}').contains(&c),
+            Language::Russian => ('\u{0400}'..='\u{04FF}').contains(&c),
+            Language::English => c.is_ascii_alphabetic(),
+        }
+    }
+
+    /// זיהוי שפה לפי הכתב הדומיננטי בטקסט
+    pub fn detect(text: &str) -> Option<Language> {
+        let mut counts = [0usize; 3];
+        for c in text.chars() {
+            for (i, lang) in Language::ALL.iter().enumerate() {
+                if lang.owns_char(c) {
+                    counts[i] += 1;
+                }
+            }
+        }
+
+        Language::ALL
+            .iter()
+            .zip(counts.iter())
+            .filter(|(_, count)| **count > 0)
+            .max_by_key(|(_, count)| **count)
+            .map(|(lang, _)| *lang)
+    }
+}
+
+/// רשומה שיש לה צורות בכמה שפות: מונח במילון, רשומת מילון פרויקט או מושג במסד המונחים
+pub trait Multilingual {
+    /// כל הצורות של הרשומה; בכל שפה הצורה המועדפת ראשונה
+    fn forms(&self) -> Vec<(Language, &str)>;
+
+    /// הצורה המועדפת בשפה המבוקשת, אם קיימת
+    fn text_in(&self, lang: Language) -> Option<&str> {
+        self.forms()
+            .into_iter()
+            .find(|(candidate, _)| *candidate == lang)
+            .map(|(_, text)| text)
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_code() {
+        assert_eq!(Language::from_code("he"), Some(Language::Hebrew));
+        assert_eq!(Language::from_code("RUS"), Some(Language::Russian));
+        assert_eq!(Language::from_code("en"), Some(Language::English));
+        assert_eq!(Language::from_code("fr"), None);
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Language::detect("ראש ספרינקלר"), Some(Language::Hebrew));
+        assert_eq!(Language::detect("рабочее давление"), Some(Language::Russian));
+        assert_eq!(Language::detect("Sprinkler Head K-5.6"), Some(Language::English));
+        assert_eq!(Language::detect("ראש ספרינקלר K"), Some(Language::Hebrew));
+        assert_eq!(Language::detect("123 / 456"), None);
+    }
+}
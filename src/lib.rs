@@ -1,6 +1,28 @@
+pub mod language;
 pub mod morphology;
 pub mod neural;
 pub mod translation_engine;
+pub mod translation;
+pub mod streaming;
+pub mod batch;
+pub mod glossary;
+pub mod protection;
+pub mod sense;
+pub mod trace;
+pub mod standards;
+pub mod clause_references;
+pub mod technical_dictionary;
+pub mod dictionary_search;
+pub mod term_usage;
+pub mod tbx;
+pub mod knowledge_sharing;
+pub mod knowledge_store;
+pub mod dictionary_merge;
+pub mod permissions;
+pub mod review_workflow;
+pub mod version_diff;
+pub mod glossary_import;
+pub mod term_extraction;
 pub mod quality_control;
 pub mod gui;
 pub mod learning_manager;
@@ -19,6 +41,7 @@ pub use neural::{
     attention::{MultiHeadAttention, AttentionConfig},
 };
 
+pub use language::Language;
 pub use translation_engine::TranslationEngine;
 pub use quality_control::{QualityControl, IssueSeverity};
 pub use learning_manager::{LearningManager, LearningEvent, LearningEventType, UserFeedback};
@@ -2,9 +2,20 @@ use std::error::Error;
 use std::sync::Arc;
 use std::sync::Mutex;
 
+mod language;
+mod morphology;
 mod translation;
+mod streaming;
+mod batch;
+mod glossary;
+mod protection;
+mod quality_control;
+mod translation_models;
+mod sense;
+mod trace;
 mod technical_terms;
 mod standards;
+mod clause_references;
 mod document_processor;
 mod file_processor;
 mod fonts;
@@ -13,14 +24,35 @@ mod templates;
 mod template_translator;
 mod gui;
 mod technical_dictionary;
+mod dictionary_search;
+mod term_usage;
+mod tbx;
 mod knowledge_sharing;
+mod knowledge_store;
+mod dictionary_merge;
+mod permissions;
+mod review_workflow;
+mod version_diff;
+mod glossary_import;
+mod term_extraction;
 
 use translation::Translator;
-use templates::TemplateManager;
-use template_translator::TemplateTranslator;
-use gui::RustoHebruApp;
+use streaming::StreamingTranslator;
+use quality_control::QualityControl;
+use gui::ModernGui;
 use technical_dictionary::TechnicalDictionary;
 use knowledge_sharing::KnowledgeManager;
+use language::Language;
+use technical_terms::TermsDatabase;
+use term_extraction::MonolingualExtractor;
+use term_usage::UsageReportOptions;
+use glossary_import::{ColumnMapping, GlossaryImporter, SpreadsheetTable};
+use permissions::PermissionPolicy;
+
+// מאגר הגרסאות, היסטוריית השינויים והסקירות
+const KNOWLEDGE_DB: &str = "knowledge.db";
+// כללי ההרשאות לפי תפקיד; בהיעדר הקובץ - ברירת המחדל
+const PERMISSIONS_FILE: &str = "permissions.json";
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
@@ -33,19 +65,51 @@ async fn main() -> Result<(), Box<dyn Error>> {
         TechnicalDictionary::new("technical_dictionary.json".to_string())?
     ));
     
-    // יצירת מנהל הידע
-    let knowledge_manager = Arc::new(Mutex::new(KnowledgeManager::new()));
-    
-    // יצירת מנהל תבניות
-    let template_manager = Arc::new(TemplateManager::new("templates".to_string())?);
+    // ניתוח מונחים לפני פרויקט: extract-terms <מסמך> <שפה> [גיליון פלט]
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("extract-terms") {
+        return run_term_extraction(&args[2..], &terms_db, &technical_dictionary);
+    }
+    // דוח מונחים לבדיקת המינוחן: term-usage-report [דוח.csv]
+    if args.get(1).map(String::as_str) == Some("term-usage-report") {
+        return run_usage_report(&args[2..], &technical_dictionary);
+    }
+    // ייבוא גיליון מונחים: import-glossary <גיליון> [source=<עמודה> target=<עמודה> ...] [--commit]
+    if args.get(1).map(String::as_str) == Some("import-glossary") {
+        return run_glossary_import(&args[2..], &technical_dictionary);
+    }
+    // דוח הבדלים בין גרסאות: version-diff <גרסה> <גרסה> [דוח.html|דוח.md]
+    if args.get(1).map(String::as_str) == Some("version-diff") {
+        return run_version_diff(&args[2..]);
+    }
+    // שחזור גרסה קודמת כגרסה חדשה: rollback-version <גרסה> <מזהה לגרסה החדשה>
+    if args.get(1).map(String::as_str) == Some("rollback-version") {
+        return run_rollback(&args[2..], &technical_dictionary);
+    }
+    // מפתח גישה ל-API עבור משתף פעולה: issue-api-key <משתמש>
+    if args.get(1).map(String::as_str) == Some("issue-api-key") {
+        return run_issue_api_key(&args[2..]);
+    }
+
+    // התפקיד של המשתמש המקומי קובע אילו פעולות זמינות בממשק
+    let knowledge_manager = open_knowledge_manager()?;
+    let user = current_user();
+    let role = knowledge_manager
+        .effective_role(&user)
+        .ok_or_else(|| format!("{} אינו רשום כמשתף פעולה", user))?;
     
-    // יצירת מתרגם תבניות
-    let translator = Translator::new(
+    let translator = Arc::new(Translator::new(
         terms_db.clone(),
         standards_db.clone(),
         technical_dictionary.clone(),
-    );
-    let template_translator = Arc::new(TemplateTranslator::new(translator));
+    ));
+    let gui = ModernGui::new(
+        Arc::new(StreamingTranslator::new(translator)),
+        Arc::new(QualityControl::new()),
+        role,
+        knowledge_manager.policy().clone(),
+    )
+    .with_dictionary(technical_dictionary.clone());
     
     // הגדרות חלון
     let native_options = eframe::NativeOptions {
@@ -59,13 +123,161 @@ async fn main() -> Result<(), Box<dyn Error>> {
     eframe::run_native(
         "RustoHebru",
         native_options,
-        Box::new(|_cc| Box::new(RustoHebruApp::new(
-            template_manager.clone(),
-            template_translator.clone(),
-            technical_dictionary.clone(),
-            knowledge_manager.clone(),
-        ))),
+        Box::new(move |_cc| Box::new(gui)),
     ).map_err(|e| anyhow::anyhow!("שגיאה בהפעלת הממשק הגרפי: {}", e))?;
     
     Ok(())
-} 
\ No newline at end of file
+} 
+
+// חילוץ מונחים ממסמך וכתיבת המונחים החסרים לגיליון עבודה
+fn run_term_extraction(
+    args: &[String],
+    terms_db: &TermsDatabase,
+    technical_dictionary: &Mutex<TechnicalDictionary>,
+) -> Result<(), Box<dyn Error>> {
+    let (Some(document), Some(lang)) = (args.first(), args.get(1)) else {
+        return Err("שימוש: extract-terms <מסמך> <he|ru|en> [גיליון.csv]".into());
+    };
+    let lang = Language::from_code(lang).ok_or_else(|| format!("שפה לא נתמכת: {}", lang))?;
+    let target_lang = lang.default_target();
+    let worksheet = args.get(2).map(String::as_str).unwrap_or("term_worksheet.csv");
+
+    let text = std::fs::read_to_string(document)?;
+    let dictionary = technical_dictionary
+        .lock()
+        .map_err(|_| "המילון הטכני אינו זמין")?;
+    let analysis = MonolingualExtractor::new(lang, target_lang).analyze(&text, Some(&dictionary), Some(terms_db));
+
+    analysis.save_worksheet_csv(std::path::Path::new(worksheet))?;
+    println!("{}", analysis.summary());
+    for term in analysis.candidates.iter().take(20) {
+        let mark = if term.known.is_some() { "✓" } else { "+" };
+        println!("{} {} ({}, {:.2})", mark, term.text, term.frequency, term.termhood);
+    }
+    println!("המונחים החסרים נכתבו ל-{}", worksheet);
+    Ok(())
+}
+
+// דוח מונחים ללא שימוש, מונחים שנדחים בסקירה ומונחים לא מאומתים בשימוש רב
+fn run_usage_report(args: &[String], technical_dictionary: &Mutex<TechnicalDictionary>) -> Result<(), Box<dyn Error>> {
+    let output = args.first().map(String::as_str).unwrap_or("term_usage_report.csv");
+    let dictionary = technical_dictionary
+        .lock()
+        .map_err(|_| "המילון הטכני אינו זמין")?;
+    let report = dictionary.usage_report(&UsageReportOptions::default());
+
+    report.save_csv(std::path::Path::new(output))?;
+    println!("{}", report.summary());
+    for entry in report.frequently_rejected.iter().take(20) {
+        println!("✗ {} → {} ({}/{} נדחו)", entry.source, entry.target, entry.rejected, entry.usage_count);
+    }
+    println!("הדוח נכתב ל-{}", output);
+    Ok(())
+}
+
+fn run_version_diff(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (Some(from), Some(to)) = (args.first(), args.get(1)) else {
+        return Err("שימוש: version-diff <גרסה> <גרסה> [דוח.html|דוח.md]".into());
+    };
+    let output = args.get(2).map(String::as_str).unwrap_or("version_diff.md");
+
+    let diff = open_knowledge_manager()?.diff_versions(from, to)?;
+    diff.save(std::path::Path::new(output))?;
+    println!("{}", diff.summary());
+    println!("הדוח נכתב ל-{}", output);
+    Ok(())
+}
+
+fn run_rollback(args: &[String], technical_dictionary: &Mutex<TechnicalDictionary>) -> Result<(), Box<dyn Error>> {
+    let (Some(version), Some(new_version)) = (args.first(), args.get(1)) else {
+        return Err("שימוש: rollback-version <גרסה> <מזהה לגרסה החדשה>".into());
+    };
+
+    let dictionary = technical_dictionary
+        .lock()
+        .map_err(|_| "המילון הטכני אינו זמין")?;
+    let diff = open_knowledge_manager()?.rollback_to_version(&dictionary, version, new_version, &current_user())?;
+    println!("המילון שוחזר לגרסה {} ונשמר כגרסה {}", version, new_version);
+    println!("{}", diff.summary());
+    Ok(())
+}
+
+// המפתח מודפס פעם אחת בלבד; במאגר נשמר רק הגיבוב שלו
+fn run_issue_api_key(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let Some(user) = args.first() else {
+        return Err("שימוש: issue-api-key <משתמש>".into());
+    };
+    let api_key = open_knowledge_manager()?.issue_api_key(&current_user(), user)?;
+    println!("{}", api_key);
+    Ok(())
+}
+
+fn open_knowledge_manager() -> anyhow::Result<KnowledgeManager> {
+    let policy = PermissionPolicy::load_or_default(std::path::Path::new(PERMISSIONS_FILE))?;
+    Ok(KnowledgeManager::open(std::path::Path::new(KNOWLEDGE_DB))?.with_policy(policy))
+}
+
+// המשתמש שבשמו פועלות פקודות שורת הפקודה
+fn current_user() -> String {
+    std::env::var("RUSTOHEBRU_USER")
+        .or_else(|_| std::env::var("USER"))
+        .unwrap_or_else(|_| "local".to_string())
+}
+
+// ייבוא יבש כברירת מחדל: מדפיס את ההבדלים וכותב אותם לקובץ; `--commit` מחיל אותם על המילון
+fn run_glossary_import(args: &[String], technical_dictionary: &Mutex<TechnicalDictionary>) -> Result<(), Box<dyn Error>> {
+    let Some(sheet) = args.first() else {
+        return Err("שימוש: import-glossary <גיליון> [source=<עמודה>] [target=<עמודה>] [domain=<עמודה>] \
+                    [notes=<עמודה>] [standards=<עמודה>] [english=<עמודה>] [--commit]".into());
+    };
+    let path = std::path::Path::new(sheet);
+    let table = SpreadsheetTable::load(path)?;
+
+    // מיפוי מפורש מחליף את ההצעה האוטומטית, שדה אחר שדה
+    let mut mapping = ColumnMapping::suggest(&table.headers)
+        .unwrap_or_else(|| ColumnMapping::new("", ""));
+    let mut explicit_notes = false;
+    for arg in &args[1..] {
+        let Some((field, header)) = arg.split_once('=') else {
+            continue;
+        };
+        match field {
+            "source" => mapping.source = header.to_string(),
+            "target" => mapping.target = header.to_string(),
+            "domain" => mapping.domain = Some(header.to_string()),
+            // עמודות הערות מפורשות מחליפות את העמודה שהוצעה; כמה `notes=` מצטרפים זה לזה
+            "notes" => {
+                if !std::mem::replace(&mut explicit_notes, true) {
+                    mapping.notes.clear();
+                }
+                mapping.notes.push(header.to_string());
+            }
+            "standards" => mapping.standards = Some(header.to_string()),
+            "english" => mapping.english = Some(header.to_string()),
+            other => return Err(format!("שדה מיפוי לא מוכר: {}", other).into()),
+        }
+    }
+
+    let dictionary = technical_dictionary
+        .lock()
+        .map_err(|_| "המילון הטכני אינו זמין")?;
+    let source_name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let plan = GlossaryImporter::new(mapping, Language::Hebrew, Language::Russian)
+        .with_source_name(&source_name)
+        .plan(&table, &dictionary)?;
+
+    let diff_path = path.with_extension("diff.csv");
+    plan.save_diff_csv(&diff_path)?;
+    println!("{}", plan.summary());
+    for conflict in &plan.conflicts {
+        println!("! שורה {}: {} - {} / {} ({})", conflict.row, conflict.source, conflict.existing_target, conflict.incoming_target, conflict.reason);
+    }
+    println!("ההבדלים נכתבו ל-{}", diff_path.display());
+
+    if args.iter().any(|arg| arg == "--commit") {
+        let mut knowledge_manager = open_knowledge_manager()?;
+        let applied = plan.commit(&dictionary, &mut knowledge_manager, &current_user())?;
+        println!("{} מונחים עודכנו במילון", applied);
+    }
+    Ok(())
+}
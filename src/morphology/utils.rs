@@ -14,7 +14,8 @@ lazy_static! {
         set
     };
 
-    static ref HEBREW_PREFIXES: HashSet<&'static str> = {
+    /// אותיות השימוש שעשויות להופיע בתחילת מילה בעברית
+    pub static ref HEBREW_PREFIXES: HashSet<&'static str> = {
         let mut set = HashSet::new();
         set.insert("ב");
         set.insert("ה");
@@ -64,6 +65,22 @@ pub fn is_russian_word(word: &str) -> bool {
     word.chars().all(|c| is_russian_letter(c) || c.is_whitespace())
 }
 
+/// מספר אותיות השימוש המרבי לפני מילה ("ומהבית")
+pub const MAX_HEBREW_PREFIXES: usize = 3;
+
+/// בודק אם תו הוא אות שימוש
+pub fn is_hebrew_prefix(c: char) -> bool {
+    let mut buffer = [0; 4];
+    HEBREW_PREFIXES.contains(&*c.encode_utf8(&mut buffer))
+}
+
+/// בודק אם מילה היא צורת הבסיס עם אותיות שימוש לפניה ("בצנרת", "הלחץ")
+pub fn is_prefixed_form(word: &str, base: &str) -> bool {
+    word.strip_suffix(base).map_or(false, |prefix| {
+        prefix.chars().count() <= MAX_HEBREW_PREFIXES && prefix.chars().all(is_hebrew_prefix)
+    })
+}
+
 /// מסיר תחיליות מוכרות ממילה בעברית
 pub fn remove_hebrew_prefixes(word: &str) -> String {
     let mut result = word.to_string();
@@ -102,6 +119,79 @@ pub fn tokenize(text: &str) -> Vec<String> {
         .collect()
 }
 
+// סופיות נטייה נפוצות ברוסית, מהארוכה לקצרה
+const RUSSIAN_ENDINGS: &[&str] = &[
+    "ями", "ами", "ого", "его", "ому", "ему", "ыми", "ими", "иях", "ах", "ях", "ов", "ев",
+    "ей", "ой", "ий", "ый", "ая", "яя", "ое", "ее", "ые", "ие", "ую", "юю", "ом", "ем",
+    "ам", "ям", "а", "я", "ы", "и", "у", "ю", "е", "о", "ь",
+];
+
+/// החלפת אותיות סופיות באותיות רגילות, כדי שגזע ללא סופית יושווה נכון
+pub fn normalize_final_letters(word: &str) -> String {
+    word.chars()
+        .map(|c| match c {
+            'ך' => 'כ',
+            'ם' => 'מ',
+            'ן' => 'נ',
+            'ף' => 'פ',
+            'ץ' => 'צ',
+            c => c,
+        })
+        .collect()
+}
+
+/// הסרת ניקוד וטעמים; המקף העברי אינו ניקוד ונשמר
+pub fn strip_niqqud(text: &str) -> String {
+    text.chars()
+        .filter(|c| !('\u{0591}'..='\u{05C7}').contains(c) || *c == '\u{05BE}')
+        .collect()
+}
+
+/// מפתחות למה של מילה, לחיפוש צורות נטויות.
+/// בעברית מוחזר גם מפתח עם תחילית אחת שהוסרה, כי אי אפשר לדעת בלי הקשר אם האות היא חלק מהשורש.
+pub fn lemma_keys(word: &str) -> Vec<String> {
+    let word: String = word.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect();
+    let mut keys = Vec::new();
+
+    if word.chars().any(is_hebrew_letter) || word.chars().any(|c| "ךםןףץ".contains(c)) {
+        let mut candidates = vec![word.clone()];
+        if let Some(first) = word.chars().next() {
+            let rest = &word[first.len_utf8()..];
+            if HEBREW_PREFIXES.contains(first.to_string().as_str()) && rest.chars().count() >= 3 {
+                candidates.push(rest.to_string());
+            }
+        }
+        for candidate in candidates {
+            let stem = ["ים", "ות"]
+                .iter()
+                .find_map(|suffix| {
+                    candidate
+                        .strip_suffix(suffix)
+                        .filter(|stem| stem.chars().count() >= 2)
+                })
+                .unwrap_or(&candidate);
+            keys.push(normalize_final_letters(stem));
+        }
+    } else if word.chars().any(|c| RUSSIAN_LETTERS.contains(&c) || c == 'ё') {
+        let word = word.replace('ё', "е");
+        let stem = RUSSIAN_ENDINGS
+            .iter()
+            .find_map(|ending| word.strip_suffix(ending).filter(|stem| stem.chars().count() >= 3))
+            .unwrap_or(&word);
+        keys.push(stem.to_string());
+    } else {
+        let stem = word
+            .strip_suffix("es")
+            .filter(|stem| stem.len() >= 3 && (stem.ends_with('s') || stem.ends_with('x') || stem.ends_with("ch")))
+            .or_else(|| word.strip_suffix('s').filter(|stem| stem.len() >= 3 && !stem.ends_with('s')))
+            .unwrap_or(&word);
+        keys.push(stem.to_string());
+    }
+
+    keys.dedup();
+    keys
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,6 +217,10 @@ mod tests {
         assert_eq!(remove_hebrew_prefixes("השלום"), "שלום");
         assert_eq!(remove_hebrew_prefixes("בבית"), "בית");
         assert_eq!(remove_hebrew_prefixes("לכתוב"), "כתוב");
+
+        assert!(is_prefixed_form("ובצנרת", "צנרת"));
+        assert!(is_prefixed_form("לחץ", "לחץ"));
+        assert!(!is_prefixed_form("תלחץ", "לחץ"));
     }
 
     #[test]
@@ -142,4 +236,13 @@ mod tests {
         let tokens = tokenize(text);
         assert_eq!(tokens, vec!["שלום", "עולם", "מה", "נשמע"]);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_lemma_keys() {
+        // צורת הרבים והצורה המיודעת חולקות מפתח עם צורת היחיד
+        assert!(lemma_keys("מגופים").contains(&"מגופ".to_string()));
+        assert!(lemma_keys("המגוף").contains(&"מגופ".to_string()));
+        assert_eq!(lemma_keys("трубопроводов"), lemma_keys("трубопровод"));
+        assert_eq!(lemma_keys("Valves"), vec!["valve"]);
+    }
+}
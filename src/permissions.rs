@@ -0,0 +1,280 @@
+//! מדיניות הרשאות לפי תפקיד משתף הפעולה. כל שינוי במילון ובמנהל הידע נבדק מול המדיניות
+//! לפני ביצועו; הכללים ניתנים להגדרה בקובץ JSON.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::path::Path;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use crate::knowledge_sharing::CollaboratorRole;
+
+/// פעולה שדורשת הרשאה
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    ViewTerms,
+    EditTerm,
+    DeleteTerm,
+    ImportGlossary,
+    MergeDictionaries,
+    ResolveConflict,
+    CreateVersion,
+    RollbackVersion,
+    LockTerm,
+    RequestReview,
+    CommentReview,
+    ReviewTerm,
+    ChangeReviewStatus,
+    ManageCollaborators,
+}
+
+impl Action {
+    pub const ALL: [Action; 14] = [
+        Action::ViewTerms,
+        Action::EditTerm,
+        Action::DeleteTerm,
+        Action::ImportGlossary,
+        Action::MergeDictionaries,
+        Action::ResolveConflict,
+        Action::CreateVersion,
+        Action::RollbackVersion,
+        Action::LockTerm,
+        Action::RequestReview,
+        Action::CommentReview,
+        Action::ReviewTerm,
+        Action::ChangeReviewStatus,
+        Action::ManageCollaborators,
+    ];
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Action::ViewTerms => "צפייה במונחים",
+            Action::EditTerm => "עריכת מונח",
+            Action::DeleteTerm => "מחיקת מונח",
+            Action::ImportGlossary => "ייבוא גיליון מונחים",
+            Action::MergeDictionaries => "מיזוג מילונים",
+            Action::ResolveConflict => "פתרון סתירה",
+            Action::CreateVersion => "יצירת גרסה",
+            Action::RollbackVersion => "שחזור גרסה",
+            Action::LockTerm => "נעילת מונח לעריכה",
+            Action::RequestReview => "בקשת סקירה",
+            Action::CommentReview => "הערה בסקירה",
+            Action::ReviewTerm => "החלטת סקירה",
+            Action::ChangeReviewStatus => "שינוי מצב סקירה",
+            Action::ManageCollaborators => "ניהול משתפי פעולה",
+        })
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum PermissionError {
+    #[error("פעולה נדחתה: ל-{user_id} (תפקיד {role:?}) אין הרשאה ל{action}")]
+    Denied {
+        user_id: String,
+        role: CollaboratorRole,
+        action: Action,
+    },
+    #[error("פעולה נדחתה: {user_id} אינו רשום כמשתף פעולה ({action})")]
+    UnknownUser { user_id: String, action: Action },
+}
+
+/// כללי תפקיד -> פעולות מותרות
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionPolicy {
+    pub rules: HashMap<CollaboratorRole, BTreeSet<Action>>,
+    /// התפקיד של כל משתמש כל עוד לא נרשם אף משתף פעולה (עבודה מקומית של משתמש יחיד);
+    /// `None` - כל פעולה נדחית עד שנרשם מנהל
+    #[serde(default = "default_bootstrap_role")]
+    pub bootstrap_role: Option<CollaboratorRole>,
+}
+
+fn default_bootstrap_role() -> Option<CollaboratorRole> {
+    Some(CollaboratorRole::Admin)
+}
+
+impl Default for PermissionPolicy {
+    fn default() -> Self {
+        use Action::*;
+
+        let rules = [
+            (CollaboratorRole::Admin, Action::ALL.to_vec()),
+            (
+                CollaboratorRole::Editor,
+                vec![
+                    ViewTerms,
+                    EditTerm,
+                    DeleteTerm,
+                    ImportGlossary,
+                    MergeDictionaries,
+                    ResolveConflict,
+                    CreateVersion,
+                    LockTerm,
+                    RequestReview,
+                    CommentReview,
+                ],
+            ),
+            (CollaboratorRole::Reviewer, vec![ViewTerms, CommentReview, ReviewTerm, ChangeReviewStatus]),
+            (CollaboratorRole::Viewer, vec![ViewTerms]),
+        ];
+
+        Self {
+            rules: rules
+                .into_iter()
+                .map(|(role, actions)| (role, actions.into_iter().collect()))
+                .collect(),
+            bootstrap_role: default_bootstrap_role(),
+        }
+    }
+}
+
+impl PermissionPolicy {
+    /// טעינת המדיניות מקובץ JSON; בהיעדר קובץ - מדיניות ברירת המחדל
+    pub fn load_or_default(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let data = std::fs::read_to_string(path)
+            .with_context(|| format!("קריאת קובץ ההרשאות נכשלה: {}", path.display()))?;
+        serde_json::from_str(&data).with_context(|| format!("קובץ ההרשאות אינו תקין: {}", path.display()))
+    }
+
+    pub fn allow(mut self, role: CollaboratorRole, action: Action) -> Self {
+        self.rules.entry(role).or_default().insert(action);
+        self
+    }
+
+    pub fn deny(mut self, role: CollaboratorRole, action: Action) -> Self {
+        if let Some(actions) = self.rules.get_mut(&role) {
+            actions.remove(&action);
+        }
+        self
+    }
+
+    pub fn with_bootstrap_role(mut self, role: Option<CollaboratorRole>) -> Self {
+        self.bootstrap_role = role;
+        self
+    }
+
+    pub fn allows(&self, role: &CollaboratorRole, action: Action) -> bool {
+        self.rules.get(role).map_or(false, |actions| actions.contains(&action))
+    }
+
+    /// התפקיד שבו פועל משתמש: התפקיד הרשום, או תפקיד ההתחלה כל עוד לא נרשם אף משתף פעולה
+    pub fn effective_role<'a>(
+        &'a self,
+        role: Option<&'a CollaboratorRole>,
+        registered_users: bool,
+    ) -> Option<&'a CollaboratorRole> {
+        match (role, &self.bootstrap_role) {
+            (Some(role), _) => Some(role),
+            (None, Some(bootstrap)) if !registered_users => Some(bootstrap),
+            (None, _) => None,
+        }
+    }
+
+    /// בדיקת הרשאה למשתמש; `role` הוא `None` כשהמשתמש אינו רשום.
+    /// `registered_users` - האם נרשם משתף פעולה כלשהו
+    pub fn check(
+        &self,
+        user_id: &str,
+        role: Option<&CollaboratorRole>,
+        registered_users: bool,
+        action: Action,
+    ) -> Result<(), PermissionError> {
+        let Some(role) = self.effective_role(role, registered_users) else {
+            return Err(PermissionError::UnknownUser { user_id: user_id.to_string(), action });
+        };
+        if self.allows(role, action) {
+            Ok(())
+        } else {
+            Err(PermissionError::Denied {
+                user_id: user_id.to_string(),
+                role: role.clone(),
+                action,
+            })
+        }
+    }
+}
+
+/// גיבוב של מפתח גישה; רק הגיבוב נשמר, והמפתח עצמו נמסר למשתמש פעם אחת
+pub fn hash_api_key(key: &str) -> String {
+    format!("{:x}", Sha256::digest(key.trim().as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_rules() {
+        let policy = PermissionPolicy::default();
+        assert!(policy.allows(&CollaboratorRole::Editor, Action::EditTerm));
+        assert!(!policy.allows(&CollaboratorRole::Reviewer, Action::EditTerm));
+        assert!(policy.allows(&CollaboratorRole::Reviewer, Action::ReviewTerm));
+        assert!(!policy.allows(&CollaboratorRole::Viewer, Action::CommentReview));
+
+        let err = policy
+            .check("dana", Some(&CollaboratorRole::Viewer), true, Action::DeleteTerm)
+            .unwrap_err();
+        assert!(matches!(err, PermissionError::Denied { action: Action::DeleteTerm, .. }));
+    }
+
+    #[test]
+    fn test_bootstrap_and_unknown_users() {
+        let policy = PermissionPolicy::default();
+        // לפני שנרשם משתף פעולה כלשהו כל משתמש פועל כמנהל
+        assert!(policy.check("local", None, false, Action::ManageCollaborators).is_ok());
+        assert!(matches!(
+            policy.check("local", None, true, Action::ViewTerms),
+            Err(PermissionError::UnknownUser { .. })
+        ));
+
+        let strict = PermissionPolicy::default().with_bootstrap_role(None);
+        assert!(strict.check("local", None, false, Action::ViewTerms).is_err());
+    }
+
+    #[test]
+    fn test_configured_rules_from_json() {
+        let policy: PermissionPolicy = serde_json::from_str(
+            r#"{ "rules": { "Reviewer": ["view_terms", "edit_term"], "Viewer": [] } }"#,
+        )
+        .unwrap();
+        assert!(policy.allows(&CollaboratorRole::Reviewer, Action::EditTerm));
+        assert!(!policy.allows(&CollaboratorRole::Admin, Action::ViewTerms));
+        // בהיעדר שדה בקובץ - כמו ברירת המחדל
+        assert_eq!(policy.bootstrap_role, Some(CollaboratorRole::Admin));
+        let policy: PermissionPolicy = serde_json::from_str(r#"{ "rules": {}, "bootstrap_role": null }"#).unwrap();
+        assert!(policy.bootstrap_role.is_none());
+        let policy: PermissionPolicy = serde_json::from_str(
+            r#"{ "rules": { "Reviewer": ["view_terms", "edit_term"], "Viewer": [] } }"#,
+        )
+        .unwrap();
+
+        let policy = policy.allow(CollaboratorRole::Viewer, Action::CommentReview).deny(CollaboratorRole::Reviewer, Action::EditTerm);
+        assert!(policy.allows(&CollaboratorRole::Viewer, Action::CommentReview));
+        assert!(!policy.allows(&CollaboratorRole::Reviewer, Action::EditTerm));
+    }
+
+    #[test]
+    fn test_manager_enforces_policy() {
+        use crate::knowledge_sharing::{ChangeType, KnowledgeManager};
+
+        let mut manager = KnowledgeManager::new();
+        manager.add_collaborator("admin", "admin".to_string(), "מנהל".to_string(), CollaboratorRole::Admin).unwrap();
+        manager.add_collaborator("admin", "viewer".to_string(), "צופה".to_string(), CollaboratorRole::Viewer).unwrap();
+
+        let err = manager
+            .track_change("מגוף".to_string(), "viewer".to_string(), ChangeType::Addition, "target".to_string(), None, Some("задвижка".to_string()))
+            .unwrap_err();
+        assert!(matches!(err.downcast_ref::<PermissionError>(), Some(PermissionError::Denied { action: Action::EditTerm, .. })));
+        assert!(manager.get_term_history("מגוף").is_none());
+
+        // אחרי רישום משתף הפעולה הראשון, משתמש לא רשום נדחה
+        assert!(manager.acquire_edit_lock("מגוף".to_string(), "stranger".to_string()).is_err());
+        assert!(manager.add_collaborator("viewer", "x".to_string(), "x".to_string(), CollaboratorRole::Admin).is_err());
+    }
+}
@@ -0,0 +1,292 @@
+use anyhow::{Result, Context};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Serialize, Deserialize};
+use std::fs;
+use std::path::Path;
+use crate::quality_control::find_term_occurrences;
+
+const PLACEHOLDER_OPEN: char = '\u{E000}';
+const PLACEHOLDER_CLOSE: char = '\u{E001}';
+
+lazy_static! {
+    // תבניות מובנות לקטעים שאסור לתרגם
+    static ref BUILTIN_PATTERNS: Vec<(&'static str, Regex)> = vec![
+        // מקדם ספיקה: "K-factor 5.6", "K=80", "K 5.6"
+        ("k_factor", Regex::new(r"(?i)\bK(?:-factor)?\s*[=:]?\s*\d+(?:[.,]\d+)?").unwrap()),
+        // דגמים וקודי קטלוג: "TY3251", "VK-102", "DN50", "3262-75"
+        ("model_number", Regex::new(r"\b[A-Z]{1,5}[-/]?\d{2,}[A-Z0-9./-]*\b|\b\d{2,}[-/]\d{2,}(?:[-/]\d+)*\b").unwrap()),
+        // נוסחאות כימיות: "CO2", "H2O", "NaHCO3"
+        ("chemical", Regex::new(r"\b(?:[A-Z][a-z]?\d*)*[A-Z][a-z]?\d+(?:[A-Z][a-z]?\d*)*\b").unwrap()),
+    ];
+}
+
+/// קטע בטקסט המקור שחייב לעבור את התרגום ללא שינוי
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProtectedSpan {
+    /// מיקום בבתים בטקסט המקור
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+    /// שם הכלל שסימן את הקטע
+    pub rule: String,
+}
+
+/// כלל הגנה מבוסס ביטוי רגולרי
+#[derive(Debug, Clone)]
+pub struct ProtectionPattern {
+    pub name: String,
+    pub regex: Regex,
+}
+
+/// הגדרות הגנה כפי שנשמרות בקובץ הפרויקט
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProtectionConfig {
+    /// מונחים שאין לתרגם: שמות מותגים, דגמים וכדומה
+    #[serde(default)]
+    pub do_not_translate: Vec<String>,
+    /// ביטויים רגולריים לפי שם
+    #[serde(default)]
+    pub patterns: Vec<(String, String)>,
+    #[serde(default = "default_true")]
+    pub builtin_patterns: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// רשימות "לא לתרגם" וכללי ביטויים רגולריים של פרויקט
+#[derive(Debug, Clone, Default)]
+pub struct ProtectionRules {
+    terms: Vec<String>,
+    patterns: Vec<ProtectionPattern>,
+}
+
+/// טקסט שבו הקטעים המוגנים הוחלפו בממלאי מקום
+#[derive(Debug, Clone)]
+pub struct MaskedText {
+    pub text: String,
+    pub spans: Vec<ProtectedSpan>,
+    fully_protected: bool,
+}
+
+impl MaskedText {
+    /// האם הטקסט כולו מורכב מקטעים מוגנים
+    pub fn is_fully_protected(&self) -> bool {
+        self.fully_protected
+    }
+
+    /// החזרת הקטעים המוגנים במקום ממלאי המקום בטקסט המתורגם
+    pub fn restore(&self, translated: &str) -> String {
+        let mut result = translated.to_string();
+        for (index, span) in self.spans.iter().enumerate() {
+            result = result.replace(&placeholder(index), &span.text);
+        }
+        result
+    }
+}
+
+impl ProtectionRules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// כללים הכוללים את התבניות המובנות
+    pub fn with_builtin_patterns() -> Self {
+        let mut rules = Self::new();
+        for (name, regex) in BUILTIN_PATTERNS.iter() {
+            rules.patterns.push(ProtectionPattern {
+                name: name.to_string(),
+                regex: regex.clone(),
+            });
+        }
+        rules
+    }
+
+    pub fn from_config(config: &ProtectionConfig) -> Result<Self> {
+        let mut rules = if config.builtin_patterns {
+            Self::with_builtin_patterns()
+        } else {
+            Self::new()
+        };
+        for term in &config.do_not_translate {
+            rules.add_term(term.clone());
+        }
+        for (name, pattern) in &config.patterns {
+            rules.add_pattern(name.clone(), pattern)?;
+        }
+        Ok(rules)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("קריאת הגדרות ההגנה נכשלה: {}", path.as_ref().display()))?;
+        let config: ProtectionConfig = serde_json::from_str(&content)
+            .context("פענוח הגדרות ההגנה נכשל")?;
+        Self::from_config(&config)
+    }
+
+    pub fn add_term(&mut self, term: String) {
+        let term = term.trim().to_string();
+        if !term.is_empty() && !self.terms.contains(&term) {
+            self.terms.push(term);
+        }
+    }
+
+    pub fn add_pattern(&mut self, name: String, pattern: &str) -> Result<()> {
+        let regex = Regex::new(pattern)
+            .with_context(|| format!("ביטוי רגולרי לא תקין בכלל {}: {}", name, pattern))?;
+        self.patterns.push(ProtectionPattern { name, regex });
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.patterns.is_empty()
+    }
+
+    /// איתור כל הקטעים המוגנים, ממוינים וללא חפיפות
+    pub fn find_spans(&self, text: &str) -> Vec<ProtectedSpan> {
+        let mut spans = Vec::new();
+
+        // מונח מוגן רק כמילה שלמה ובאותיות המדויקות: "Ace" אינו מוגן בתוך "Surface"
+        for term in &self.terms {
+            for (start, end) in find_term_occurrences(text, term) {
+                if &text[start..end] != term {
+                    continue;
+                }
+                spans.push(ProtectedSpan {
+                    start,
+                    end,
+                    text: term.clone(),
+                    rule: "do_not_translate".to_string(),
+                });
+            }
+        }
+
+        for pattern in &self.patterns {
+            for found in pattern.regex.find_iter(text) {
+                spans.push(ProtectedSpan {
+                    start: found.start(),
+                    end: found.end(),
+                    text: found.as_str().to_string(),
+                    rule: pattern.name.clone(),
+                });
+            }
+        }
+
+        // מיזוג קטעים חופפים; הקטע שמתחיל ראשון (והארוך מביניהם) קובע את שם הכלל
+        spans.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+        let mut merged: Vec<ProtectedSpan> = Vec::new();
+        for span in spans {
+            match merged.last_mut() {
+                Some(last) if span.start < last.end => {
+                    if span.end > last.end {
+                        last.end = span.end;
+                        last.text = text[last.start..last.end].to_string();
+                    }
+                }
+                _ => merged.push(span),
+            }
+        }
+        merged
+    }
+
+    /// האם מיקום בטקסט נמצא בתוך קטע מוגן
+    pub fn is_protected(spans: &[ProtectedSpan], position: usize) -> bool {
+        spans.iter().any(|span| span.start <= position && position < span.end)
+    }
+
+    /// החלפת הקטעים המוגנים בממלאי מקום שהמתרגם אינו נוגע בהם
+    pub fn mask(&self, text: &str) -> MaskedText {
+        let spans = self.find_spans(text);
+        let mut masked = String::with_capacity(text.len());
+        let mut cursor = 0;
+
+        for (index, span) in spans.iter().enumerate() {
+            masked.push_str(&text[cursor..span.start]);
+            masked.push_str(&placeholder(index));
+            cursor = span.end;
+        }
+        masked.push_str(&text[cursor..]);
+
+        let fully_protected = !spans.is_empty() && {
+            let mut rest = text.to_string();
+            for span in spans.iter().rev() {
+                rest.replace_range(span.start..span.end, "");
+            }
+            !rest.chars().any(|c| c.is_alphanumeric())
+        };
+
+        MaskedText {
+            text: masked,
+            spans,
+            fully_protected,
+        }
+    }
+
+    /// קטעים מוגנים מהמקור שאינם מופיעים כלשונם בתרגום, לפי מספר המופעים
+    pub fn missing_spans(&self, source_text: &str, translated_text: &str) -> Vec<ProtectedSpan> {
+        let spans = self.find_spans(source_text);
+        let mut missing = Vec::new();
+
+        for (index, span) in spans.iter().enumerate() {
+            let occurrence = spans[..index].iter().filter(|s| s.text == span.text).count();
+            if translated_text.matches(span.text.as_str()).count() <= occurrence {
+                missing.push(span.clone());
+            }
+        }
+        missing
+    }
+}
+
+fn placeholder(index: usize) -> String {
+    format!("{}{}{}", PLACEHOLDER_OPEN, index, PLACEHOLDER_CLOSE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_patterns() {
+        let rules = ProtectionRules::with_builtin_patterns();
+        let spans = rules.find_spans("ראש ספרינקלר TY3251 כולל K-factor 5.6, צינור CO2");
+        let texts: Vec<&str> = spans.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["TY3251", "K-factor 5.6", "CO2"]);
+    }
+
+    #[test]
+    fn test_mask_and_restore() {
+        let mut rules = ProtectionRules::new();
+        rules.add_term("Viking".to_string());
+        rules.add_pattern("catalogue".to_string(), r"\bVK\d+\b").unwrap();
+
+        let masked = rules.mask("ראש Viking דגם VK102");
+        assert_eq!(masked.spans.len(), 2);
+        assert!(!masked.text.contains("Viking"));
+        assert!(!masked.is_fully_protected());
+        assert_eq!(masked.restore(&masked.text), "ראש Viking דגם VK102");
+
+        assert!(rules.mask("Viking VK102").is_fully_protected());
+    }
+
+    #[test]
+    fn test_terms_protected_as_whole_words() {
+        let mut rules = ProtectionRules::new();
+        rules.add_term("Ace".to_string());
+
+        assert!(rules.find_spans("Clean the Surface").is_empty());
+        let spans = rules.find_spans("מגוף Ace, ובמשטח Surface");
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].text, "Ace");
+    }
+
+    #[test]
+    fn test_missing_spans() {
+        let rules = ProtectionRules::with_builtin_patterns();
+        let missing = rules.missing_spans("K-factor 5.6 ו-TY3251", "K-фактор 5,6 и TY3251");
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].text, "K-factor 5.6");
+    }
+}
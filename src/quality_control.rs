@@ -1,6 +1,11 @@
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use anyhow::Result;
+use crate::protection::ProtectionRules;
+use crate::language::Language;
+use crate::standards::{StandardStatus, StandardsDatabase};
+use crate::technical_dictionary::{TechnicalDictionary, UsageStatus};
+use crate::morphology::utils::{is_hebrew_prefix, MAX_HEBREW_PREFIXES};
 use crate::morphology::{
     HebrewMorphology, RussianMorphology,
     HebrewAnalyzer, RussianAnalyzer,
@@ -33,6 +38,8 @@ pub enum IssueType {
     Terminology,
     Cultural,
     Neural,
+    ProtectedSpan,
+    StandardReference,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -223,6 +230,80 @@ impl QualityControl {
         Ok(())
     }
 
+    /// בדיקה שכל הקטעים המוגנים במקור מופיעים כלשונם בתרגום
+    pub fn validate_protected_spans(
+        &self,
+        source_text: &str,
+        translated_text: &str,
+        rules: &ProtectionRules,
+        report: &mut ValidationReport,
+    ) {
+        for span in rules.missing_spans(source_text, translated_text) {
+            report.add_issue(ValidationIssue {
+                issue_type: IssueType::ProtectedSpan,
+                description: format!("קטע מוגן ({}) לא הועתק כלשונו: {}", span.rule, span.text),
+                severity: IssueSeverity::High,
+                position: Some((span.start, span.end)),
+            });
+        }
+    }
+
+    /// סימון צורות מיושנות או אסורות, במקור ובתרגום
+    pub fn validate_term_usage(
+        &self,
+        source_text: &str,
+        translated_text: &str,
+        source_lang: Language,
+        target_lang: Language,
+        dictionary: &TechnicalDictionary,
+        report: &mut ValidationReport,
+    ) {
+        for (text, lang, side) in [
+            (source_text, source_lang, "בטקסט המקור"),
+            (translated_text, target_lang, "בתרגום"),
+        ] {
+            for form in dictionary.discouraged_forms(lang) {
+                for (start, end) in find_term_occurrences(text, &form.text) {
+                    let (label, severity) = match form.status {
+                        UsageStatus::Forbidden => ("מונח אסור", IssueSeverity::High),
+                        _ => ("מונח מיושן", IssueSeverity::Medium),
+                    };
+                    let description = match &form.preferred {
+                        Some(preferred) => format!("{} {}: {} - יש להשתמש ב-{}", label, side, form.text, preferred),
+                        None => format!("{} {}: {} - אין במילון צורה מותרת", label, side, form.text),
+                    };
+                    report.add_issue(ValidationIssue {
+                        issue_type: IssueType::Terminology,
+                        description,
+                        severity,
+                        position: Some((start, end)),
+                    });
+                }
+            }
+        }
+    }
+
+    /// אזהרה על ציון מהדורת תקן שהוחלפה או בוטלה
+    pub fn validate_standard_citations(
+        &self,
+        text: &str,
+        standards: &StandardsDatabase,
+        report: &mut ValidationReport,
+    ) {
+        for warning in standards.edition_warnings(text) {
+            let severity = match warning.status {
+                StandardStatus::Withdrawn => IssueSeverity::High,
+                _ => IssueSeverity::Medium,
+            };
+            report.add_issue(ValidationIssue {
+                issue_type: IssueType::StandardReference,
+                description: warning.message(),
+                severity,
+                position: Some((warning.citation.start, warning.citation.end)),
+            });
+        }
+    }
+
     fn validate_hebrew_gender_agreement(&self, _word: &str, _gender: &Gender) -> bool {
         // TODO: יישום בדיקת התאמת מין בעברית
         true
@@ -286,6 +367,48 @@ impl QualityControl {
     }
 }
 
+/// מופעים של מונח כמילה שלמה, ללא תלות באותיות גדולות; בעברית מותרות אותיות שימוש לפניו.
+/// מחזיר טווחי בתים בטקסט המקורי
+pub(crate) fn find_term_occurrences(text: &str, term: &str) -> Vec<(usize, usize)> {
+    if term.is_empty() {
+        return Vec::new();
+    }
+
+    text.char_indices()
+        .filter_map(|(start, _)| {
+            let end = start + match_ignore_case(&text[start..], term)?;
+            // עד MAX_HEBREW_PREFIXES אותיות שימוש לפני המונח עדיין נחשבות לתחילת מילה
+            let prefixes = text[..start]
+                .chars()
+                .rev()
+                .take(MAX_HEBREW_PREFIXES)
+                .take_while(|c| is_hebrew_prefix(*c))
+                .count();
+            let word_start = text[..start]
+                .chars()
+                .rev()
+                .nth(prefixes)
+                .map_or(true, |c| !c.is_alphanumeric());
+            let word_end = text[end..].chars().next().map_or(true, |c| !c.is_alphanumeric());
+            (word_start && word_end).then_some((start, end))
+        })
+        .collect()
+}
+
+// אורך הקטע בתחילת `text` שזהה ל-`term` ללא תלות באותיות גדולות
+fn match_ignore_case(text: &str, term: &str) -> Option<usize> {
+    let mut chars = text.char_indices();
+    let mut end = 0;
+    for expected in term.chars() {
+        let (position, c) = chars.next()?;
+        if !c.to_lowercase().eq(expected.to_lowercase()) {
+            return None;
+        }
+        end = position + c.len_utf8();
+    }
+    Some(end)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -326,4 +449,70 @@ mod tests {
         let metrics = qc.metrics.lock().await;
         assert_eq!(metrics.total_validations, 1);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_protected_spans_validation() {
+        let qc = QualityControl::new();
+        let rules = ProtectionRules::with_builtin_patterns();
+        let mut report = ValidationReport::new();
+
+        qc.validate_protected_spans("ראש TY3251 עם K-factor 5.6", "ороситель TY3251 с K 5,6", &rules, &mut report);
+
+        let issues: Vec<_> = report.issues.iter()
+            .filter(|i| i.issue_type == IssueType::ProtectedSpan)
+            .collect();
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].description.contains("K-factor 5.6"));
+    }
+
+    #[test]
+    fn test_term_usage_validation() {
+        use crate::technical_dictionary::{TechnicalTerm, TermVariant};
+
+        let dictionary = TechnicalDictionary::in_memory();
+        let mut term = TechnicalTerm::for_test("ראש ספרינקלר", "ороситель", "fire_protection");
+        term.variants = vec![
+            TermVariant { text: "ממטיר".to_string(), lang: Language::Hebrew, status: UsageStatus::Forbidden },
+            TermVariant { text: "спринклер".to_string(), lang: Language::Russian, status: UsageStatus::Deprecated },
+        ];
+        dictionary.insert_terms(vec![term]).unwrap();
+
+        let qc = QualityControl::new();
+        let mut report = ValidationReport::new();
+        qc.validate_term_usage(
+            "להחליף את הממטיר",
+            "заменить спринклер, спринклерный узел",
+            Language::Hebrew,
+            Language::Russian,
+            &dictionary,
+            &mut report,
+        );
+
+        let issues: Vec<_> = report.issues.iter()
+            .filter(|i| i.issue_type == IssueType::Terminology)
+            .collect();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].severity, IssueSeverity::High);
+        assert_eq!(issues[1].severity, IssueSeverity::Medium);
+    }
+
+    #[test]
+    fn test_standard_citation_validation() {
+        use crate::standards::create_initial_standards;
+
+        let qc = QualityControl::new();
+        let mut report = ValidationReport::new();
+        qc.validate_standard_citations(
+            "התקנה לפי NFPA 13:2019, צנרת לפי ГОСТ 3262-75",
+            &create_initial_standards(),
+            &mut report,
+        );
+
+        let issues: Vec<_> = report.issues.iter()
+            .filter(|i| i.issue_type == IssueType::StandardReference)
+            .collect();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, IssueSeverity::Medium);
+        assert!(issues[0].description.contains("NFPA13-2025"));
+    }
+}
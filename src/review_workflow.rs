@@ -0,0 +1,257 @@
+//! מחזור החיים של בקשת סקירה ומספר האישורים הנדרש לפי תחום.
+//!
+//! Pending → InReview → Approved / Rejected / NeedsChanges, ו-NeedsChanges או Rejected חוזרים
+//! ל-InReview אחרי תיקון. Approved הוא מצב סופי: המונח מסומן כמאומת במילון.
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::knowledge_sharing::ReviewStatus;
+
+impl ReviewStatus {
+    /// האם מותר לעבור מהמצב הנוכחי למצב `next`
+    pub fn can_transition_to(self, next: ReviewStatus) -> bool {
+        use ReviewStatus::*;
+        matches!(
+            (self, next),
+            (Pending, InReview)
+                | (InReview, Approved | Rejected | NeedsChanges)
+                | (NeedsChanges, InReview)
+                | (Rejected, InReview)
+        )
+    }
+
+    /// מצבים שסוקר יכול לבחור בהחלטה
+    pub fn is_decision(self) -> bool {
+        matches!(self, ReviewStatus::Approved | ReviewStatus::Rejected | ReviewStatus::NeedsChanges)
+    }
+
+    pub fn is_final(self) -> bool {
+        self == ReviewStatus::Approved
+    }
+}
+
+/// מספר האישורים הנדרש לאישור סופי, לפי תחום המונח
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewQuorum {
+    pub default_approvals: usize,
+    #[serde(default)]
+    pub domains: HashMap<String, usize>,
+}
+
+impl Default for ReviewQuorum {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl ReviewQuorum {
+    pub fn new(default_approvals: usize) -> Self {
+        Self {
+            default_approvals: default_approvals.max(1),
+            domains: HashMap::new(),
+        }
+    }
+
+    pub fn with_domain(mut self, domain: &str, approvals: usize) -> Self {
+        self.domains.insert(domain.to_string(), approvals.max(1));
+        self
+    }
+
+    pub fn required(&self, domain: Option<&str>) -> usize {
+        domain
+            .and_then(|domain| self.domains.get(domain))
+            .copied()
+            .unwrap_or(self.default_approvals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge_sharing::{CollaboratorRole, KnowledgeManager};
+    use crate::technical_dictionary::{TechnicalDictionary, TechnicalTerm};
+
+    fn proposed_term() -> TechnicalTerm {
+        let mut term = TechnicalTerm::for_test("מגוף", "задвижка", "hydraulics");
+        term.english = Some("gate valve".to_string());
+        term.metadata.confidence_score = 0.6;
+        term
+    }
+
+    fn manager() -> KnowledgeManager {
+        let mut manager = KnowledgeManager::new().with_review_quorum(ReviewQuorum::new(1).with_domain("hydraulics", 2));
+        manager.add_collaborator("admin", "admin".to_string(), "מנהל".to_string(), CollaboratorRole::Admin).unwrap();
+        manager.add_collaborator("admin", "editor".to_string(), "עורך".to_string(), CollaboratorRole::Editor).unwrap();
+        for reviewer in ["r1", "r2"] {
+            manager.add_collaborator("admin", reviewer.to_string(), reviewer.to_string(), CollaboratorRole::Reviewer).unwrap();
+        }
+        manager
+    }
+
+    #[test]
+    fn test_transitions() {
+        use ReviewStatus::*;
+        assert!(Pending.can_transition_to(InReview));
+        assert!(!Pending.can_transition_to(Approved));
+        assert!(InReview.can_transition_to(NeedsChanges));
+        assert!(NeedsChanges.can_transition_to(InReview));
+        assert!(!Approved.can_transition_to(InReview));
+
+        let quorum = ReviewQuorum::new(1).with_domain("fire", 3);
+        assert_eq!(quorum.required(Some("fire")), 3);
+        assert_eq!(quorum.required(Some("general")), 1);
+    }
+
+    #[test]
+    fn test_quorum_approval_verifies_term() {
+        let dictionary = TechnicalDictionary::in_memory();
+        let mut manager = manager();
+        assert!(manager.acquire_edit_lock("מגוף".to_string(), "editor".to_string()).unwrap());
+        let request_id = manager
+            .propose_term(proposed_term(), "editor".to_string(), vec!["r1".to_string(), "r2".to_string()], String::new())
+            .unwrap();
+
+        // אישור ראשון מתוך שניים: הבקשה נשארת בסקירה והמונח לא נוסף
+        let status = manager
+            .record_review_decision(&dictionary, &request_id, "r1".to_string(), ReviewStatus::Approved, "תקין".to_string())
+            .unwrap();
+        assert_eq!(status, ReviewStatus::InReview);
+        assert!(dictionary.get_term("מגוף").is_none());
+        assert!(manager.get_pending_reviews("r1").is_empty());
+
+        let status = manager
+            .record_review_decision(&dictionary, &request_id, "r2".to_string(), ReviewStatus::Approved, "תקין".to_string())
+            .unwrap();
+        assert_eq!(status, ReviewStatus::Approved);
+        assert!(dictionary.get_term("מגוף").unwrap().metadata.verified);
+        let change = manager.get_term_history("מגוף").unwrap().changes.last().unwrap().clone();
+        assert_eq!(change.field, "metadata.verified");
+        assert_eq!(change.changed_by, "r2");
+        // הנעילה שוחררה
+        assert!(manager.acquire_edit_lock("מגוף".to_string(), "admin".to_string()).unwrap());
+        // בקשה שאושרה סופית אינה נפתחת מחדש
+        assert!(manager.update_review_status(&request_id, "admin", ReviewStatus::Rejected).is_err());
+    }
+
+    #[test]
+    fn test_approved_proposal_keeps_usage() {
+        let dictionary = TechnicalDictionary::in_memory();
+        let mut existing = proposed_term();
+        existing.metadata.usage_count = 12;
+        existing.metadata.usage.accepted = 12;
+        dictionary.insert_terms(vec![existing]).unwrap();
+        let mut manager = manager();
+
+        // מניין ההידראוליקה הוא שניים, ולכן סוקר יחיד אינו מספיק
+        assert!(manager
+            .propose_term(proposed_term(), "editor".to_string(), vec!["r1".to_string()], String::new())
+            .is_err());
+
+        let mut replacement = proposed_term();
+        replacement.target = "шибер".to_string();
+        let request_id = manager
+            .propose_term(replacement, "editor".to_string(), vec!["r1".to_string(), "r2".to_string()], String::new())
+            .unwrap();
+        for reviewer in ["r1", "r2"] {
+            manager
+                .record_review_decision(&dictionary, &request_id, reviewer.to_string(), ReviewStatus::Approved, String::new())
+                .unwrap();
+        }
+
+        let term = dictionary.get_term("מגוף").unwrap();
+        assert_eq!(term.target, "шибер");
+        assert!(term.metadata.verified);
+        assert_eq!((term.metadata.usage_count, term.metadata.usage.accepted), (12, 12));
+    }
+
+    #[test]
+    fn test_invalid_transitions_rejected() {
+        let dictionary = TechnicalDictionary::in_memory();
+        let mut manager = manager();
+        let request_id = manager
+            .propose_term(proposed_term(), "editor".to_string(), vec!["r1".to_string(), "r2".to_string()], String::new())
+            .unwrap();
+
+        // אישור אינו אפשרי בשינוי מצב ישיר, ורק סוקרי הבקשה מחליטים
+        assert!(manager.update_review_status(&request_id, "r1", ReviewStatus::Approved).is_err());
+        assert!(manager
+            .record_review_decision(&dictionary, &request_id, "admin".to_string(), ReviewStatus::Approved, String::new())
+            .is_err());
+
+        manager
+            .record_review_decision(&dictionary, &request_id, "r1".to_string(), ReviewStatus::Approved, String::new())
+            .unwrap();
+        manager
+            .record_review_decision(&dictionary, &request_id, "r2".to_string(), ReviewStatus::NeedsChanges, "חסר תקן".to_string())
+            .unwrap();
+        assert!(manager.get_review_request(&request_id).unwrap().approvals.is_empty());
+        assert!(manager
+            .record_review_decision(&dictionary, &request_id, "r1".to_string(), ReviewStatus::Approved, String::new())
+            .is_err());
+
+        // המגיש מחזיר את הבקשה לסקירה אחרי התיקון
+        manager.update_review_status(&request_id, "editor", ReviewStatus::InReview).unwrap();
+        assert_eq!(manager.get_review_request(&request_id).unwrap().status, ReviewStatus::InReview);
+    }
+
+    #[test]
+    fn test_rejection_clears_approvals() {
+        let dictionary = TechnicalDictionary::in_memory();
+        let mut manager = manager();
+        let request_id = manager
+            .propose_term(proposed_term(), "editor".to_string(), vec!["r1".to_string(), "r2".to_string()], String::new())
+            .unwrap();
+
+        manager
+            .record_review_decision(&dictionary, &request_id, "r1".to_string(), ReviewStatus::Approved, String::new())
+            .unwrap();
+        manager
+            .record_review_decision(&dictionary, &request_id, "r2".to_string(), ReviewStatus::Rejected, "מונח שגוי".to_string())
+            .unwrap();
+        assert!(manager.get_review_request(&request_id).unwrap().approvals.is_empty());
+
+        // אחרי החזרה לסקירה אישור יחיד אינו מספיק בהידראוליקה
+        manager.update_review_status(&request_id, "editor", ReviewStatus::InReview).unwrap();
+        let status = manager
+            .record_review_decision(&dictionary, &request_id, "r1".to_string(), ReviewStatus::Approved, String::new())
+            .unwrap();
+        assert_eq!(status, ReviewStatus::InReview);
+        assert!(dictionary.get_term("מגוף").is_none());
+    }
+
+    #[test]
+    fn test_decisions_recorded_as_usage() {
+        let dictionary = TechnicalDictionary::in_memory();
+        dictionary.insert_terms(vec![proposed_term()]).unwrap();
+        let mut manager = manager();
+        let request_id = manager
+            .create_review_request(&dictionary, "מגוף".to_string(), "editor".to_string(), vec!["r1".to_string(), "r2".to_string()])
+            .unwrap();
+
+        manager
+            .record_review_decision(&dictionary, &request_id, "r1".to_string(), ReviewStatus::Approved, String::new())
+            .unwrap();
+        manager
+            .record_review_decision(&dictionary, &request_id, "r2".to_string(), ReviewStatus::NeedsChanges, "חסר תקן".to_string())
+            .unwrap();
+
+        // החלטה חוזרת של אותו סוקר אינה נספרת שוב
+        manager.update_review_status(&request_id, "editor", ReviewStatus::InReview).unwrap();
+        manager
+            .record_review_decision(&dictionary, &request_id, "r1".to_string(), ReviewStatus::Approved, String::new())
+            .unwrap();
+        let usage = dictionary.get_term("מגוף").unwrap().metadata.usage;
+        assert_eq!((usage.accepted, usage.rejected), (1, 1));
+
+        // דחיית הצעה להחליף את המונח שומרת על המונח הקיים ואינה נרשמת כדחייה שלו
+        let mut replacement = proposed_term();
+        replacement.target = "шибер".to_string();
+        let proposal_id = manager
+            .propose_term(replacement, "editor".to_string(), vec!["r1".to_string(), "r2".to_string()], String::new())
+            .unwrap();
+        manager
+            .record_review_decision(&dictionary, &proposal_id, "r2".to_string(), ReviewStatus::Rejected, String::new())
+            .unwrap();
+        assert_eq!(dictionary.get_term("מגוף").unwrap().metadata.usage.rejected, 1);
+    }
+}
@@ -0,0 +1,270 @@
+use crate::morphology::utils::is_prefixed_form;
+use crate::translation_models::{Domain, DomainModel};
+use serde::{Serialize, Deserialize};
+use std::collections::{BTreeMap, HashMap};
+
+// משקלות הראיות: מילים שכנות חזקות מהתחום של המקטע, והמקטע חזק מהמסמך כולו
+const NEIGHBOUR_WEIGHT: f64 = 1.0;
+const SEGMENT_WEIGHT: f64 = 0.6;
+const DOCUMENT_WEIGHT: f64 = 0.3;
+const NEIGHBOUR_WINDOW: usize = 4;
+
+/// משמעות אפשרית של מונח רב-משמעי, מתויגת לפי תחום
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SenseCandidate {
+    pub target: String,
+    /// שם הדיסציפלינה, כמו בתחומי `DomainModel::with_disciplines`
+    pub domain: String,
+    /// מילים שכנות שמעידות על המשמעות
+    pub cues: Vec<String>,
+}
+
+/// החלטת בחירת משמעות, יחד עם הראיות שהובילו אליה
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SenseDecision {
+    pub term: String,
+    pub chosen: String,
+    pub domain: String,
+    /// כל המועמדים והציון של כל אחד
+    pub scores: Vec<(String, f64)>,
+    pub evidence: Vec<String>,
+}
+
+/// הקשר המסמך לבחירת משמעות: ציוני תחום של המסמך כולו ומילים שכנות
+#[derive(Debug, Clone, Default)]
+pub struct SenseContext {
+    pub document_scores: HashMap<Domain, f64>,
+    pub neighbours: Vec<String>,
+}
+
+impl SenseContext {
+    /// הקשר שבו מילים שכנות נלקחות מחלון סביב המקטע
+    pub fn with_neighbours(&self, segments: &[&str], index: usize) -> Self {
+        let from = index.saturating_sub(1);
+        let to = (index + 2).min(segments.len());
+        let neighbours = segments[from..to]
+            .iter()
+            .flat_map(|s| s.split_whitespace())
+            .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_string())
+            .filter(|w| !w.is_empty())
+            .collect();
+
+        Self {
+            document_scores: self.document_scores.clone(),
+            neighbours,
+        }
+    }
+
+    /// הדיסציפלינה הבולטת במסמך, אם יש כזו
+    pub fn document_domain(&self) -> Option<&str> {
+        self.document_scores
+            .iter()
+            .filter(|(_, score)| **score > 0.0)
+            .filter_map(|(domain, score)| match domain {
+                Domain::Custom(name) => Some((name.as_str(), *score)),
+                _ => None,
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(name, _)| name)
+    }
+}
+
+/// בחירת משמעות למונחים רב-משמעיים לפי תחום, מסמך ומילים שכנות.
+/// המשמעויות עצמן באות מהמילון הטכני וממסד המונחים, לפי תחום; כאן נשמרות רק מילות
+/// הרמז של כל תחום
+pub struct SenseDisambiguator {
+    domain_cues: BTreeMap<String, Vec<String>>,
+    domain_model: DomainModel,
+}
+
+impl SenseDisambiguator {
+    pub fn new() -> Self {
+        let mut disambiguator = Self {
+            domain_cues: BTreeMap::new(),
+            domain_model: DomainModel::with_disciplines(),
+        };
+
+        let cues: [(&str, &[&str]); 5] = [
+            ("hydraulics", &["מים", "צנרת", "בר", "משאבה", "מד", "ספרינקלר", "מגוף", "ספיקה", "קוטר", "ניקוז", "אספקה"]),
+            ("structural", &["קורה", "בטון", "פלדה", "עומס", "חתך", "מאמץ"]),
+            ("plumbing", &["כיור", "מטבח", "אמבטיה", "ברזייה", "ביוב"]),
+            ("fire_protection", &["כיבוי", "אש", "זרנוק", "הידרנט", "עמדת"]),
+            ("electrical", &["חשמל", "כבל", "מתח", "תקשורת", "שרטוט"]),
+        ];
+        for (domain, words) in cues {
+            disambiguator.add_cues(domain, words);
+        }
+
+        disambiguator
+    }
+
+    /// מילים שכנות שמעידות על תחום
+    pub fn add_cues(&mut self, domain: &str, words: &[&str]) {
+        self.domain_cues
+            .entry(domain.to_string())
+            .or_default()
+            .extend(words.iter().map(|w| w.to_string()));
+    }
+
+    /// התחומים שיש להם מילות רמז, לחיפוש משמעויות לפי תחום
+    pub fn domains(&self) -> impl Iterator<Item = &str> {
+        self.domain_cues.keys().map(String::as_str)
+    }
+
+    /// מועמד לבחירה: תרגום בתחום מסוים, עם מילות הרמז של התחום
+    pub fn candidate(&self, target: &str, domain: &str) -> SenseCandidate {
+        SenseCandidate {
+            target: target.to_string(),
+            domain: domain.to_string(),
+            cues: self.domain_cues.get(domain).cloned().unwrap_or_default(),
+        }
+    }
+
+    /// ציוני תחום לכל המסמך, מחושבים פעם אחת לפני התרגום
+    pub fn document_context(&self, text: &str) -> SenseContext {
+        SenseContext {
+            document_scores: self.domain_model.scores(text),
+            neighbours: Vec::new(),
+        }
+    }
+
+    /// בחירת המשמעות המתאימה מבין המועמדים; `None` אם יש פחות משני מועמדים
+    pub fn disambiguate(
+        &self,
+        term: &str,
+        candidates: &[SenseCandidate],
+        context: &SenseContext,
+    ) -> Option<SenseDecision> {
+        if candidates.len() < 2 {
+            return None;
+        }
+
+        let term = term.trim();
+        let segment_text = context.neighbours.join(" ");
+        let segment_scores = self.domain_model.scores(&segment_text);
+        let segment_domain = self.domain_model.detect(&segment_text).unwrap_or(Domain::General);
+
+        // חלון מילים סביב המונח בתוך ההקשר
+        let window: Vec<&String> = match context.neighbours.iter().position(|w| matches_word(w, term)) {
+            Some(position) => {
+                let from = position.saturating_sub(NEIGHBOUR_WINDOW);
+                let to = (position + NEIGHBOUR_WINDOW + 1).min(context.neighbours.len());
+                context.neighbours[from..to].iter().collect()
+            }
+            None => context.neighbours.iter().collect(),
+        };
+
+        let mut evidence = Vec::new();
+        let mut scores = Vec::new();
+
+        for candidate in candidates {
+            let domain = Domain::Custom(candidate.domain.clone());
+            let mut score = 0.0;
+
+            let cue_hits: Vec<&String> = window
+                .iter()
+                .copied()
+                .filter(|w| candidate.cues.iter().any(|cue| matches_word(w, cue)))
+                .collect();
+            if !cue_hits.is_empty() {
+                score += NEIGHBOUR_WEIGHT * cue_hits.len() as f64;
+                evidence.push(format!(
+                    "{}: מילים שכנות {}",
+                    candidate.target,
+                    cue_hits.iter().map(|w| w.as_str()).collect::<Vec<_>>().join(", ")
+                ));
+            }
+
+            let segment_score = segment_scores.get(&domain).copied().unwrap_or(0.0);
+            if segment_score > 0.0 {
+                score += SEGMENT_WEIGHT * segment_score;
+                if segment_domain == domain {
+                    evidence.push(format!("{}: תחום המקטע {}", candidate.target, candidate.domain));
+                }
+            }
+
+            let document_score = context.document_scores.get(&domain).copied().unwrap_or(0.0);
+            if document_score > 0.0 {
+                score += DOCUMENT_WEIGHT * document_score;
+                evidence.push(format!(
+                    "{}: ציון תחום {} במסמך {:.3}",
+                    candidate.target, candidate.domain, document_score
+                ));
+            }
+
+            scores.push((candidate, score));
+        }
+
+        // בשוויון נבחרת המשמעות הראשונה שהוגדרה (ברירת המחדל)
+        let (chosen, _) = scores
+            .iter()
+            .fold(None::<&(&SenseCandidate, f64)>, |best, current| match best {
+                Some(best) if best.1 >= current.1 => Some(best),
+                _ => Some(current),
+            })?;
+        if scores.iter().all(|(_, score)| *score == 0.0) {
+            evidence.push("אין ראיות - נבחרה משמעות ברירת המחדל".to_string());
+        }
+
+        Some(SenseDecision {
+            term: term.to_string(),
+            chosen: chosen.target.clone(),
+            domain: chosen.domain.clone(),
+            scores: scores.iter().map(|(c, s)| (c.target.clone(), *s)).collect(),
+            evidence,
+        })
+    }
+}
+
+// התאמת מילה למונח, כולל אותיות שימוש בעברית ("בצנרת", "הלחץ")
+fn matches_word(word: &str, term: &str) -> bool {
+    is_prefixed_form(word, term)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context_for(text: &str, disambiguator: &SenseDisambiguator) -> SenseContext {
+        let segments: Vec<&str> = vec![text];
+        disambiguator.document_context(text).with_neighbours(&segments, 0)
+    }
+
+    #[test]
+    fn test_neighbours_choose_sense() {
+        let disambiguator = SenseDisambiguator::new();
+        let candidates = vec![
+            disambiguator.candidate("давление", "hydraulics"),
+            disambiguator.candidate("напряжение", "structural"),
+        ];
+
+        let context = context_for("יש לבדוק את הלחץ בקורה תחת עומס", &disambiguator);
+        let decision = disambiguator.disambiguate("לחץ", &candidates, &context).unwrap();
+        assert_eq!(decision.chosen, "напряжение");
+        assert!(!decision.evidence.is_empty());
+
+        let context = context_for("הלחץ בצנרת המים 6 בר", &disambiguator);
+        let decision = disambiguator.disambiguate("לחץ", &candidates, &context).unwrap();
+        assert_eq!(decision.chosen, "давление");
+    }
+
+    #[test]
+    fn test_short_cues_match_whole_words() {
+        let disambiguator = SenseDisambiguator::new();
+        // "אש" בתוך "ראש", "מתח" בתוך "מתחת", "מים" בתוך "הקיימים" ו"בר" בתוך "עבר"
+        let context = disambiguator.document_context("ראש הצוות עבר מתחת לקירות הקיימים");
+        assert!(context.document_scores.values().all(|score| *score == 0.0));
+
+        let context = disambiguator.document_context("כיבוי אש ומתח בלוח");
+        assert!(context.document_scores[&Domain::Custom("fire_protection".to_string())] > 0.0);
+        assert!(context.document_scores[&Domain::Custom("electrical".to_string())] > 0.0);
+    }
+
+    #[test]
+    fn test_unambiguous_term() {
+        let disambiguator = SenseDisambiguator::new();
+        let context = SenseContext::default();
+        let candidates = vec![disambiguator.candidate("задвижка", "hydraulics")];
+        assert!(disambiguator.disambiguate("מגוף", &candidates, &context).is_none());
+    }
+}
@@ -1,7 +1,22 @@
+use anyhow::{Result, Context};
+use lazy_static::lazy_static;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use crate::language::Language;
 
-#[derive(Debug, Serialize, Deserialize)]
+lazy_static! {
+    // ציון תקן בטקסט: "ГОСТ Р 51052-2002", "NFPA 13:2019", "ת"י 1596", "СП 5.13130.2009";
+    // אות שימוש לפני ציון עברי ("בת"י 1596") אינה חלק מהציון
+    static ref CITATION_PATTERN: Regex = Regex::new(
+        r#"\b[ובלמש]?((ГОСТ\s?Р|ГОСТ|GOST\s?R|GOST|СП|SP|NFPA|ISO|EN|DIN|ת["״]י|IS|SI)\s?(\d+(?:\.\d+)*)(?:\s?[-:–]\s?(\d{4}|\d{2})\b)?)"#
+    ).unwrap();
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Standard {
     pub code: String,
     pub name_he: String,
@@ -10,21 +25,202 @@ pub struct Standard {
     pub description_he: Option<String>,
     pub description_ru: Option<String>,
     pub related_standards: Vec<String>,
+    /// הגוף המפרסם: מכון התקנים, Росстандарт, NFPA
+    #[serde(default)]
+    pub issuing_body: Option<String>,
+    /// צורות הציון של התקן בטקסט, למשל "ГОСТ 3262" או "ת\"י 1596"
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub editions: Vec<StandardEdition>,
+    #[serde(default)]
+    pub clauses: Vec<StandardClause>,
+}
+
+impl Standard {
+    /// הצורה המקובלת לציון התקן: הכינוי הראשון, או הקוד
+    pub fn designation(&self) -> &str {
+        self.aliases.first().map(String::as_str).unwrap_or(&self.code)
+    }
+
+    pub fn edition(&self, year: u16) -> Option<&StandardEdition> {
+        self.editions.iter().find(|edition| edition.year == year)
+    }
+
+    /// המהדורה העדכנית ביותר שבתוקף
+    pub fn current_edition(&self) -> Option<&StandardEdition> {
+        self.editions
+            .iter()
+            .filter(|edition| edition.status == StandardStatus::Active)
+            .max_by_key(|edition| edition.year)
+    }
+
+    pub fn clause(&self, number: &str) -> Option<&StandardClause> {
+        self.clauses.iter().find(|clause| clause.number == number.trim())
+    }
+}
+
+/// מעמד של מהדורת תקן
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StandardStatus {
+    #[default]
+    Active,
+    /// הוחלפה במהדורה חדשה או בתקן אחר
+    Superseded,
+    /// בוטלה ללא תחליף, או שהתחליף מצוין ב-`replaced_by`
+    Withdrawn,
+}
+
+impl StandardStatus {
+    fn label(&self) -> &'static str {
+        match self {
+            StandardStatus::Active => "בתוקף",
+            StandardStatus::Superseded => "הוחלפה",
+            StandardStatus::Withdrawn => "בוטלה",
+        }
+    }
+}
+
+/// הפניה למהדורה מסוימת של תקן
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EditionRef {
+    pub code: String,
+    pub year: Option<u16>,
+}
+
+impl fmt::Display for EditionRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.year {
+            Some(year) => write!(f, "{}-{}", self.code, year),
+            None => write!(f, "{}", self.code),
+        }
+    }
+}
+
+/// מהדורה של תקן
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StandardEdition {
+    pub year: u16,
+    #[serde(default)]
+    pub status: StandardStatus,
+    /// המהדורה או התקן שהחליפו את המהדורה הזו
+    #[serde(default)]
+    pub replaced_by: Option<EditionRef>,
+}
+
+/// סעיף בתקן וכותרתו
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StandardClause {
+    /// מספר הסעיף: "5.2.1"
+    pub number: String,
+    #[serde(default)]
+    pub title_he: Option<String>,
+    #[serde(default)]
+    pub title_ru: Option<String>,
+    #[serde(default)]
+    pub title_en: Option<String>,
+}
+
+impl StandardClause {
+    pub fn title(&self, lang: Language) -> Option<&str> {
+        match lang {
+            Language::Hebrew => self.title_he.as_deref(),
+            Language::Russian => self.title_ru.as_deref(),
+            Language::English => self.title_en.as_deref(),
+        }
+    }
+}
+
+/// ציון תקן שנמצא בטקסט
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StandardCitation {
+    /// קוד התקן במסד הנתונים
+    pub code: String,
+    pub year: Option<u16>,
+    pub text: String,
+    /// מיקום בבתים בטקסט
+    pub start: usize,
+    pub end: usize,
+}
+
+/// אזהרה על ציון מהדורה שאינה בתוקף
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditionWarning {
+    pub citation: StandardCitation,
+    pub status: StandardStatus,
+    /// המהדורה העדכנית בסוף שרשרת ההחלפות, אם ידועה
+    pub replacement: Option<EditionRef>,
+}
+
+impl EditionWarning {
+    pub fn message(&self) -> String {
+        match &self.replacement {
+            Some(replacement) => format!(
+                "המהדורה {} {}; יש לבדוק מול {}",
+                self.citation.text,
+                self.status.label(),
+                replacement
+            ),
+            None => format!("המהדורה {} {}", self.citation.text, self.status.label()),
+        }
+    }
+}
+
+// שורה בקובץ CSV של תקנים: שורה לכל מהדורה או סעיף, השדות הכלליים נלקחים מהשורה הראשונה
+#[derive(Debug, Deserialize)]
+struct StandardRow {
+    code: String,
+    #[serde(default)]
+    name_he: String,
+    #[serde(default)]
+    name_ru: String,
+    #[serde(default)]
+    country: String,
+    #[serde(default)]
+    issuing_body: Option<String>,
+    /// כינויים מופרדים בנקודה-פסיק
+    #[serde(default)]
+    aliases: Option<String>,
+    #[serde(default)]
+    related: Option<String>,
+    #[serde(default)]
+    year: Option<u16>,
+    #[serde(default)]
+    status: Option<StandardStatus>,
+    #[serde(default)]
+    replaced_by: Option<String>,
+    #[serde(default)]
+    replaced_by_year: Option<u16>,
+    #[serde(default)]
+    clause: Option<String>,
+    #[serde(default)]
+    clause_title_he: Option<String>,
+    #[serde(default)]
+    clause_title_ru: Option<String>,
+    #[serde(default)]
+    clause_title_en: Option<String>,
 }
 
 #[derive(Debug)]
 pub struct StandardsDatabase {
     standards: HashMap<String, Standard>,
+    // צורת ציון מנורמלת ← קוד התקן
+    designations: HashMap<String, String>,
 }
 
 impl StandardsDatabase {
     pub fn new() -> Self {
         Self {
             standards: HashMap::new(),
+            designations: HashMap::new(),
         }
     }
 
     pub fn add_standard(&mut self, standard: Standard) {
+        for designation in std::iter::once(&standard.code).chain(standard.aliases.iter()) {
+            self.designations.insert(normalize_designation(designation), standard.code.clone());
+        }
         self.standards.insert(standard.code.clone(), standard);
     }
 
@@ -32,6 +228,15 @@ impl StandardsDatabase {
         self.standards.get(code)
     }
 
+    /// חיפוש לפי כל צורת ציון: "ГОСТ 3262", "GOST3262-75", "ת\"י 1596"
+    pub fn find_by_designation(&self, designation: &str) -> Option<&Standard> {
+        self.standards.get(designation).or_else(|| {
+            self.designations
+                .get(&normalize_designation(designation))
+                .and_then(|code| self.standards.get(code))
+        })
+    }
+
     pub fn get_related_standards(&self, code: &str) -> Vec<&Standard> {
         if let Some(standard) = self.standards.get(code) {
             standard
@@ -43,12 +248,212 @@ impl StandardsDatabase {
             vec![]
         }
     }
+
+    pub fn len(&self) -> usize {
+        self.standards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.standards.is_empty()
+    }
+
+    /// שרשרת ההחלפות החל מהמהדורה הנתונה, עד מהדורה שבתוקף או שאינה ידועה
+    pub fn supersession_chain(&self, start: &EditionRef) -> Vec<EditionRef> {
+        let mut chain = Vec::new();
+        let mut visited = HashSet::new();
+        let mut current = start.clone();
+
+        while visited.insert(current.clone()) {
+            let Some(edition) = self.edition_of(&current) else {
+                break;
+            };
+            match &edition.replaced_by {
+                Some(next) => {
+                    chain.push(next.clone());
+                    current = next.clone();
+                }
+                None => break,
+            }
+        }
+        chain
+    }
+
+    fn edition_of(&self, reference: &EditionRef) -> Option<&StandardEdition> {
+        let standard = self.find_by_designation(&reference.code)?;
+        match reference.year {
+            Some(year) => standard.edition(year),
+            None => standard.current_edition(),
+        }
+    }
+
+    /// כותרת הסעיף בשפה המבוקשת, אם הוגדרה
+    pub fn clause_title(&self, code: &str, clause: &str, lang: Language) -> Option<&str> {
+        self.find_by_designation(code)?.clause(clause)?.title(lang)
+    }
+
+    /// כל ציוני התקנים המוכרים בטקסט
+    pub fn find_citations(&self, text: &str) -> Vec<StandardCitation> {
+        CITATION_PATTERN
+            .captures_iter(text)
+            .filter_map(|captures| {
+                let whole = captures.get(1)?;
+                let (number, mut year) = split_year_suffix(&captures[3]);
+                if let Some(suffix) = captures.get(4) {
+                    year = parse_year(suffix.as_str());
+                }
+                let standard = self.find_by_designation(&format!("{}{}", &captures[2], number))?;
+                Some(StandardCitation {
+                    code: standard.code.clone(),
+                    year,
+                    text: whole.as_str().trim().to_string(),
+                    start: whole.start(),
+                    end: whole.end(),
+                })
+            })
+            .collect()
+    }
+
+    /// אזהרות על מהדורות שהוחלפו או בוטלו; ציון ללא שנה מתייחס למהדורה העדכנית
+    pub fn edition_warnings(&self, text: &str) -> Vec<EditionWarning> {
+        self.find_citations(text)
+            .into_iter()
+            .filter_map(|citation| {
+                let year = citation.year?;
+                let edition = self.standards.get(&citation.code)?.edition(year)?;
+                if edition.status == StandardStatus::Active {
+                    return None;
+                }
+                let replacement = self
+                    .supersession_chain(&EditionRef { code: citation.code.clone(), year: Some(year) })
+                    .pop();
+                Some(EditionWarning {
+                    status: edition.status,
+                    replacement,
+                    citation,
+                })
+            })
+            .collect()
+    }
+
+    /// ייבוא תקנים מקובץ JSON (מערך של תקנים); מחזיר את מספר התקנים שנוספו או עודכנו
+    pub fn import_json(&mut self, path: &Path) -> Result<usize> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("קריאת קובץ התקנים נכשלה: {}", path.display()))?;
+        let standards: Vec<Standard> = serde_json::from_str(&content)
+            .with_context(|| format!("פענוח קובץ התקנים נכשל: {}", path.display()))?;
+
+        let count = standards.len();
+        for standard in standards {
+            self.add_standard(standard);
+        }
+        Ok(count)
+    }
+
+    /// ייבוא תקנים מקובץ CSV, שורה לכל מהדורה או סעיף
+    pub fn import_csv(&mut self, path: &Path) -> Result<usize> {
+        let mut reader = csv::Reader::from_path(path)
+            .with_context(|| format!("קריאת קובץ התקנים נכשלה: {}", path.display()))?;
+
+        // BTreeMap לשמירה על סדר יציב בין ייבואים
+        let mut standards: BTreeMap<String, Standard> = BTreeMap::new();
+        for (index, row) in reader.deserialize::<StandardRow>().enumerate() {
+            let row = row.with_context(|| format!("שורה {} בקובץ התקנים אינה תקינה", index + 2))?;
+            let standard = standards.entry(row.code.clone()).or_insert_with(|| Standard {
+                code: row.code.clone(),
+                name_he: row.name_he.clone(),
+                name_ru: row.name_ru.clone(),
+                country: row.country.clone(),
+                description_he: None,
+                description_ru: None,
+                related_standards: split_list(row.related.as_deref()),
+                issuing_body: row.issuing_body.clone(),
+                aliases: split_list(row.aliases.as_deref()),
+                editions: Vec::new(),
+                clauses: Vec::new(),
+            });
+
+            if let Some(year) = row.year {
+                if standard.edition(year).is_none() {
+                    standard.editions.push(StandardEdition {
+                        year,
+                        status: row.status.unwrap_or_default(),
+                        replaced_by: row.replaced_by.as_ref().map(|code| EditionRef {
+                            code: code.clone(),
+                            year: row.replaced_by_year,
+                        }),
+                    });
+                }
+            }
+            if let Some(number) = row.clause {
+                standard.clauses.push(StandardClause {
+                    number,
+                    title_he: row.clause_title_he,
+                    title_ru: row.clause_title_ru,
+                    title_en: row.clause_title_en,
+                });
+            }
+        }
+
+        let count = standards.len();
+        for mut standard in standards.into_values() {
+            standard.editions.sort_by_key(|edition| edition.year);
+            self.add_standard(standard);
+        }
+        Ok(count)
+    }
+}
+
+// "ГОСТ Р 51052" ו-"GOST R51052" מגיעים לאותו מפתח
+pub(crate) fn normalize_designation(designation: &str) -> String {
+    let compact: String = designation
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '"' | '״' | '\''))
+        .flat_map(char::to_uppercase)
+        .collect();
+    compact
+        .replace("ГОСТ", "GOST")
+        .replace("GOSTР", "GOSTR")
+        .replace("СП", "SP")
+        .replace("תי", "IS")
+}
+
+// "5.13130.2009" - שנת המהדורה מופיעה לעתים כרכיב האחרון של המספר
+fn split_year_suffix(number: &str) -> (&str, Option<u16>) {
+    match number.rsplit_once('.') {
+        Some((base, last)) if last.len() == 4 && base.contains('.') => match parse_year(last) {
+            Some(year) => (base, Some(year)),
+            None => (number, None),
+        },
+        _ => (number, None),
+    }
+}
+
+// שנה בשתי ספרות, כמו ב-"ГОСТ 3262-75"
+fn parse_year(text: &str) -> Option<u16> {
+    let year: u16 = text.parse().ok()?;
+    match text.len() {
+        2 if year > 30 => Some(1900 + year),
+        2 => Some(2000 + year),
+        4 if (1900..2100).contains(&year) => Some(year),
+        _ => None,
+    }
+}
+
+fn split_list(list: Option<&str>) -> Vec<String> {
+    list.map(|list| {
+        list.split(';')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::to_string)
+            .collect()
+    })
+    .unwrap_or_default()
 }
 
 // יצירת מסד נתוני תקנים ראשוני
 pub fn create_initial_standards() -> StandardsDatabase {
     let mut db = StandardsDatabase::new();
-    
+
     // הוספת תקנים בסיסיים
     db.add_standard(Standard {
         code: "IS1596".to_string(),
@@ -58,6 +463,10 @@ pub fn create_initial_standards() -> StandardsDatabase {
         description_he: Some("תקן למערכות כיבוי אש אוטומטיות (ספרינקלרים)".to_string()),
         description_ru: Some("Стандарт для автоматических систем пожаротушения (спринклеров)".to_string()),
         related_standards: vec!["NFPA13".to_string()],
+        issuing_body: Some("מכון התקנים הישראלי".to_string()),
+        aliases: vec!["ת\"י 1596".to_string(), "SI 1596".to_string()],
+        editions: Vec::new(),
+        clauses: Vec::new(),
     });
 
     db.add_standard(Standard {
@@ -68,7 +477,121 @@ pub fn create_initial_standards() -> StandardsDatabase {
         description_he: Some("תקן לצנרת פלדה מגולוונת למערכות אינסטלציה".to_string()),
         description_ru: Some("Стандарт для оцинкованных стальных труб в системах водоснабжения".to_string()),
         related_standards: vec![],
+        issuing_body: Some("Росстандарт".to_string()),
+        aliases: vec!["ГОСТ 3262".to_string()],
+        editions: vec![StandardEdition { year: 1975, status: StandardStatus::Active, replaced_by: None }],
+        clauses: Vec::new(),
+    });
+
+    db.add_standard(Standard {
+        code: "NFPA13".to_string(),
+        name_he: "NFPA 13 - התקנת מערכות מתזים".to_string(),
+        name_ru: "NFPA 13 - Монтаж спринклерных систем".to_string(),
+        country: "ארה\"ב".to_string(),
+        description_he: None,
+        description_ru: None,
+        related_standards: vec!["IS1596".to_string()],
+        issuing_body: Some("NFPA".to_string()),
+        aliases: vec!["NFPA 13".to_string()],
+        editions: vec![
+            StandardEdition {
+                year: 2019,
+                status: StandardStatus::Superseded,
+                replaced_by: Some(EditionRef { code: "NFPA13".to_string(), year: Some(2022) }),
+            },
+            StandardEdition {
+                year: 2022,
+                status: StandardStatus::Superseded,
+                replaced_by: Some(EditionRef { code: "NFPA13".to_string(), year: Some(2025) }),
+            },
+            StandardEdition { year: 2025, status: StandardStatus::Active, replaced_by: None },
+        ],
+        clauses: Vec::new(),
     });
 
     db
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gost_51052() -> Standard {
+        Standard {
+            code: "GOSTR51052".to_string(),
+            name_he: "ГОСТ Р 51052 - תחנות בקרה".to_string(),
+            name_ru: "ГОСТ Р 51052 - Узлы управления".to_string(),
+            country: "רוסיה".to_string(),
+            description_he: None,
+            description_ru: None,
+            related_standards: Vec::new(),
+            issuing_body: Some("Росстандарт".to_string()),
+            aliases: vec!["ГОСТ Р 51052".to_string(), "ГОСТ 51052".to_string()],
+            editions: vec![
+                StandardEdition {
+                    year: 2002,
+                    status: StandardStatus::Withdrawn,
+                    replaced_by: Some(EditionRef { code: "GOSTR51052".to_string(), year: Some(2023) }),
+                },
+                StandardEdition { year: 2023, status: StandardStatus::Active, replaced_by: None },
+            ],
+            clauses: vec![StandardClause {
+                number: "5.2.1".to_string(),
+                title_he: Some("דרישות כלליות".to_string()),
+                title_ru: Some("Общие требования".to_string()),
+                title_en: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_find_citations() {
+        let db = create_initial_standards();
+        let citations = db.find_citations("צנרת לפי ГОСТ 3262-75 ומתזים לפי ת\"י 1596 ו-NFPA 13:2019");
+
+        let found: Vec<(&str, Option<u16>)> = citations.iter().map(|c| (c.code.as_str(), c.year)).collect();
+        assert_eq!(found, vec![("GOST3262-75", Some(1975)), ("IS1596", None), ("NFPA13", Some(2019))]);
+    }
+
+    #[test]
+    fn test_withdrawn_edition_warning() {
+        let mut db = create_initial_standards();
+        db.add_standard(gost_51052());
+
+        let warnings = db.edition_warnings("Узел управления по ГОСТ Р 51052-2002, трубы по ГОСТ 3262-75");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].status, StandardStatus::Withdrawn);
+        assert_eq!(warnings[0].replacement.as_ref().unwrap().year, Some(2023));
+
+        // שרשרת: 2019 ← 2022 ← 2025
+        let chain = db.supersession_chain(&EditionRef { code: "NFPA13".to_string(), year: Some(2019) });
+        assert_eq!(chain.last().unwrap().year, Some(2025));
+        assert_eq!(chain.len(), 2);
+        assert!(db.edition_warnings("לפי NFPA 13").is_empty());
+    }
+
+    #[test]
+    fn test_import_csv() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("standards.csv");
+        fs::write(
+            &path,
+            "code,name_he,name_ru,country,issuing_body,aliases,year,status,replaced_by,replaced_by_year,clause,clause_title_ru\n\
+             SP5.13130,קובץ כללים 5.13130,СП 5.13130,רוסיה,МЧС России,СП 5.13130,2009,withdrawn,SP484.1311500,2020,,\n\
+             SP5.13130,,,,,,,,,,5.2.1,Общие требования\n",
+        )
+        .unwrap();
+
+        let mut db = StandardsDatabase::new();
+        let count = db.import_csv(&path).unwrap();
+
+        assert_eq!(count, 1);
+        let standard = db.find_by_designation("СП 5.13130").unwrap();
+        assert_eq!(standard.editions[0].status, StandardStatus::Withdrawn);
+        assert_eq!(db.clause_title("SP5.13130", "5.2.1", Language::Russian), Some("Общие требования"));
+
+        let warnings = db.edition_warnings("согласно СП 5.13130.2009");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].replacement.as_ref().unwrap().code, "SP484.1311500");
+    }
+}
@@ -0,0 +1,247 @@
+use crate::translation::{Translator, SegmentTranslation, TranslationOutput};
+use anyhow::Result;
+use serde::{Serialize, Deserialize};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+const DEFAULT_CHANNEL_CAPACITY: usize = 32;
+
+/// התקדמות תרגום של מסמך ארוך
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TranslationProgress {
+    pub segments_done: usize,
+    pub segments_total: usize,
+    pub words_done: usize,
+    pub words_total: usize,
+}
+
+impl TranslationProgress {
+    /// חלק העבודה שהושלם, לפי מספר מילים
+    pub fn fraction(&self) -> f32 {
+        if self.words_total == 0 {
+            if self.segments_total == 0 {
+                return 1.0;
+            }
+            return self.segments_done as f32 / self.segments_total as f32;
+        }
+        self.words_done as f32 / self.words_total as f32
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.segments_done >= self.segments_total
+    }
+}
+
+/// אירוע בזרם התרגום
+#[derive(Debug, Clone)]
+pub enum StreamEvent {
+    /// מקטע תורגם
+    Segment {
+        index: usize,
+        segment: SegmentTranslation,
+        progress: TranslationProgress,
+    },
+    /// כל המקטעים תורגמו
+    Completed(TranslationOutput),
+    /// התרגום בוטל; מצורפת ההתקדמות עד לביטול
+    Cancelled(TranslationProgress),
+}
+
+/// זרם של מקטעים מתורגמים
+pub struct TranslationStream {
+    receiver: mpsc::Receiver<StreamEvent>,
+    cancel: CancellationToken,
+}
+
+impl TranslationStream {
+    /// ממתין לאירוע הבא; `None` כאשר הזרם הסתיים
+    pub async fn next(&mut self) -> Option<StreamEvent> {
+        self.receiver.recv().await
+    }
+
+    /// קריאה ללא המתנה, לשימוש מתוך לולאת ציור של הממשק
+    pub fn try_next(&mut self) -> Option<StreamEvent> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// מבקש לעצור את התרגום לאחר המקטע הנוכחי
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+    }
+
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancel.clone()
+    }
+}
+
+/// מתרגם מסמכים ארוכים מקטע אחר מקטע, עם דיווח התקדמות ואפשרות ביטול
+pub struct StreamingTranslator {
+    translator: Arc<Translator>,
+    channel_capacity: usize,
+}
+
+impl StreamingTranslator {
+    pub fn new(translator: Arc<Translator>) -> Self {
+        Self {
+            translator,
+            channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+        }
+    }
+
+    pub fn with_channel_capacity(mut self, capacity: usize) -> Self {
+        self.channel_capacity = capacity.max(1);
+        self
+    }
+
+    pub fn translator(&self) -> &Arc<Translator> {
+        &self.translator
+    }
+
+    /// מתחיל תרגום ברקע ומחזיר זרם של מקטעים מתורגמים
+    pub fn translate_stream(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        cancel: CancellationToken,
+    ) -> Result<TranslationStream> {
+        let (source, target) = Translator::resolve_languages(source_lang, target_lang)?;
+        let sentences = self.translator.split_segments(text);
+        let document_context = self.translator.sense_context(text);
+        let contexts: Vec<_> = (0..sentences.len())
+            .map(|index| document_context.with_neighbours(&sentences, index))
+            .collect();
+        let segments: Vec<String> = sentences.into_iter().map(str::to_string).collect();
+
+        let (tx, rx) = mpsc::channel(self.channel_capacity);
+        let translator = self.translator.clone();
+        let token = cancel.clone();
+
+        // התרגום עצמו סינכרוני, ולכן רץ על תהליכון חוסם
+        tokio::task::spawn_blocking(move || {
+            let mut progress = TranslationProgress {
+                segments_total: segments.len(),
+                words_total: segments.iter().map(|s| count_words(s)).sum(),
+                ..Default::default()
+            };
+            let mut output = TranslationOutput::new(source, target);
+
+            for (index, text) in segments.iter().enumerate() {
+                if token.is_cancelled() {
+                    let _ = tx.blocking_send(StreamEvent::Cancelled(progress));
+                    return;
+                }
+
+                let segment = translator.translate_segment_in(text, source, target, &contexts[index]);
+                progress.segments_done += 1;
+                progress.words_done += count_words(text);
+                // המקטע נשלח עם מספור הערות השוליים של המסמך
+                output.push_sentence(segment);
+                let segment = output.segments[index].clone();
+
+                let event = StreamEvent::Segment {
+                    index,
+                    segment,
+                    progress: progress.clone(),
+                };
+                // הצד המקבל נסגר - אין טעם להמשיך
+                if tx.blocking_send(event).is_err() {
+                    return;
+                }
+            }
+
+            let _ = tx.blocking_send(StreamEvent::Completed(output));
+        });
+
+        Ok(TranslationStream {
+            receiver: rx,
+            cancel,
+        })
+    }
+
+    /// תרגום מלא עם קריאה חוזרת לכל עדכון התקדמות; מחזיר שגיאה אם בוטל
+    pub async fn translate_with_progress<F>(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        cancel: CancellationToken,
+        mut on_progress: F,
+    ) -> Result<TranslationOutput>
+    where
+        F: FnMut(&TranslationProgress),
+    {
+        let mut stream = self.translate_stream(text, source_lang, target_lang, cancel)?;
+
+        while let Some(event) = stream.next().await {
+            match event {
+                StreamEvent::Segment { progress, .. } => on_progress(&progress),
+                StreamEvent::Completed(output) => return Ok(output),
+                StreamEvent::Cancelled(progress) => anyhow::bail!(
+                    "התרגום בוטל לאחר {} מתוך {} מקטעים",
+                    progress.segments_done,
+                    progress.segments_total
+                ),
+            }
+        }
+
+        anyhow::bail!("זרם התרגום הסתיים באופן בלתי צפוי")
+    }
+}
+
+fn count_words(text: &str) -> usize {
+    text.split_whitespace().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::technical_dictionary::TechnicalDictionary;
+    use crate::technical_terms::create_initial_terms;
+    use crate::standards::create_initial_standards;
+    use std::sync::Mutex;
+
+    fn create_streaming_translator() -> StreamingTranslator {
+        let translator = Translator::new(
+            Arc::new(create_initial_terms()),
+            Arc::new(create_initial_standards()),
+            Arc::new(Mutex::new(TechnicalDictionary::in_memory())),
+        );
+        StreamingTranslator::new(Arc::new(translator))
+    }
+
+    #[tokio::test]
+    async fn test_stream_progress() {
+        let streaming = create_streaming_translator();
+        let mut updates = Vec::new();
+
+        let output = streaming
+            .translate_with_progress(
+                "תקין. נדרש ניקוי שנתי.",
+                "he",
+                "ru",
+                CancellationToken::new(),
+                |p| updates.push(p.clone()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(output.text, "исправен. требуется ежегодная очистка.");
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[1].words_done, 4);
+        assert!(updates[1].is_finished());
+    }
+
+    #[tokio::test]
+    async fn test_stream_cancelled() {
+        let streaming = create_streaming_translator();
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        let result = streaming
+            .translate_with_progress("תקין. לא נמצאו.", "he", "ru", cancel, |_| {})
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,795 @@
+use crate::language::{Language, Multilingual};
+use crate::technical_dictionary::{TechnicalDictionary, TechnicalTerm, TermMetadata, TermVariant, UsageStatus};
+use crate::term_usage::UsageStats;
+use crate::technical_terms::{self, TermsDatabase};
+use anyhow::{Result, Context};
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::{Serialize, Deserialize};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+const TBX_NAMESPACE: &str = "urn:iso:std:iso:30042:ed-2";
+const TBX_REFERENCE_PREFIX: &str = "tbx:";
+
+/// מונח בודד בתוך קטע שפה
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TbxTerm {
+    pub text: String,
+    pub usage_status: Option<UsageStatus>,
+    pub part_of_speech: Option<String>,
+    pub notes: Vec<String>,
+}
+
+/// קטע שפה של מושג
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TbxLangSet {
+    /// קוד השפה כפי שהופיע בקובץ (xml:lang)
+    pub lang: String,
+    pub definition: Option<String>,
+    pub terms: Vec<TbxTerm>,
+}
+
+impl TbxLangSet {
+    pub fn language(&self) -> Option<Language> {
+        // "he-IL" -> "he"
+        Language::from_code(self.lang.split(['-', '_']).next().unwrap_or_default())
+    }
+
+    /// המונח המועדף: מסומן כמועדף, אחרת הראשון שאינו מיושן
+    pub fn preferred_term(&self) -> Option<&TbxTerm> {
+        self.terms
+            .iter()
+            .find(|t| t.usage_status == Some(UsageStatus::Preferred))
+            .or_else(|| self.terms.iter().find(|t| !t.usage_status.map_or(false, |s| s.is_discouraged())))
+            .or_else(|| self.terms.first())
+    }
+}
+
+/// רשומת מושג (termEntry / conceptEntry)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TbxConcept {
+    pub id: String,
+    pub subject_field: Option<String>,
+    pub definition: Option<String>,
+    pub notes: Vec<String>,
+    pub lang_sets: Vec<TbxLangSet>,
+}
+
+impl TbxConcept {
+    pub fn lang_set(&self, lang: Language) -> Option<&TbxLangSet> {
+        self.lang_sets.iter().find(|set| set.language() == Some(lang))
+    }
+
+    fn definition_in(&self, lang: Language) -> Option<String> {
+        self.lang_set(lang)
+            .and_then(|set| set.definition.clone())
+            .or_else(|| self.definition.clone())
+    }
+}
+
+/// מסמך TBX-Basic
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TbxDocument {
+    /// שפת המקור של המסמך
+    pub source_lang: Option<String>,
+    pub concepts: Vec<TbxConcept>,
+}
+
+/// שדה שבו הרשומה המיובאת שונה מהרשומה הקיימת
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TbxConflictField {
+    Target,
+    English,
+    Domain,
+    UsageStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TbxConflict {
+    pub concept_id: String,
+    pub source: String,
+    pub field: TbxConflictField,
+    pub existing: String,
+    pub incoming: String,
+}
+
+/// מה לעשות ברשומה קיימת שהייבוא סותר
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TbxImportMode {
+    /// הרשומה הקיימת נשמרת והסתירה מדווחת
+    KeepExisting,
+    /// הרשומה המיובאת מחליפה את הקיימת והסתירה מדווחת
+    Overwrite,
+}
+
+/// דוח ייבוא: מה נוסף, מה זהה, מה סותר ומה דולג
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TbxImportReport {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub unchanged: Vec<String>,
+    pub conflicts: Vec<TbxConflict>,
+    /// מושגים שאין בהם מונח באחת משפות הצמד, עם הסיבה
+    pub skipped: Vec<(String, String)>,
+}
+
+impl TbxImportReport {
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "נוספו {}, עודכנו {}, ללא שינוי {}, סתירות {}, דולגו {}",
+            self.added.len(),
+            self.updated.len(),
+            self.unchanged.len(),
+            self.conflicts.len(),
+            self.skipped.len()
+        )
+    }
+}
+
+impl TbxDocument {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("קריאת קובץ TBX נכשלה: {}", path.as_ref().display()))?;
+        Self::parse(&content)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        fs::write(&path, self.to_xml())
+            .with_context(|| format!("שמירת קובץ TBX נכשלה: {}", path.as_ref().display()))
+    }
+
+    /// פענוח TBX-Basic, גם במבנה 2008 (martif/termEntry/langSet/tig)
+    /// וגם במבנה 2019 (tbx/conceptEntry/langSec/termSec)
+    pub fn parse(xml: &str) -> Result<Self> {
+        let mut reader = Reader::from_str(xml);
+        reader.trim_text(true);
+
+        let mut document = TbxDocument::default();
+        let mut concept: Option<TbxConcept> = None;
+        let mut lang_set: Option<TbxLangSet> = None;
+        let mut term: Option<TbxTerm> = None;
+        let mut element_type: Option<String> = None;
+        let mut text = String::new();
+
+        loop {
+            match reader.read_event().context("קובץ TBX אינו XML תקין")? {
+                Event::Start(e) => {
+                    let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                    match name.as_str() {
+                        "martif" | "tbx" => document.source_lang = attribute(&e, "xml:lang")?,
+                        "termEntry" | "conceptEntry" => {
+                            let id = attribute(&e, "id")?
+                                .unwrap_or_else(|| format!("c{}", document.concepts.len() + 1));
+                            concept = Some(TbxConcept { id, ..Default::default() });
+                        }
+                        "langSet" | "langSec" => {
+                            let lang = attribute(&e, "xml:lang")?.unwrap_or_default();
+                            lang_set = Some(TbxLangSet { lang, ..Default::default() });
+                        }
+                        "tig" | "ntig" | "termSec" => term = Some(TbxTerm::default()),
+                        "term" | "termNote" | "descrip" | "note" | "admin" => {
+                            element_type = attribute(&e, "type")?;
+                            text.clear();
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Text(t) => text.push_str(&t.unescape()?),
+                Event::End(e) => {
+                    let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                    let value = text.trim().to_string();
+                    match name.as_str() {
+                        "term" => {
+                            if let Some(term) = term.as_mut() {
+                                term.text = value;
+                            }
+                        }
+                        "termNote" => {
+                            if let Some(term) = term.as_mut() {
+                                match element_type.as_deref() {
+                                    Some("administrativeStatus") => term.usage_status = status_from_tbx(&value),
+                                    Some("partOfSpeech") => term.part_of_speech = Some(value),
+                                    _ => {}
+                                }
+                            }
+                        }
+                        "descrip" => match element_type.as_deref() {
+                            Some("subjectField") => {
+                                if let Some(concept) = concept.as_mut() {
+                                    concept.subject_field = Some(value);
+                                }
+                            }
+                            Some("definition") => match (lang_set.as_mut(), concept.as_mut()) {
+                                (Some(set), _) => set.definition = Some(value),
+                                (None, Some(concept)) => concept.definition = Some(value),
+                                _ => {}
+                            },
+                            _ => {}
+                        },
+                        "note" if !value.is_empty() => {
+                            // ההערה שייכת לרמה הפנימית ביותר שפתוחה
+                            if let Some(term) = term.as_mut() {
+                                term.notes.push(value);
+                            } else if let Some(concept) = concept.as_mut() {
+                                concept.notes.push(value);
+                            }
+                        }
+                        "tig" | "ntig" | "termSec" => {
+                            if let (Some(term), Some(set)) = (term.take(), lang_set.as_mut()) {
+                                if !term.text.is_empty() {
+                                    set.terms.push(term);
+                                }
+                            }
+                        }
+                        "langSet" | "langSec" => {
+                            if let (Some(set), Some(concept)) = (lang_set.take(), concept.as_mut()) {
+                                concept.lang_sets.push(set);
+                            }
+                        }
+                        "termEntry" | "conceptEntry" => {
+                            if let Some(concept) = concept.take() {
+                                document.concepts.push(concept);
+                            }
+                        }
+                        _ => {}
+                    }
+                    if matches!(name.as_str(), "term" | "termNote" | "descrip" | "note" | "admin") {
+                        element_type = None;
+                        text.clear();
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(document)
+    }
+
+    /// כתיבת המסמך כ-TBX-Basic במבנה 2019
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::new();
+        let source_lang = self.source_lang.as_deref().unwrap_or(Language::Hebrew.code());
+
+        let _ = writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        let _ = writeln!(
+            xml,
+            r#"<tbx type="TBX-Basic" style="dca" xml:lang="{}" xmlns="{}">"#,
+            escape(source_lang),
+            TBX_NAMESPACE
+        );
+        let _ = writeln!(xml, "  <tbxHeader><fileDesc><sourceDesc><p>RustoHebru</p></sourceDesc></fileDesc></tbxHeader>");
+        let _ = writeln!(xml, "  <text>");
+        let _ = writeln!(xml, "    <body>");
+
+        for concept in &self.concepts {
+            let _ = writeln!(xml, r#"      <conceptEntry id="{}">"#, escape(&concept.id));
+            if let Some(field) = &concept.subject_field {
+                let _ = writeln!(xml, r#"        <descrip type="subjectField">{}</descrip>"#, escape(field));
+            }
+            if let Some(definition) = &concept.definition {
+                let _ = writeln!(xml, r#"        <descrip type="definition">{}</descrip>"#, escape(definition));
+            }
+            for note in &concept.notes {
+                let _ = writeln!(xml, "        <note>{}</note>", escape(note));
+            }
+
+            for set in &concept.lang_sets {
+                let _ = writeln!(xml, r#"        <langSec xml:lang="{}">"#, escape(&set.lang));
+                if let Some(definition) = &set.definition {
+                    let _ = writeln!(xml, r#"          <descrip type="definition">{}</descrip>"#, escape(definition));
+                }
+                for term in &set.terms {
+                    let _ = writeln!(xml, "          <termSec>");
+                    let _ = writeln!(xml, "            <term>{}</term>", escape(&term.text));
+                    if let Some(status) = term.usage_status {
+                        let _ = writeln!(
+                            xml,
+                            r#"            <termNote type="administrativeStatus">{}</termNote>"#,
+                            status_to_tbx(status)
+                        );
+                    }
+                    if let Some(pos) = &term.part_of_speech {
+                        let _ = writeln!(xml, r#"            <termNote type="partOfSpeech">{}</termNote>"#, escape(pos));
+                    }
+                    for note in &term.notes {
+                        let _ = writeln!(xml, "            <note>{}</note>", escape(note));
+                    }
+                    let _ = writeln!(xml, "          </termSec>");
+                }
+                let _ = writeln!(xml, "        </langSec>");
+            }
+            let _ = writeln!(xml, "      </conceptEntry>");
+        }
+
+        let _ = writeln!(xml, "    </body>");
+        let _ = writeln!(xml, "  </text>");
+        let _ = writeln!(xml, "</tbx>");
+        xml
+    }
+
+    /// ייצוא המילון הטכני: מושג לכל מונח, עם קטע שפה למקור, ליעד ולאנגלית
+    pub fn from_dictionary(dictionary: &TechnicalDictionary) -> Self {
+        let mut document = TbxDocument {
+            source_lang: Some(Language::Hebrew.code().to_string()),
+            concepts: Vec::new(),
+        };
+
+        for (index, term) in dictionary.all_terms().into_iter().enumerate() {
+            // מזהה המושג המקורי נשמר בייבוא, כך שייצוא חוזר שומר עליו
+            let id = term
+                .metadata
+                .source_references
+                .iter()
+                .find_map(|r| r.strip_prefix(TBX_REFERENCE_PREFIX))
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("c{}", index + 1));
+
+            // קטע שפה לכל שפה שיש בה צורה ראשית, עם הצורות החלופיות ומעמדן
+            let mut lang_sets = Vec::new();
+            for lang in Language::ALL {
+                let Some(main) = term.text_in(lang) else {
+                    continue;
+                };
+                let mut terms = vec![TbxTerm {
+                    text: main.to_string(),
+                    usage_status: term.main_status(lang),
+                    ..Default::default()
+                }];
+                terms.extend(term.variants.iter().filter(|v| v.lang == lang && v.text != main).map(|variant| TbxTerm {
+                    text: variant.text.clone(),
+                    usage_status: Some(variant.status),
+                    ..Default::default()
+                }));
+
+                lang_sets.push(TbxLangSet {
+                    lang: lang.code().to_string(),
+                    definition: if lang == term.source_lang { term.definition.clone() } else { None },
+                    terms,
+                });
+            }
+
+            document.concepts.push(TbxConcept {
+                id,
+                subject_field: Some(term.domain.clone()),
+                definition: None,
+                notes: term.notes.clone(),
+                lang_sets,
+            });
+        }
+
+        document
+    }
+
+    /// ייצוא מסד המונחים הקבוע: מושג לכל רשומה, עם כל הצורות בכל שפה
+    pub fn from_terms_database(database: &TermsDatabase) -> Self {
+        let mut concepts: Vec<&technical_terms::Concept> = database.concepts().collect();
+        concepts.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let concepts = concepts
+            .into_iter()
+            .map(|concept| {
+                let lang_sets = Language::ALL
+                    .iter()
+                    .filter_map(|&lang| {
+                        let forms: Vec<&technical_terms::ConceptTerm> = concept.terms_in(lang).collect();
+                        if forms.is_empty() {
+                            return None;
+                        }
+                        // מעמד נרשם רק כשיש יותר מצורה אחת: הראשונה מועדפת, והשאר לפי תגיות השימוש
+                        let several = forms.len() > 1;
+                        let terms = forms
+                            .iter()
+                            .enumerate()
+                            .map(|(position, form)| TbxTerm {
+                                text: form.text.clone(),
+                                usage_status: several.then(|| {
+                                    if position == 0 {
+                                        UsageStatus::Preferred
+                                    } else {
+                                        form.usage
+                                            .iter()
+                                            .find_map(|tag| status_from_tbx(tag))
+                                            .unwrap_or(UsageStatus::Admitted)
+                                    }
+                                }),
+                                notes: form.context.iter().map(|context| format!("context: {}", context)).collect(),
+                                ..Default::default()
+                            })
+                            .collect();
+                        Some(TbxLangSet {
+                            lang: lang.code().to_string(),
+                            definition: None,
+                            terms,
+                        })
+                    })
+                    .collect();
+                let mut notes: Vec<String> = concept.notes.iter().cloned().collect();
+                if !concept.context.is_empty() {
+                    notes.push(format!("context: {}", concept.context));
+                }
+                notes.extend(concept.standards.iter().map(|standard| format!("standard: {}", standard)));
+
+                TbxConcept {
+                    id: concept.id.clone(),
+                    subject_field: Some(concept.domain.clone()),
+                    definition: None,
+                    notes,
+                    lang_sets,
+                }
+            })
+            .collect();
+
+        TbxDocument {
+            source_lang: Some(Language::Hebrew.code().to_string()),
+            concepts,
+        }
+    }
+
+    /// המרת המושגים לרשומות מילון בכיוון התרגום המבוקש
+    pub fn to_technical_terms(&self, source: Language, target: Language) -> (Vec<(String, TechnicalTerm)>, Vec<(String, String)>) {
+        let mut terms = Vec::new();
+        let mut skipped = Vec::new();
+
+        for concept in &self.concepts {
+            let (Some(source_set), Some(target_set)) = (concept.lang_set(source), concept.lang_set(target)) else {
+                skipped.push((concept.id.clone(), format!("חסר קטע שפה {} או {}", source, target)));
+                continue;
+            };
+            let (Some(source_term), Some(target_term)) = (source_set.preferred_term(), target_set.preferred_term()) else {
+                skipped.push((concept.id.clone(), "קטע שפה ללא מונחים".to_string()));
+                continue;
+            };
+
+            let english = concept
+                .lang_set(Language::English)
+                .and_then(TbxLangSet::preferred_term)
+                .map(|term| term.text.clone());
+            // כל שאר הצורות נשמרות כחלופות, עם המעמד שלהן בקובץ
+            let main_texts = [Some(&source_term.text), Some(&target_term.text), english.as_ref()];
+            let variants = [source, target, Language::English]
+                .into_iter()
+                .filter_map(|lang| concept.lang_set(lang).map(|set| (lang, set)))
+                .flat_map(|(lang, set)| {
+                    set.terms
+                        .iter()
+                        .filter(|t| !main_texts.contains(&Some(&t.text)))
+                        .map(move |t| TermVariant {
+                            text: t.text.clone(),
+                            lang,
+                            status: t.usage_status.unwrap_or_default(),
+                        })
+                })
+                .collect();
+            let mut notes = concept.notes.clone();
+            notes.extend(source_term.notes.iter().cloned());
+            notes.extend(target_term.notes.iter().cloned());
+
+            let mut term = TechnicalTerm {
+                source: source_term.text.clone(),
+                target: target_term.text.clone(),
+                source_lang: source,
+                target_lang: target,
+                english,
+                domain: concept.subject_field.clone().unwrap_or_else(|| "general".to_string()),
+                context: Vec::new(),
+                usage_examples: Vec::new(),
+                variants,
+                definition: concept.definition_in(source),
+                notes,
+                metadata: TermMetadata {
+                    confidence_score: 0.8,
+                    last_updated: chrono::Utc::now(),
+                    usage_count: 0,
+                    verified: target_term.usage_status.is_some(),
+                    source_references: vec![format!("{}{}", TBX_REFERENCE_PREFIX, concept.id)],
+                    usage: UsageStats::default(),
+                },
+            };
+            // מעמד הצורה הראשית ביעד נשמר כמו בקובץ; צורה ללא מעמד נחשבת מותרת
+            term.set_status(&target_term.text, target, target_term.usage_status.unwrap_or_default());
+            terms.push((concept.id.clone(), term));
+        }
+
+        (terms, skipped)
+    }
+
+    /// ייבוא למילון הטכני, עם דוח סתירות מול הרשומות הקיימות
+    pub fn import_into_dictionary(
+        &self,
+        dictionary: &TechnicalDictionary,
+        source: Language,
+        target: Language,
+        mode: TbxImportMode,
+    ) -> Result<TbxImportReport> {
+        let (terms, skipped) = self.to_technical_terms(source, target);
+        let mut report = TbxImportReport {
+            skipped,
+            ..Default::default()
+        };
+        let mut accepted = Vec::new();
+
+        for (concept_id, incoming) in terms {
+            let Some(existing) = dictionary.get_term(&incoming.source) else {
+                report.added.push(incoming.source.clone());
+                accepted.push(incoming);
+                continue;
+            };
+
+            let conflicts = compare_terms(&concept_id, &existing, &incoming);
+            if conflicts.is_empty() {
+                report.unchanged.push(incoming.source.clone());
+                continue;
+            }
+            report.conflicts.extend(conflicts);
+            if mode == TbxImportMode::Overwrite {
+                report.updated.push(incoming.source.clone());
+                accepted.push(incoming);
+            }
+        }
+
+        if !accepted.is_empty() {
+            dictionary.insert_terms(accepted)?;
+        }
+        Ok(report)
+    }
+
+    /// ייבוא למסד המונחים הקבוע; מושג קיים מזוהה לפי הצורה העברית המועדפת
+    pub fn import_into_terms_database(&self, database: &mut TermsDatabase, mode: TbxImportMode) -> TbxImportReport {
+        let mut report = TbxImportReport::default();
+
+        for concept in &self.concepts {
+            let text = |lang| {
+                concept
+                    .lang_set(lang)
+                    .and_then(TbxLangSet::preferred_term)
+                    .map(|term| term.text.clone())
+            };
+            let (Some(hebrew), Some(russian)) = (text(Language::Hebrew), text(Language::Russian)) else {
+                report.skipped.push((concept.id.clone(), "חסר מונח בעברית או ברוסית".to_string()));
+                continue;
+            };
+            let english = text(Language::English);
+
+            let mut standards = Vec::new();
+            let mut context = String::new();
+            let mut notes = Vec::new();
+            for note in &concept.notes {
+                if let Some(standard) = note.strip_prefix("standard: ") {
+                    standards.push(standard.to_string());
+                } else if let Some(value) = note.strip_prefix("context: ") {
+                    context = value.to_string();
+                } else {
+                    notes.push(note.clone());
+                }
+            }
+            if let Some(definition) = concept.definition_in(Language::Hebrew) {
+                notes.insert(0, definition);
+            }
+
+            let existing = database.find_term(&hebrew, Language::Hebrew).cloned();
+            let mut incoming = technical_terms::Concept::new(
+                existing.as_ref().map_or(concept.id.as_str(), |existing| existing.id.as_str()),
+                concept.subject_field.as_deref().unwrap_or(technical_terms::GENERAL_DOMAIN),
+            );
+            incoming.context = context;
+            incoming.standards = standards;
+            incoming.notes = (!notes.is_empty()).then(|| notes.join("\n"));
+            for lang in Language::ALL {
+                let Some(set) = concept.lang_set(lang) else {
+                    continue;
+                };
+                // הצורה המועדפת ראשונה, והשאר לפי סדר הקובץ
+                let preferred = set.preferred_term();
+                let ordered = preferred
+                    .into_iter()
+                    .chain(set.terms.iter().filter(|term| Some(*term) != preferred));
+                for term in ordered {
+                    let mut form = technical_terms::ConceptTerm::new(lang, &term.text);
+                    if let Some(status) = term.usage_status.filter(|status| *status != UsageStatus::Preferred) {
+                        form = form.with_usage(&format!("{:?}", status).to_lowercase());
+                    }
+                    form.context = term.notes.iter().find_map(|note| note.strip_prefix("context: ")).map(str::to_string);
+                    incoming.terms.push(form);
+                }
+            }
+
+            if let Some(existing) = &existing {
+                let mut conflicts = Vec::new();
+                let existing_russian = existing.text_in(Language::Russian).unwrap_or_default();
+                if existing_russian != russian {
+                    conflicts.push((TbxConflictField::Target, existing_russian.to_string(), russian.clone()));
+                }
+                let existing_english = existing.text_in(Language::English);
+                if english.is_some() && existing_english != english.as_deref() {
+                    conflicts.push((
+                        TbxConflictField::English,
+                        existing_english.unwrap_or_default().to_string(),
+                        english.clone().unwrap_or_default(),
+                    ));
+                }
+                if conflicts.is_empty() {
+                    report.unchanged.push(hebrew);
+                    continue;
+                }
+                report.conflicts.extend(conflicts.into_iter().map(|(field, existing, incoming)| TbxConflict {
+                    concept_id: concept.id.clone(),
+                    source: hebrew.clone(),
+                    field,
+                    existing,
+                    incoming,
+                }));
+                if mode == TbxImportMode::KeepExisting {
+                    continue;
+                }
+                report.updated.push(hebrew);
+            } else {
+                report.added.push(hebrew);
+            }
+            database.add_concept(incoming);
+        }
+
+        report
+    }
+}
+
+fn compare_terms(concept_id: &str, existing: &TechnicalTerm, incoming: &TechnicalTerm) -> Vec<TbxConflict> {
+    let mut fields = vec![(TbxConflictField::Target, existing.target.clone(), incoming.target.clone())];
+    if incoming.english.is_some() {
+        fields.push((
+            TbxConflictField::English,
+            existing.english.clone().unwrap_or_default(),
+            incoming.english.clone().unwrap_or_default(),
+        ));
+    }
+    fields.push((TbxConflictField::Domain, existing.domain.clone(), incoming.domain.clone()));
+    fields.push((
+        TbxConflictField::UsageStatus,
+        status_to_tbx(existing.main_status(existing.target_lang).unwrap_or_default()).to_string(),
+        status_to_tbx(incoming.main_status(incoming.target_lang).unwrap_or_default()).to_string(),
+    ));
+
+    fields
+        .into_iter()
+        .filter(|(_, existing, incoming)| existing != incoming)
+        .map(|(field, existing_value, incoming_value)| TbxConflict {
+            concept_id: concept_id.to_string(),
+            source: incoming.source.clone(),
+            field,
+            existing: existing_value,
+            incoming: incoming_value,
+        })
+        .collect()
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>> {
+    Ok(match element.try_get_attribute(name)? {
+        Some(attribute) => Some(attribute.unescape_value()?.to_string()),
+        None => None,
+    })
+}
+
+fn status_from_tbx(value: &str) -> Option<UsageStatus> {
+    match value {
+        "preferredTerm-admn-sts" | "preferred" => Some(UsageStatus::Preferred),
+        "admittedTerm-admn-sts" | "admitted" => Some(UsageStatus::Admitted),
+        "deprecatedTerm-admn-sts" | "deprecated" => Some(UsageStatus::Deprecated),
+        "supersededTerm-admn-sts" | "superseded" => Some(UsageStatus::Superseded),
+        "forbidden" => Some(UsageStatus::Forbidden),
+        _ => None,
+    }
+}
+
+fn status_to_tbx(status: UsageStatus) -> &'static str {
+    match status {
+        UsageStatus::Preferred => "preferredTerm-admn-sts",
+        UsageStatus::Admitted => "admittedTerm-admn-sts",
+        UsageStatus::Deprecated => "deprecatedTerm-admn-sts",
+        UsageStatus::Superseded => "supersededTerm-admn-sts",
+        // ב-TBX-Basic אין מעמד "אסור"; הקרוב ביותר הוא מיושן
+        UsageStatus::Forbidden => "deprecatedTerm-admn-sts",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::technical_terms::create_initial_terms;
+
+    const SAMPLE_2008: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<martif type="TBX-Basic" xml:lang="he">
+  <text><body>
+    <termEntry id="sprinkler-head">
+      <descrip type="subjectField">fire_protection</descrip>
+      <langSet xml:lang="he">
+        <descrip type="definition">רכיב פיזור מים במערכת כיבוי</descrip>
+        <tig><term>ראש ספרינקלר</term><termNote type="administrativeStatus">preferredTerm-admn-sts</termNote></tig>
+        <tig><term>ממטיר</term><termNote type="administrativeStatus">deprecatedTerm-admn-sts</termNote></tig>
+      </langSet>
+      <langSet xml:lang="ru-RU">
+        <tig><term>спринклерный ороситель</term><termNote type="administrativeStatus">preferredTerm-admn-sts</termNote><note>NFPA 13</note></tig>
+        <tig><term>ороситель/спринклер</term></tig>
+      </langSet>
+      <langSet xml:lang="en">
+        <tig><term>Sprinkler Head</term></tig>
+      </langSet>
+    </termEntry>
+  </body></text>
+</martif>"#;
+
+    #[test]
+    fn test_parse_tbx_basic() {
+        let document = TbxDocument::parse(SAMPLE_2008).unwrap();
+        assert_eq!(document.concepts.len(), 1);
+
+        let (terms, skipped) = document.to_technical_terms(Language::Hebrew, Language::Russian);
+        assert!(skipped.is_empty());
+        let (id, term) = &terms[0];
+        assert_eq!(id, "sprinkler-head");
+        assert_eq!(term.target, "спринклерный ороситель");
+        assert_eq!(term.english.as_deref(), Some("Sprinkler Head"));
+        assert_eq!(term.domain, "fire_protection");
+        assert!(term.variants.contains(&TermVariant {
+            text: "ממטיר".to_string(),
+            lang: Language::Hebrew,
+            status: UsageStatus::Deprecated,
+        }));
+        assert!(term.variants.iter().any(|v| v.text == "ороситель/спринклер" && v.status == UsageStatus::Admitted));
+        assert_eq!(term.main_status(Language::Russian), Some(UsageStatus::Preferred));
+        assert_eq!(term.notes, vec!["NFPA 13".to_string()]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let document = TbxDocument::parse(SAMPLE_2008).unwrap();
+        let reparsed = TbxDocument::parse(&document.to_xml()).unwrap();
+        assert_eq!(reparsed.concepts, document.concepts);
+    }
+
+    #[test]
+    fn test_terms_database_conflicts() {
+        let mut database = create_initial_terms();
+        let document = TbxDocument::parse(SAMPLE_2008).unwrap();
+
+        let report = document.import_into_terms_database(&mut database, TbxImportMode::KeepExisting);
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].field, TbxConflictField::Target);
+        assert_eq!(
+            database.get_translation("ראש ספרינקלר", Language::Hebrew, Language::Russian).as_deref(),
+            Some("ороситель/спринклер")
+        );
+
+        let report = document.import_into_terms_database(&mut database, TbxImportMode::Overwrite);
+        assert_eq!(report.updated, vec!["ראש ספרינקלר".to_string()]);
+        assert_eq!(
+            database.get_translation("спринклерный ороситель", Language::Russian, Language::Hebrew).as_deref(),
+            Some("ראש ספרינקלר")
+        );
+        // הצורה הקודמת נשארת במושג כצורה נוספת
+        assert_eq!(
+            database.get_translation("ראש ספרינקלר", Language::Hebrew, Language::Russian).as_deref(),
+            Some("спринклерный ороситель")
+        );
+        let concept = database.find_term("ороситель/спринклер", Language::Russian).unwrap();
+        assert_eq!(concept.id, "sprinkler-head");
+        assert_eq!(concept.terms_in(Language::Hebrew).count(), 2);
+        assert_eq!(database.len(), 4);
+    }
+
+    #[test]
+    fn test_export_terms_database() {
+        let document = TbxDocument::from_terms_database(&create_initial_terms());
+        let xml = document.to_xml();
+        assert!(xml.contains(r#"<langSec xml:lang="ru">"#));
+
+        let reparsed = TbxDocument::parse(&xml).unwrap();
+        assert_eq!(reparsed.concepts.len(), document.concepts.len());
+    }
+}
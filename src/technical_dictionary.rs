@@ -1,19 +1,133 @@
-use std::sync::Arc;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use dashmap::DashMap;
 use serde::{Serialize, Deserialize};
-use anyhow::Result;
+use serde_json::Value;
+use anyhow::{Result, Context};
+use crate::language::{Language, Multilingual};
+use crate::morphology::utils::lemma_keys;
+use crate::dictionary_search::{SearchPage, TermQuery};
+use crate::term_usage::{TermUsageReport, UsageOutcome, UsageReportOptions, UsageStats};
+
+/// גרסת הסכמה הנוכחית של קובץ המילון
+pub const SCHEMA_VERSION: u32 = 4;
+
+// הגדרות האינדקס המטושטש
+const NGRAM_SIZE: usize = 3;
+const MIN_SIMILARITY: f64 = 0.35;
+const LEMMA_SIMILARITY: f64 = 0.9;
+const MAX_CANDIDATES: usize = 10;
+
+// מספר רישומי השימוש שנצברים בזיכרון לפני כתיבת הקובץ
+const USAGE_SAVE_INTERVAL: usize = 50;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TechnicalTerm {
     pub source: String,
     pub target: String,
+    #[serde(default = "default_source_lang")]
+    pub source_lang: Language,
+    #[serde(default = "default_target_lang")]
+    pub target_lang: Language,
+    // צורה אנגלית, משמשת גם כציר לתרגום עקיף
+    #[serde(default)]
+    pub english: Option<String>,
     pub domain: String,
     pub context: Vec<String>,
     pub usage_examples: Vec<String>,
-    pub synonyms: Vec<String>,
+    /// צורות חלופיות בכל שפה, עם מעמד השימוש של כל אחת.
+    /// צורה ראשית מופיעה כאן רק כשמעמדה אינו מועדף
+    #[serde(default)]
+    pub variants: Vec<TermVariant>,
+    #[serde(default)]
+    pub definition: Option<String>,
+    #[serde(default)]
+    pub notes: Vec<String>,
     pub metadata: TermMetadata,
 }
 
+fn default_source_lang() -> Language {
+    Language::Hebrew
+}
+
+fn default_target_lang() -> Language {
+    Language::Russian
+}
+
+/// צורה חלופית של מונח בשפה מסוימת
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TermVariant {
+    pub text: String,
+    pub lang: Language,
+    pub status: UsageStatus,
+}
+
+impl Multilingual for TechnicalTerm {
+    fn forms(&self) -> Vec<(Language, &str)> {
+        let mut forms = vec![(self.source_lang, self.source.as_str()), (self.target_lang, self.target.as_str())];
+        if let Some(english) = &self.english {
+            forms.push((Language::English, english.as_str()));
+        }
+        forms
+    }
+}
+
+impl TechnicalTerm {
+    /// מעמד השימוש של צורה בשפה נתונה; צורה ראשית מועדפת אלא אם נרשם לה מעמד אחר
+    pub fn status_of(&self, text: &str, lang: Language) -> Option<UsageStatus> {
+        let text = text.trim().to_lowercase();
+        let same = |candidate: &str| candidate.to_lowercase() == text;
+        if let Some(variant) = self.variants.iter().find(|variant| variant.lang == lang && same(&variant.text)) {
+            return Some(variant.status);
+        }
+        self.text_in(lang).filter(|main| same(main)).map(|_| UsageStatus::Preferred)
+    }
+
+    /// מעמד השימוש של הצורה הראשית בשפה נתונה
+    pub fn main_status(&self, lang: Language) -> Option<UsageStatus> {
+        self.status_of(self.text_in(lang)?, lang)
+    }
+
+    /// קביעת מעמד השימוש של צורה; צורה ראשית מועדפת אינה נשמרת בין הצורות החלופיות
+    pub fn set_status(&mut self, text: &str, lang: Language, status: UsageStatus) {
+        let implied = status == UsageStatus::Preferred && self.text_in(lang) == Some(text);
+        match self.variants.iter().position(|variant| variant.lang == lang && variant.text == text) {
+            Some(position) if implied => {
+                self.variants.remove(position);
+            }
+            Some(position) => self.variants[position].status = status,
+            None if implied => {}
+            None => self.variants.push(TermVariant { text: text.to_string(), lang, status }),
+        }
+    }
+
+    /// הצורה לתרגום בשפה נתונה: המועדפת, אחרת הראשונה שאינה מיושנת או אסורה
+    pub fn preferred_in(&self, lang: Language) -> Option<&str> {
+        let forms: Vec<(&str, UsageStatus)> = self
+            .text_in(lang)
+            .and_then(|main| Some((main, self.main_status(lang)?)))
+            .into_iter()
+            .chain(self.variants.iter().filter(|variant| variant.lang == lang).map(|variant| (variant.text.as_str(), variant.status)))
+            .collect();
+        forms
+            .iter()
+            .find(|(_, status)| *status == UsageStatus::Preferred)
+            .or_else(|| forms.iter().find(|(_, status)| !status.is_discouraged()))
+            .map(|(text, _)| *text)
+    }
+
+    /// צורות שאין להשתמש בהן בשפה נתונה
+    pub fn discouraged_variants(&self, lang: Language) -> impl Iterator<Item = &TermVariant> {
+        self.variants
+            .iter()
+            .filter(move |variant| variant.lang == lang && variant.status.is_discouraged())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TermMetadata {
     pub confidence_score: f64,
@@ -21,10 +135,55 @@ pub struct TermMetadata {
     pub usage_count: u64,
     pub verified: bool,
     pub source_references: Vec<String>,
+    /// קבלות ודחיות בסקירה; מתעדכן ב-`record_usage`
+    #[serde(default)]
+    pub usage: UsageStats,
+}
+
+/// מעמד השימוש של צורת מונח (ISO 704)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageStatus {
+    Preferred,
+    #[default]
+    Admitted,
+    Deprecated,
+    Superseded,
+    /// אסור לשימוש, למשל מונח שגוי או מונח של מתחרה
+    Forbidden,
+}
+
+impl UsageStatus {
+    /// האם יש להחליף את הצורה בצורה המועדפת
+    pub fn is_discouraged(&self) -> bool {
+        matches!(self, UsageStatus::Deprecated | UsageStatus::Superseded | UsageStatus::Forbidden)
+    }
+}
+
+/// צורה שאין להשתמש בה, יחד עם הצורה המועדפת שמחליפה אותה
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscouragedForm {
+    pub text: String,
+    pub status: UsageStatus,
+    /// `None` כשלמונח אין צורה מותרת בשפה
+    pub preferred: Option<String>,
+}
+
+/// מבנה קובץ המילון בדיסק
+#[derive(Debug, Serialize, Deserialize)]
+struct DictionaryFile {
+    schema_version: u32,
+    terms: Vec<TechnicalTerm>,
 }
 
 pub struct TechnicalDictionary {
     terms: Arc<DashMap<String, TechnicalTerm>>,
+    // קובץ המילון; `None` למילון בזיכרון בלבד
+    path: Option<PathBuf>,
+    // כתיבה אחת לקובץ בכל רגע נתון
+    save_lock: Mutex<()>,
+    // רישומי שימוש שעדיין לא נכתבו לקובץ
+    unsaved_usage: AtomicUsize,
     index: Arc<TermIndex>,
     learning_system: Arc<AdaptiveLearning>,
     validation_system: Arc<TermValidation>,
@@ -32,9 +191,29 @@ pub struct TechnicalDictionary {
 }
 
 impl TechnicalDictionary {
-    pub fn new() -> Self {
+    /// טעינת המילון מקובץ; קובץ שאינו קיים ייווצר בשמירה הראשונה
+    pub fn new(path: String) -> Result<Self> {
+        let mut dictionary = Self::in_memory();
+        let path = PathBuf::from(path);
+
+        if path.exists() {
+            for term in load_terms(&path)? {
+                dictionary.index.add_term(&term)?;
+                dictionary.terms.insert(term.source.clone(), term);
+            }
+        }
+
+        dictionary.path = Some(path);
+        Ok(dictionary)
+    }
+
+    /// מילון ללא קובץ, לבדיקות ולשימוש זמני
+    pub fn in_memory() -> Self {
         Self {
             terms: Arc::new(DashMap::new()),
+            path: None,
+            save_lock: Mutex::new(()),
+            unsaved_usage: AtomicUsize::new(0),
             index: Arc::new(TermIndex::new()),
             learning_system: Arc::new(AdaptiveLearning::new()),
             validation_system: Arc::new(TermValidation::new()),
@@ -42,7 +221,76 @@ impl TechnicalDictionary {
         }
     }
 
-    pub async fn add_term(&self, term: TechnicalTerm) -> Result<()> {
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// שמירת המילון לקובץ שלו בכתיבה אטומית
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let _guard = self.save_lock.lock().map_err(|_| anyhow::anyhow!("נעילת שמירת המילון נכשלה"))?;
+
+        self.unsaved_usage.store(0, Ordering::SeqCst);
+        let file = DictionaryFile {
+            schema_version: SCHEMA_VERSION,
+            terms: self.all_terms(),
+        };
+        write_atomic(path, serde_json::to_string_pretty(&file)?.as_bytes())
+    }
+
+    pub fn get_term(&self, source: &str) -> Option<TechnicalTerm> {
+        self.terms.get(source).map(|term| term.value().clone())
+    }
+
+    /// כל המונחים, ממוינים לפי מונח המקור
+    pub fn all_terms(&self) -> Vec<TechnicalTerm> {
+        let mut terms: Vec<TechnicalTerm> = self.terms.iter().map(|entry| entry.value().clone()).collect();
+        terms.sort_by(|a, b| a.source.cmp(&b.source));
+        terms
+    }
+
+    /// חיפוש לפי תחילית, תת-מחרוזת או תבנית, עם מסננים, מיון ודפדוף
+    pub fn search(&self, query: &TermQuery) -> SearchPage {
+        query.apply(self.all_terms())
+    }
+
+    /// בדיקת תקינות של כל המונחים לפני שינוי כלשהו במילון
+    pub(crate) fn validate_terms(&self, terms: &[TechnicalTerm]) -> Result<()> {
+        terms.iter().try_for_each(|term| self.validation_system.validate_term(term))
+    }
+
+    /// הוספה או החלפה של מונחים רבים ושמירה אחת בסופה, לייבוא. מונח לא תקין אחד
+    /// מבטל את כל ההוספה
+    pub(crate) fn insert_terms(&self, terms: Vec<TechnicalTerm>) -> Result<()> {
+        self.validate_terms(&terms)?;
+        for term in terms {
+            self.index.add_term(&term)?;
+            self.terms.insert(term.source.clone(), term);
+        }
+        self.save()
+    }
+
+    /// מחיקת מונח ושמירת המילון
+    pub(crate) fn remove_term(&self, source: &str) -> Result<Option<TechnicalTerm>> {
+        let removed = self.terms.remove(source).map(|(_, term)| term);
+        if removed.is_some() {
+            self.index.remove_term(source);
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
+    pub(crate) async fn add_term(&self, term: TechnicalTerm) -> Result<()> {
         // וידוא תקינות
         self.validation_system.validate_term(&term)?;
         
@@ -55,20 +303,80 @@ impl TechnicalDictionary {
         // עדכון מערכת הלמידה
         self.learning_system.process_new_term(&term).await?;
         
+        // שמירה כדי שהשינוי ישרוד הפעלה מחדש
+        self.save()?;
+        
         Ok(())
     }
 
-    pub async fn find_term(&self, query: &str, context: Option<&str>) -> Result<Vec<TechnicalTerm>> {
-        let mut results = Vec::new();
-        
-        // חיפוש מדויק
-        if let Some(term) = self.terms.get(query) {
-            results.push(term.clone());
-        }
-        
-        // חיפוש דומים
-        let similar_terms = self.index.find_similar(query)?;
-        results.extend(similar_terms);
+    /// תרגום ישיר של מונח בין שתי שפות, בכל כיוון שנשמר במילון
+    pub fn lookup(&self, text: &str, source: Language, target: Language) -> Option<String> {
+        // גם צורה חלופית במקור מתורגמת תמיד לצורה המועדפת ביעד; כשכמה מונחים חולקים את
+        // הצורה, מונח עם צורה מועדפת קודם, ובתוך כל קבוצה לפי סדר המקור
+        let text = text.trim();
+        self.terms
+            .iter()
+            .filter_map(|entry| {
+                let term = entry.value();
+                term.status_of(text, source)?;
+                let form = term.preferred_in(target)?;
+                let preferred = term.status_of(form, target) == Some(UsageStatus::Preferred);
+                Some((!preferred, term.source.clone(), form.to_string()))
+            })
+            .min()
+            .map(|(_, _, form)| form)
+    }
+
+    /// כל התרגומים של צורה לפי תחום המונח, לבחירת משמעות של מונח רב-משמעי
+    pub fn domain_translations(&self, text: &str, source: Language, target: Language) -> Vec<(String, String)> {
+        let text = text.trim();
+        let mut translations: Vec<(String, String)> = self.terms
+            .iter()
+            .filter(|entry| entry.value().status_of(text, source).is_some())
+            .filter_map(|entry| {
+                let term = entry.value();
+                Some((term.preferred_in(target)?.to_string(), term.domain.clone()))
+            })
+            .collect();
+        translations.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        translations.dedup();
+        translations
+    }
+
+    /// כל הצורות המיושנות והאסורות בשפה נתונה, לבקרת איכות
+    pub fn discouraged_forms(&self, lang: Language) -> Vec<DiscouragedForm> {
+        let mut forms: Vec<DiscouragedForm> = self.terms
+            .iter()
+            .flat_map(|entry| {
+                let term = entry.value();
+                let preferred = term.preferred_in(lang).map(str::to_string);
+                term.discouraged_variants(lang)
+                    .map(|variant| DiscouragedForm {
+                        text: variant.text.clone(),
+                        status: variant.status,
+                        preferred: preferred.clone(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        forms.sort_by(|a, b| a.text.cmp(&b.text));
+        forms
+    }
+
+    /// חיפוש מונחים קרובים: התאמה מדויקת, צורות נטויות ושגיאות כתיב, מדורגים לפי דמיון
+    pub async fn find_term(&self, query: &str, context: Option<&str>) -> Result<Vec<TermCandidate>> {
+        let mut results: Vec<TermCandidate> = self.index
+            .find_similar(query)?
+            .into_iter()
+            .filter_map(|found| {
+                self.terms.get(&found.key).map(|term| TermCandidate {
+                    term: term.value().clone(),
+                    similarity: found.similarity,
+                    matched_form: found.form,
+                    kind: found.kind,
+                })
+            })
+            .collect();
         
         // סינון לפי הקשר
         if let Some(context) = context {
@@ -76,7 +384,7 @@ impl TechnicalDictionary {
         }
         
         // מיון לפי רלוונטיות
-        self.sort_by_relevance(&mut results, query)?;
+        self.sort_by_relevance(&mut results)?;
         
         Ok(results)
     }
@@ -94,36 +402,76 @@ impl TechnicalDictionary {
             
             // עדכון מערכת הלמידה
             self.learning_system.process_term_update(term.value()).await?;
+        } else {
+            return Ok(());
         }
         
+        self.save()?;
         Ok(())
     }
 
-    pub async fn learn_from_usage(&self, text: &str, translation: &str) -> Result<()> {
-        // זיהוי מונחים בטקסט
-        let terms = self.identify_terms_in_text(text)?;
-        
-        // ניתוח הקשר
-        let context = self.context_analyzer.analyze(text)?;
-        
-        // עדכון סטטיסטיקות שימוש
-        for term in terms {
-            self.update_usage_statistics(&term, &context).await?;
+    /// עדכון סטטיסטיקת השימוש לפי תרגום שנסקר: `translation` היא הגרסה הסופית אחרי הסקירה.
+    /// מונח שצורת המקור שלו מופיעה בטקסט נחשב מקובל אם אחת מצורות היעד המותרות נשארה בתרגום.
+    pub(crate) fn learn_from_usage(&self, text: &str, translation: &str) -> Result<()> {
+        // השוואה לפי למות, כדי שגם צורות נטויות ייחשבו
+        let text = word_lemma_keys(text);
+        let translation = word_lemma_keys(translation);
+        let now = chrono::Utc::now();
+        let mut recorded = 0;
+
+        for mut entry in self.terms.iter_mut() {
+            let term = entry.value_mut();
+            let (source_lang, target_lang) = (term.source_lang, term.target_lang);
+            let mut sources = std::iter::once(term.source.as_str()).chain(
+                term.variants.iter().filter(|v| v.lang == source_lang).map(|v| v.text.as_str()),
+            );
+            if !sources.any(|form| mentions(&text, form)) {
+                continue;
+            }
+            let kept = std::iter::once(term.target.as_str())
+                .chain(
+                    term.variants
+                        .iter()
+                        .filter(|v| v.lang == target_lang && !v.status.is_discouraged())
+                        .map(|v| v.text.as_str()),
+                )
+                .any(|form| mentions(&translation, form));
+            let outcome = if kept { UsageOutcome::Accepted } else { UsageOutcome::Rejected };
+            term.metadata.record_usage(outcome, now);
+            recorded += 1;
+        }
+
+        self.note_usage(recorded)
+    }
+
+    /// כתיבת רישומי השימוש שטרם נשמרו
+    pub fn flush_usage(&self) -> Result<()> {
+        if self.unsaved_usage.load(Ordering::SeqCst) > 0 {
+            self.save()?;
         }
-        
-        // למידה מהתרגום
-        self.learning_system.learn_from_translation(text, translation, &terms).await?;
-        
         Ok(())
     }
 
-    fn filter_by_context(&self, terms: &[TechnicalTerm], context: &str) -> Result<Vec<TechnicalTerm>> {
+    // רישומי שימוש נכתבים במנות, ולא בכל רישום
+    fn note_usage(&self, count: usize) -> Result<()> {
+        if count > 0 && self.unsaved_usage.fetch_add(count, Ordering::SeqCst) + count >= USAGE_SAVE_INTERVAL {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// מונחים שדורשים בדיקה: ללא שימוש, נדחים לעתים קרובות, או לא מאומתים ובשימוש רב
+    pub fn usage_report(&self, options: &UsageReportOptions) -> TermUsageReport {
+        TermUsageReport::build(&self.all_terms(), options, chrono::Utc::now())
+    }
+
+    fn filter_by_context(&self, candidates: &[TermCandidate], context: &str) -> Result<Vec<TermCandidate>> {
         let context_vector = self.context_analyzer.vectorize(context)?;
         
-        let filtered: Vec<_> = terms
+        let filtered: Vec<_> = candidates
             .iter()
-            .filter(|term| {
-                let term_context = term.context.join(" ");
+            .filter(|candidate| {
+                let term_context = candidate.term.context.join(" ");
                 let term_vector = self.context_analyzer.vectorize(&term_context).unwrap_or_default();
                 self.context_analyzer.calculate_similarity(&context_vector, &term_vector) > 0.7
             })
@@ -133,68 +481,645 @@ impl TechnicalDictionary {
         Ok(filtered)
     }
 
-    fn sort_by_relevance(&self, terms: &mut [TechnicalTerm], query: &str) -> Result<()> {
-        terms.sort_by(|a, b| {
-            let score_a = self.calculate_relevance_score(a, query);
-            let score_b = self.calculate_relevance_score(b, query);
+    fn sort_by_relevance(&self, candidates: &mut [TermCandidate]) -> Result<()> {
+        candidates.sort_by(|a, b| {
+            let score_a = self.calculate_relevance_score(a);
+            let score_b = self.calculate_relevance_score(b);
             score_b.partial_cmp(&score_a).unwrap_or(std::cmp::Ordering::Equal)
         });
         
         Ok(())
     }
 
-    fn calculate_relevance_score(&self, term: &TechnicalTerm, query: &str) -> f64 {
-        let mut score = 0.0;
+    fn calculate_relevance_score(&self, candidate: &TermCandidate) -> f64 {
+        let term = &candidate.term;
         
-        // התאמה טקסטואלית
-        score += self.calculate_text_similarity(&term.source, query);
+        // התאמה טקסטואלית היא הגורם העיקרי; השאר מכריעים בין מועמדים קרובים
+        let mut score = candidate.similarity;
         
-        // ציון אמון
-        score += term.metadata.confidence_score;
+        // ציון אמון, לאחר דעיכה מאז השימוש האחרון
+        score += term.metadata.confidence_at(chrono::Utc::now()) / 10.0;
         
         // תדירות שימוש
-        score += (term.metadata.usage_count as f64).log10() / 10.0;
+        score += ((term.metadata.usage_count + 1) as f64).log10() / 100.0;
         
         // אימות
         if term.metadata.verified {
-            score += 0.3;
+            score += 0.03;
         }
         
         score
     }
 }
 
+// רישומי שימוש שלא הגיעו למנה מלאה נכתבים כשהמילון נסגר
+impl Drop for TechnicalDictionary {
+    fn drop(&mut self) {
+        let _ = self.flush_usage();
+    }
+}
+
+/// קריאת קובץ מילון בכל גרסת סכמה נתמכת.
+/// קובץ בגרסה ישנה מומר, ועותק של המקור נשמר לצדו לפני שהמילון נכתב מחדש.
+fn load_terms(path: &Path) -> Result<Vec<TechnicalTerm>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("קריאת המילון הטכני נכשלה: {}", path.display()))?;
+    let value: Value = serde_json::from_str(&content)
+        .with_context(|| format!("פענוח המילון הטכני נכשל: {}", path.display()))?;
+
+    let version = schema_version(&value);
+    if version > SCHEMA_VERSION {
+        anyhow::bail!(
+            "גרסת הסכמה של המילון ({}) חדשה מהגרסה הנתמכת ({})",
+            version,
+            SCHEMA_VERSION
+        );
+    }
+
+    let migrated = migrate(value)?;
+    let file: DictionaryFile = serde_json::from_value(migrated)
+        .context("מבנה המילון הטכני אינו תקין")?;
+
+    if version < SCHEMA_VERSION {
+        let backup = path.with_extension(format!("v{}.bak", version));
+        fs::copy(path, &backup)
+            .with_context(|| format!("גיבוי המילון לפני המרה נכשל: {}", backup.display()))?;
+        write_atomic(path, serde_json::to_string_pretty(&file)?.as_bytes())?;
+    }
+
+    Ok(file.terms)
+}
+
+// קבצים ללא שדה גרסה הם מערך מונחים פשוט, מלפני הוספת הגרסאות
+fn schema_version(value: &Value) -> u32 {
+    value
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .map(|v| v as u32)
+        .unwrap_or(0)
+}
+
+/// המרה הדרגתית של קובץ מילון לגרסת הסכמה הנוכחית
+fn migrate(mut value: Value) -> Result<Value> {
+    loop {
+        let version = schema_version(&value);
+        value = match version {
+            // גרסה 0: מערך מונחים ללא עטיפה
+            0 => {
+                let terms = match value {
+                    Value::Array(terms) => terms,
+                    Value::Object(map) => map.into_iter().map(|(_, term)| term).collect(),
+                    _ => anyhow::bail!("מבנה לא מוכר לקובץ מילון ללא גרסה"),
+                };
+                serde_json::json!({ "schema_version": 1, "terms": terms })
+            }
+            // גרסה 1: מונחים עברית-רוסית בלבד, ללא שדות שפה ועם מטא-נתונים חלקיים
+            1 => {
+                let mut value = value;
+                if let Some(terms) = value.get_mut("terms").and_then(Value::as_array_mut) {
+                    for term in terms.iter_mut().filter_map(Value::as_object_mut) {
+                        term.entry("source_lang").or_insert_with(|| "he".into());
+                        term.entry("target_lang").or_insert_with(|| "ru".into());
+                        for field in ["context", "usage_examples", "synonyms"] {
+                            term.entry(field).or_insert_with(|| Value::Array(Vec::new()));
+                        }
+                        term.entry("domain").or_insert_with(|| "general".into());
+                        let metadata = term
+                            .entry("metadata")
+                            .or_insert_with(|| Value::Object(Default::default()));
+                        if let Some(metadata) = metadata.as_object_mut() {
+                            metadata.entry("confidence_score").or_insert_with(|| 0.5.into());
+                            metadata
+                                .entry("last_updated")
+                                .or_insert_with(|| chrono::Utc::now().to_rfc3339().into());
+                            metadata.entry("usage_count").or_insert_with(|| 0.into());
+                            metadata.entry("verified").or_insert_with(|| false.into());
+                            metadata
+                                .entry("source_references")
+                                .or_insert_with(|| Value::Array(Vec::new()));
+                        }
+                    }
+                }
+                value["schema_version"] = 2.into();
+                value
+            }
+            // גרסה 2: מילים נרדפות כרשימה שטוחה; עוברות לצורות חלופיות מותרות בשפת המקור
+            2 => {
+                let mut value = value;
+                if let Some(terms) = value.get_mut("terms").and_then(Value::as_array_mut) {
+                    for term in terms.iter_mut().filter_map(Value::as_object_mut) {
+                        synonyms_to_variants(term);
+                    }
+                }
+                value["schema_version"] = 3.into();
+                value
+            }
+            // גרסה 3: מעמד הצורה הראשית ביעד נשמר במטא-נתונים, ולצד החלופות נותרה רשימת מילים נרדפות ריקה
+            3 => {
+                let mut value = value;
+                if let Some(terms) = value.get_mut("terms").and_then(Value::as_array_mut) {
+                    for term in terms.iter_mut().filter_map(Value::as_object_mut) {
+                        synonyms_to_variants(term);
+                        let status = term
+                            .get_mut("metadata")
+                            .and_then(Value::as_object_mut)
+                            .and_then(|metadata| metadata.remove("usage_status"))
+                            .filter(|status| *status != "preferred");
+                        if let (Some(status), Some(target)) = (status, term.get("target").cloned()) {
+                            let target_lang = term.get("target_lang").cloned().unwrap_or_else(|| "ru".into());
+                            if let Some(variants) = term.get_mut("variants").and_then(Value::as_array_mut) {
+                                variants.push(serde_json::json!({
+                                    "text": target,
+                                    "lang": target_lang,
+                                    "status": status,
+                                }));
+                            }
+                        }
+                    }
+                }
+                value["schema_version"] = 4.into();
+                value
+            }
+            _ => return Ok(value),
+        };
+    }
+}
+
+// העברת המילים הנרדפות לצורות חלופיות מותרות בשפת המקור, והסרת השדה
+fn synonyms_to_variants(term: &mut serde_json::Map<String, Value>) {
+    let source_lang = term.get("source_lang").cloned().unwrap_or_else(|| "he".into());
+    let synonyms = term
+        .remove("synonyms")
+        .and_then(|synonyms| synonyms.as_array().cloned())
+        .unwrap_or_default();
+    let variants = term
+        .entry("variants")
+        .or_insert_with(|| Value::Array(Vec::new()));
+    if let Some(variants) = variants.as_array_mut() {
+        variants.extend(synonyms.into_iter().map(|text| serde_json::json!({
+            "text": text,
+            "lang": source_lang,
+            "status": "admitted",
+        })));
+    }
+}
+
+/// כתיבה לקובץ זמני באותה תיקייה ואז החלפה, כך שהקובץ לעולם אינו נשאר כתוב למחצה
+fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    fs::create_dir_all(dir)?;
+    let temp = path.with_extension("json.tmp");
+    {
+        let mut file = fs::File::create(&temp)
+            .with_context(|| format!("יצירת קובץ זמני נכשלה: {}", temp.display()))?;
+        file.write_all(content)?;
+        file.sync_all()?;
+    }
+    fs::rename(&temp, path)
+        .with_context(|| format!("החלפת קובץ המילון נכשלה: {}", path.display()))?;
+
+    // ההחלפה עצמה נשמרת בדיסק רק אחרי סנכרון התיקייה
+    #[cfg(unix)]
+    fs::File::open(dir)
+        .and_then(|dir| dir.sync_all())
+        .with_context(|| format!("סנכרון תיקיית המילון נכשל: {}", dir.display()))?;
+    Ok(())
+}
+
+/// אופן ההתאמה של מועמד לשאילתה
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MatchKind {
+    Exact,
+    /// צורה נטויה של אותו למה
+    Lemma,
+    /// דמיון תווים, למשל שגיאת כתיב
+    Fuzzy,
+}
+
+/// מונח מועמד עם ציון הדמיון והצורה שהותאמה
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TermCandidate {
+    pub term: TechnicalTerm,
+    pub similarity: f64,
+    pub matched_form: String,
+    pub kind: MatchKind,
+}
+
+/// התאמה באינדקס: מפתח המונח (מונח המקור) והצורה שהותאמה
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexMatch {
+    pub key: String,
+    pub form: String,
+    pub similarity: f64,
+    pub kind: MatchKind,
+}
+
+#[derive(Debug, Clone)]
+struct IndexedForm {
+    text: String,
+    normalized: String,
+    grams: HashSet<String>,
+    lemmas: Vec<String>,
+}
+
+impl IndexedForm {
+    fn new(text: &str) -> Self {
+        let normalized = normalize_form(text);
+        Self {
+            text: text.to_string(),
+            grams: char_ngrams(&normalized),
+            lemmas: phrase_lemma_keys(&normalized),
+            normalized,
+        }
+    }
+}
+
+/// אינדקס מטושטש ללא ספריות חיצוניות: n-גרמים של תווים ומפתחות למה.
+/// כל צורות המונח (מקור, יעד, אנגלית ומילים נרדפות) מפנות למונח המקור.
 pub struct TermIndex {
-    embeddings: HashMap<String, Vec<f32>>,
-    index: Arc<faiss::Index>,
+    forms: DashMap<String, Vec<IndexedForm>>,
+    ngrams: DashMap<String, HashSet<String>>,
+    lemmas: DashMap<String, HashSet<String>>,
 }
 
 impl TermIndex {
     pub fn new() -> Self {
         Self {
-            embeddings: HashMap::new(),
-            index: Arc::new(faiss::Index::new(384, "Flat")),
+            forms: DashMap::new(),
+            ngrams: DashMap::new(),
+            lemmas: DashMap::new(),
         }
     }
 
+    pub fn len(&self) -> usize {
+        self.forms.len()
+    }
+
     pub fn add_term(&self, term: &TechnicalTerm) -> Result<()> {
-        let embedding = self.compute_embedding(&term.source)?;
-        self.embeddings.insert(term.source.clone(), embedding.clone());
-        self.index.add(&embedding)?;
+        self.remove_term(&term.source);
+
+        let mut texts: Vec<&str> = vec![term.source.as_str(), term.target.as_str()];
+        texts.extend(term.english.as_deref());
+        texts.extend(term.variants.iter().map(|variant| variant.text.as_str()));
+
+        let mut forms: Vec<IndexedForm> = Vec::new();
+        for text in texts.into_iter().filter(|t| !t.trim().is_empty()) {
+            let form = IndexedForm::new(text);
+            if forms.iter().any(|f| f.normalized == form.normalized) {
+                continue;
+            }
+            for gram in &form.grams {
+                self.ngrams.entry(gram.clone()).or_default().insert(term.source.clone());
+            }
+            for lemma in &form.lemmas {
+                self.lemmas.entry(lemma.clone()).or_default().insert(term.source.clone());
+            }
+            forms.push(form);
+        }
+
+        self.forms.insert(term.source.clone(), forms);
         Ok(())
     }
 
-    pub fn find_similar(&self, query: &str) -> Result<Vec<TechnicalTerm>> {
-        let query_embedding = self.compute_embedding(query)?;
-        let (distances, indices) = self.index.search(&query_embedding, 10)?;
-        
-        let mut results = Vec::new();
-        for (distance, index) in distances.iter().zip(indices.iter()) {
-            if let Some(term) = self.get_term_by_index(*index) {
-                results.push(term.clone());
+    pub fn update_term(&self, term: &TechnicalTerm) -> Result<()> {
+        self.add_term(term)
+    }
+
+    pub fn remove_term(&self, key: &str) {
+        let Some((_, forms)) = self.forms.remove(key) else {
+            return;
+        };
+        for form in forms {
+            for gram in &form.grams {
+                if let Some(mut keys) = self.ngrams.get_mut(gram) {
+                    keys.remove(key);
+                }
+            }
+            for lemma in &form.lemmas {
+                if let Some(mut keys) = self.lemmas.get_mut(lemma) {
+                    keys.remove(key);
+                }
             }
         }
-        
-        Ok(results)
     }
-} 
\ No newline at end of file
+
+    /// מועמדים מדורגים לפי דמיון, מהגבוה לנמוך
+    pub fn find_similar(&self, query: &str) -> Result<Vec<IndexMatch>> {
+        let query = IndexedForm::new(query);
+        if query.normalized.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // מועמדים: מונחים שחולקים n-גרם או מפתח למה עם השאילתה
+        let mut keys: HashSet<String> = HashSet::new();
+        for gram in &query.grams {
+            if let Some(found) = self.ngrams.get(gram) {
+                keys.extend(found.iter().cloned());
+            }
+        }
+        for lemma in &query.lemmas {
+            if let Some(found) = self.lemmas.get(lemma) {
+                keys.extend(found.iter().cloned());
+            }
+        }
+
+        let mut matches: Vec<IndexMatch> = keys
+            .into_iter()
+            .filter_map(|key| {
+                let forms = self.forms.get(&key)?;
+                let best = forms
+                    .iter()
+                    .map(|form| score_form(&query, form))
+                    .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))?;
+                let (form, similarity, kind) = best;
+                Some(IndexMatch {
+                    key: key.clone(),
+                    form: form.text.clone(),
+                    similarity,
+                    kind,
+                })
+            })
+            .filter(|found| found.similarity >= MIN_SIMILARITY)
+            .collect();
+
+        matches.sort_by(|a, b| {
+            b.similarity
+                .partial_cmp(&a.similarity)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.key.cmp(&b.key))
+        });
+        matches.truncate(MAX_CANDIDATES);
+        Ok(matches)
+    }
+}
+
+fn score_form<'a>(query: &IndexedForm, form: &'a IndexedForm) -> (&'a IndexedForm, f64, MatchKind) {
+    if query.normalized == form.normalized {
+        return (form, 1.0, MatchKind::Exact);
+    }
+    let similarity = dice(&query.grams, &form.grams);
+    if query.lemmas.iter().any(|lemma| form.lemmas.contains(lemma)) {
+        return (form, similarity.max(LEMMA_SIMILARITY), MatchKind::Lemma);
+    }
+    (form, similarity, MatchKind::Fuzzy)
+}
+
+fn normalize_form(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// n-גרמים של תווים, עם ריפוד כדי שתחילת המילה וסופה ישקלו
+fn char_ngrams(text: &str) -> HashSet<String> {
+    let padded: Vec<char> = format!(" {} ", text).chars().collect();
+    if padded.len() < NGRAM_SIZE {
+        return std::iter::once(padded.iter().collect()).collect();
+    }
+    padded.windows(NGRAM_SIZE).map(|window| window.iter().collect()).collect()
+}
+
+/// מפתחות הלמה של כל המילים בטקסט
+fn word_lemma_keys(text: &str) -> HashSet<String> {
+    text.split_whitespace().flat_map(lemma_keys).collect()
+}
+
+/// האם כל מילות הצורה מופיעות בטקסט, בצורה כלשהי
+fn mentions(text_keys: &HashSet<String>, form: &str) -> bool {
+    let mut words = form.split_whitespace().peekable();
+    words.peek().is_some() && words.all(|word| lemma_keys(word).iter().any(|key| text_keys.contains(key)))
+}
+
+/// מפתחות למה של ביטוי: צירוף מפתחות המילים, עם מספר מוגבל של צירופים
+fn phrase_lemma_keys(text: &str) -> Vec<String> {
+    const MAX_KEYS: usize = 8;
+    let mut keys = vec![String::new()];
+
+    for word in text.split_whitespace() {
+        let word_keys = lemma_keys(word);
+        if word_keys.is_empty() {
+            continue;
+        }
+        keys = keys
+            .iter()
+            .flat_map(|prefix| {
+                word_keys.iter().map(move |key| {
+                    if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{} {}", prefix, key)
+                    }
+                })
+            })
+            .take(MAX_KEYS)
+            .collect();
+    }
+
+    keys.retain(|key| !key.is_empty());
+    keys
+}
+
+fn dice(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let shared = a.intersection(b).count();
+    2.0 * shared as f64 / (a.len() + b.len()) as f64
+}
+
+#[cfg(test)]
+impl TechnicalTerm {
+    /// מונח עברי-רוסי לבדיקות: לא מאומת, ללא הקשר וללא היסטוריית שימוש
+    pub(crate) fn for_test(source: &str, target: &str, domain: &str) -> Self {
+        TechnicalTerm {
+            source: source.to_string(),
+            target: target.to_string(),
+            source_lang: Language::Hebrew,
+            target_lang: Language::Russian,
+            english: None,
+            domain: domain.to_string(),
+            context: Vec::new(),
+            usage_examples: Vec::new(),
+            variants: Vec::new(),
+            definition: None,
+            notes: Vec::new(),
+            metadata: TermMetadata {
+                confidence_score: 0.9,
+                last_updated: chrono::Utc::now(),
+                usage_count: 0,
+                verified: false,
+                source_references: Vec::new(),
+                usage: UsageStats::default(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // התיקייה נמחקת כשהערך הראשון יוצא מתחום
+    fn temp_path() -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("technical_dictionary.json");
+        (dir, path)
+    }
+
+    fn sample_term(source: &str, target: &str, english: Option<&str>) -> TechnicalTerm {
+        let mut term = TechnicalTerm::for_test(source, target, "general");
+        term.english = english.map(str::to_string);
+        term.metadata.verified = true;
+        term
+    }
+
+    #[test]
+    fn test_migrate_legacy_file() {
+        let (_dir, path) = temp_path();
+        fs::write(&path, r#"[{"source": "מגוף", "target": "задвижка"}]"#).unwrap();
+
+        let dictionary = TechnicalDictionary::new(path.to_string_lossy().to_string()).unwrap();
+        assert_eq!(
+            dictionary.lookup("מגוף", Language::Hebrew, Language::Russian).as_deref(),
+            Some("задвижка")
+        );
+
+        // הקובץ נכתב מחדש בגרסה הנוכחית, והמקור נשמר בגיבוי
+        let saved: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(schema_version(&saved), SCHEMA_VERSION);
+        assert!(path.with_extension("v0.bak").exists());
+    }
+
+    #[test]
+    fn test_migrate_status_to_variants() {
+        let (_dir, path) = temp_path();
+        fs::write(&path, r#"{"schema_version": 3, "terms": [{
+            "source": "מגוף", "target": "задвижка", "source_lang": "he", "target_lang": "ru",
+            "domain": "hydraulics", "context": [], "usage_examples": [], "synonyms": ["ברז חוסם"],
+            "variants": [],
+            "metadata": {"confidence_score": 0.5, "last_updated": "2024-01-01T00:00:00Z", "usage_count": 0,
+                         "verified": false, "source_references": [], "usage_status": "deprecated"}
+        }]}"#).unwrap();
+
+        let dictionary = TechnicalDictionary::new(path.to_string_lossy().to_string()).unwrap();
+        let term = dictionary.get_term("מגוף").unwrap();
+        assert_eq!(term.main_status(Language::Russian), Some(UsageStatus::Deprecated));
+        assert_eq!(term.main_status(Language::Hebrew), Some(UsageStatus::Preferred));
+        assert_eq!(term.status_of("ברז חוסם", Language::Hebrew), Some(UsageStatus::Admitted));
+
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(!saved.contains("synonyms") && !saved.contains("usage_status"));
+    }
+
+    #[test]
+    fn test_save_and_reload() {
+        let (_dir, path) = temp_path();
+        let dictionary = TechnicalDictionary::new(path.to_string_lossy().to_string()).unwrap();
+        assert!(dictionary.is_empty());
+
+        dictionary.terms.insert("ברז".to_string(), sample_term("ברז", "кран", Some("faucet")));
+        dictionary.save().unwrap();
+        assert!(!path.with_extension("json.tmp").exists());
+
+        let reloaded = TechnicalDictionary::new(path.to_string_lossy().to_string()).unwrap();
+        assert_eq!(reloaded.len(), 1);
+        assert_eq!(
+            reloaded.lookup("ברז", Language::Hebrew, Language::English).as_deref(),
+            Some("faucet")
+        );
+    }
+
+    #[test]
+    fn test_newer_schema_rejected() {
+        let (_dir, path) = temp_path();
+        fs::write(&path, r#"{"schema_version": 99, "terms": []}"#).unwrap();
+        assert!(TechnicalDictionary::new(path.to_string_lossy().to_string()).is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_index() {
+        let index = TermIndex::new();
+        index.add_term(&sample_term("ראש ספרינקלר", "ороситель", Some("Sprinkler Head"))).unwrap();
+        index.add_term(&sample_term("מגוף", "задвижка", Some("Gate Valve"))).unwrap();
+        index.add_term(&sample_term("צנרת אספקה", "питающий трубопровод", None)).unwrap();
+
+        // שגיאת כתיב
+        let matches = index.find_similar("ראש ספרינקלער").unwrap();
+        assert_eq!(matches[0].key, "ראש ספרינקלר");
+        assert_eq!(matches[0].kind, MatchKind::Fuzzy);
+
+        // צורה נטויה ותחילית
+        let matches = index.find_similar("המגופים").unwrap();
+        assert_eq!(matches[0].key, "מגוף");
+        assert_eq!(matches[0].kind, MatchKind::Lemma);
+
+        let matches = index.find_similar("питающего трубопровода").unwrap();
+        assert_eq!(matches[0].key, "צנרת אספקה");
+
+        index.remove_term("מגוף");
+        assert!(index.find_similar("מגוף").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_variant_lookup_emits_preferred() {
+        let dictionary = TechnicalDictionary::in_memory();
+        let mut term = sample_term("ראש ספרינקלר", "ороситель", None);
+        term.variants = vec![
+            TermVariant { text: "спринклер".to_string(), lang: Language::Russian, status: UsageStatus::Admitted },
+            TermVariant { text: "ממטיר".to_string(), lang: Language::Hebrew, status: UsageStatus::Forbidden },
+        ];
+        dictionary.terms.insert(term.source.clone(), term);
+
+        assert_eq!(
+            dictionary.lookup("ממטיר", Language::Hebrew, Language::Russian).as_deref(),
+            Some("ороситель")
+        );
+        assert_eq!(
+            dictionary.lookup("спринклер", Language::Russian, Language::Hebrew).as_deref(),
+            Some("ראש ספרינקלר")
+        );
+
+        let forms = dictionary.discouraged_forms(Language::Hebrew);
+        assert_eq!(forms.len(), 1);
+        assert_eq!(forms[0].preferred.as_deref(), Some("ראש ספרינקלר"));
+        assert!(dictionary.discouraged_forms(Language::Russian).is_empty());
+    }
+
+    #[test]
+    fn test_discouraged_main_form_not_emitted() {
+        let dictionary = TechnicalDictionary::in_memory();
+        let mut term = sample_term("מגוף", "задвижка", None);
+        term.set_status("задвижка", Language::Russian, UsageStatus::Deprecated);
+        term.set_status("шибер", Language::Russian, UsageStatus::Preferred);
+        let mut forbidden = sample_term("ברז", "кран", None);
+        forbidden.set_status("кран", Language::Russian, UsageStatus::Forbidden);
+        dictionary.insert_terms(vec![term, forbidden]).unwrap();
+
+        assert_eq!(dictionary.lookup("מגוף", Language::Hebrew, Language::Russian).as_deref(), Some("шибер"));
+        assert_eq!(dictionary.lookup("ברז", Language::Hebrew, Language::Russian), None);
+
+        let forms = dictionary.discouraged_forms(Language::Russian);
+        assert_eq!(forms.len(), 2);
+        assert_eq!((forms[0].text.as_str(), forms[0].preferred.as_deref()), ("задвижка", Some("шибер")));
+        assert_eq!((forms[1].text.as_str(), forms[1].preferred.as_deref()), ("кран", None));
+    }
+
+    #[test]
+    fn test_learn_from_usage() {
+        let dictionary = TechnicalDictionary::in_memory();
+        dictionary.terms.insert("מגוף".to_string(), sample_term("מגוף", "задвижка", None));
+        dictionary.terms.insert("ברז".to_string(), sample_term("ברז", "кран", None));
+
+        dictionary.learn_from_usage("יש לסגור את המגוף", "Закройте задвижку").unwrap();
+        dictionary.learn_from_usage("החלפת המגוף", "Замена клапана").unwrap();
+
+        let term = dictionary.get_term("מגוף").unwrap();
+        assert_eq!((term.metadata.usage.accepted, term.metadata.usage.rejected), (1, 1));
+        assert_eq!(dictionary.get_term("ברז").unwrap().metadata.usage_count, 0);
+
+        let report = dictionary.usage_report(&UsageReportOptions::default());
+        assert_eq!(report.never_used.len(), 1);
+        assert_eq!(report.never_used[0].source, "ברז");
+    }
+}
@@ -1,100 +1,358 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::language::{Language, Multilingual};
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct TechnicalTerm {
-    pub hebrew: String,
-    pub russian: String,
-    pub english: Option<String>,
+// תחום שמונחיו תקפים בכל תחום אחר
+pub const GENERAL_DOMAIN: &str = "general";
+
+/// צורת מונח בשפה אחת בתוך מושג
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConceptTerm {
+    pub text: String,
+    pub lang: Language,
+    /// תחום שבו הצורה תקפה; ללא ערך - התחום של המושג
+    #[serde(default)]
+    pub domain: Option<String>,
+    #[serde(default)]
+    pub context: Option<String>,
+    /// תגיות שימוש: "drawings", "colloquial", "deprecated"
+    #[serde(default)]
+    pub usage: Vec<String>,
+}
+
+impl ConceptTerm {
+    pub fn new(lang: Language, text: &str) -> Self {
+        Self {
+            text: text.trim().to_string(),
+            lang,
+            domain: None,
+            context: None,
+            usage: Vec::new(),
+        }
+    }
+
+    pub fn with_domain(mut self, domain: &str) -> Self {
+        self.domain = Some(domain.to_string());
+        self
+    }
+
+    pub fn with_context(mut self, context: &str) -> Self {
+        self.context = Some(context.to_string());
+        self
+    }
+
+    pub fn with_usage(mut self, tag: &str) -> Self {
+        self.usage.push(tag.to_string());
+        self
+    }
+}
+
+/// מושג אחד וכל המונחים שמציינים אותו, בכל שפה; המונח הראשון בכל שפה הוא המועדף
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Concept {
+    pub id: String,
+    pub domain: String,
     pub context: String,
     pub standards: Vec<String>,
     pub notes: Option<String>,
+    pub terms: Vec<ConceptTerm>,
+}
+
+impl Concept {
+    pub fn new(id: &str, domain: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            domain: domain.to_string(),
+            context: String::new(),
+            standards: Vec::new(),
+            notes: None,
+            terms: Vec::new(),
+        }
+    }
+
+    pub fn with_term(mut self, term: ConceptTerm) -> Self {
+        self.terms.push(term);
+        self
+    }
+
+    pub fn with_context(mut self, context: &str) -> Self {
+        self.context = context.to_string();
+        self
+    }
+
+    pub fn with_standards(mut self, standards: &[&str]) -> Self {
+        self.standards = standards.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    pub fn terms_in(&self, lang: Language) -> impl Iterator<Item = &ConceptTerm> {
+        self.terms.iter().filter(move |term| term.lang == lang)
+    }
+
+    /// התחום שבו צורה מסוימת תקפה
+    pub fn domain_of<'a>(&'a self, term: &'a ConceptTerm) -> &'a str {
+        term.domain.as_deref().unwrap_or(&self.domain)
+    }
+}
+
+impl Multilingual for Concept {
+    fn forms(&self) -> Vec<(Language, &str)> {
+        self.terms.iter().map(|term| (term.lang, term.text.as_str())).collect()
+    }
 }
 
-#[derive(Debug)]
+/// מועמד לתרגום שנמצא במסד המונחים
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TermMatch {
+    pub concept_id: String,
+    pub text: String,
+    pub domain: String,
+    pub context: Option<String>,
+    pub usage: Vec<String>,
+    /// הצורה המועדפת של המושג בשפת היעד
+    pub preferred: bool,
+}
+
+#[derive(Debug, Default)]
 pub struct TermsDatabase {
-    terms: HashMap<String, TechnicalTerm>,
-    russian_to_hebrew: HashMap<String, String>,
+    concepts: HashMap<String, Concept>,
+    // (שפה, צורה מנורמלת) ← מזהי המושגים, לפי סדר ההוספה
+    index: HashMap<(Language, String), Vec<String>>,
 }
 
 impl TermsDatabase {
     pub fn new() -> Self {
-        Self {
-            terms: HashMap::new(),
-            russian_to_hebrew: HashMap::new(),
+        Self::default()
+    }
+
+    /// הוספת מושג או החלפת מושג קיים עם אותו מזהה
+    pub fn add_concept(&mut self, concept: Concept) {
+        self.remove_concept(&concept.id);
+        for term in &concept.terms {
+            let ids = self.index.entry((term.lang, normalize(&term.text))).or_default();
+            if !ids.contains(&concept.id) {
+                ids.push(concept.id.clone());
+            }
         }
+        self.concepts.insert(concept.id.clone(), concept);
+    }
+
+    pub fn remove_concept(&mut self, id: &str) -> Option<Concept> {
+        let concept = self.concepts.remove(id)?;
+        for term in &concept.terms {
+            let key = (term.lang, normalize(&term.text));
+            if let Some(ids) = self.index.get_mut(&key) {
+                ids.retain(|existing| existing != id);
+                if ids.is_empty() {
+                    self.index.remove(&key);
+                }
+            }
+        }
+        Some(concept)
+    }
+
+    pub fn get_concept(&self, id: &str) -> Option<&Concept> {
+        self.concepts.get(id)
+    }
+
+    pub fn concepts(&self) -> impl Iterator<Item = &Concept> {
+        self.concepts.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.concepts.len()
     }
 
-    pub fn add_term(&mut self, term: TechnicalTerm) {
-        self.russian_to_hebrew.insert(term.russian.clone(), term.hebrew.clone());
-        self.terms.insert(term.hebrew.clone(), term);
+    pub fn is_empty(&self) -> bool {
+        self.concepts.is_empty()
     }
 
-    pub fn get_russian_translation(&self, hebrew_term: &str) -> Option<&String> {
-        self.terms.get(hebrew_term).map(|term| &term.russian)
+    /// כל המושגים שאחד ממונחיהם הוא הצורה הנתונה
+    pub fn find_concepts(&self, text: &str, lang: Language) -> Vec<&Concept> {
+        self.index
+            .get(&(lang, normalize(text)))
+            .map(|ids| ids.iter().filter_map(|id| self.concepts.get(id)).collect())
+            .unwrap_or_default()
     }
 
-    pub fn get_hebrew_translation(&self, russian_term: &str) -> Option<&String> {
-        self.russian_to_hebrew.get(russian_term)
+    /// המושג הראשון שנוסף עם הצורה הנתונה
+    pub fn find_term(&self, text: &str, lang: Language) -> Option<&Concept> {
+        self.find_concepts(text, lang).into_iter().next()
     }
 
-    pub fn get_all_hebrew_terms(&self) -> impl Iterator<Item = &String> {
-        self.terms.keys()
+    /// כל התרגומים האפשריים בכיוון הנתון; כשמצוין תחום - רק מונחים של התחום או כלליים,
+    /// ומונחי התחום קודמים
+    pub fn lookup(&self, text: &str, source: Language, target: Language, domain: Option<&str>) -> Vec<TermMatch> {
+        let mut matches: Vec<(bool, TermMatch)> = Vec::new();
+
+        for concept in self.find_concepts(text, source) {
+            // הצורה במקור צריכה להיות תקפה בתחום המבוקש
+            let source_in_scope = concept
+                .terms_in(source)
+                .filter(|term| normalize(&term.text) == normalize(text))
+                .any(|term| in_scope(concept.domain_of(term), domain));
+            if !source_in_scope {
+                continue;
+            }
+
+            for (position, term) in concept.terms_in(target).enumerate() {
+                let term_domain = concept.domain_of(term);
+                if !in_scope(term_domain, domain) {
+                    continue;
+                }
+                matches.push((
+                    domain == Some(term_domain),
+                    TermMatch {
+                        concept_id: concept.id.clone(),
+                        text: term.text.clone(),
+                        domain: term_domain.to_string(),
+                        context: term.context.clone().or_else(|| {
+                            (!concept.context.is_empty()).then(|| concept.context.clone())
+                        }),
+                        usage: term.usage.clone(),
+                        preferred: position == 0,
+                    },
+                ));
+            }
+        }
+
+        // מיון יציב: התאמת תחום, ואחריה הצורה המועדפת; אחרת - סדר ההוספה
+        matches.sort_by_key(|(exact, term)| (!exact, !term.preferred));
+        matches.into_iter().map(|(_, term)| term).collect()
     }
 
-    pub fn get_all_russian_terms(&self) -> impl Iterator<Item = &String> {
-        self.russian_to_hebrew.keys()
+    /// התרגום המועדף, ללא הגבלת תחום
+    pub fn get_translation(&self, text: &str, source: Language, target: Language) -> Option<String> {
+        self.get_translation_in(text, source, target, None)
     }
 
-    pub fn get_term_context(&self, term: &str) -> Option<&String> {
-        self.terms.get(term).map(|t| &t.context)
+    /// התרגום המועדף בתחום נתון
+    pub fn get_translation_in(
+        &self,
+        text: &str,
+        source: Language,
+        target: Language,
+        domain: Option<&str>,
+    ) -> Option<String> {
+        self.lookup(text, source, target, domain).into_iter().next().map(|best| best.text)
     }
+}
+
+fn normalize(text: &str) -> String {
+    text.trim().to_lowercase()
+}
 
-    pub fn get_term_standards(&self, term: &str) -> Option<&Vec<String>> {
-        self.terms.get(term).map(|t| &t.standards)
+fn in_scope(term_domain: &str, domain: Option<&str>) -> bool {
+    match domain {
+        Some(domain) => term_domain == domain || term_domain == GENERAL_DOMAIN,
+        None => true,
     }
 }
 
 // יצירת מונחים בסיסיים
 pub fn create_initial_terms() -> TermsDatabase {
     let mut db = TermsDatabase::new();
-    
+
     // הוספת מונחים בסיסיים
-    db.add_term(TechnicalTerm {
-        hebrew: "ראש ספרינקלר".to_string(),
-        russian: "ороситель/спринклер".to_string(),
-        english: Some("Sprinkler Head".to_string()),
-        context: "מערכות כיבוי אש אוטומטיות".to_string(),
-        standards: vec!["NFPA 13".to_string(), "תקן ישראלי 1596".to_string()],
-        notes: None,
-    });
-
-    db.add_term(TechnicalTerm {
-        hebrew: "צנרת אספקה".to_string(),
-        russian: "питающий трубопровод".to_string(),
-        english: Some("Supply Pipe".to_string()),
-        context: "מערכות אינסטלציה".to_string(),
-        standards: vec!["ГОСТ 3262-75".to_string()],
-        notes: None,
-    });
-
-    db.add_term(TechnicalTerm {
-        hebrew: "מגוף שליטה".to_string(),
-        russian: "контрольно-сигнальный клапан".to_string(),
-        english: Some("Control Valve".to_string()),
-        context: "מערכות כיבוי אש".to_string(),
-        standards: vec!["NFPA 13".to_string(), "ГОСТ 51052-2002".to_string()],
-        notes: None,
-    });
-
-    db.add_term(TechnicalTerm {
-        hebrew: "לחץ עבודה".to_string(),
-        russian: "рабочее давление".to_string(),
-        english: Some("Working Pressure".to_string()),
-        context: "מערכות אינסטלציה וכיבוי אש".to_string(),
-        standards: vec!["ГОСТ 356-80".to_string()],
-        notes: None,
-    });
+    db.add_concept(
+        Concept::new("sprinkler-head", "fire_protection")
+            .with_context("מערכות כיבוי אש אוטומטיות")
+            .with_standards(&["NFPA 13", "תקן ישראלי 1596"])
+            .with_term(ConceptTerm::new(Language::Hebrew, "ראש ספרינקלר"))
+            .with_term(ConceptTerm::new(Language::Russian, "ороситель/спринклер"))
+            .with_term(ConceptTerm::new(Language::English, "Sprinkler Head")),
+    );
+
+    db.add_concept(
+        Concept::new("supply-pipe", "plumbing")
+            .with_context("מערכות אינסטלציה")
+            .with_standards(&["ГОСТ 3262-75"])
+            .with_term(ConceptTerm::new(Language::Hebrew, "צנרת אספקה"))
+            .with_term(ConceptTerm::new(Language::Russian, "питающий трубопровод"))
+            .with_term(ConceptTerm::new(Language::English, "Supply Pipe")),
+    );
+
+    db.add_concept(
+        Concept::new("control-valve", "fire_protection")
+            .with_context("מערכות כיבוי אש")
+            .with_standards(&["NFPA 13", "ГОСТ 51052-2002"])
+            .with_term(ConceptTerm::new(Language::Hebrew, "מגוף שליטה"))
+            .with_term(ConceptTerm::new(Language::Russian, "контрольно-сигнальный клапан"))
+            .with_term(ConceptTerm::new(Language::English, "Control Valve")),
+    );
+
+    db.add_concept(
+        Concept::new("working-pressure", GENERAL_DOMAIN)
+            .with_context("מערכות אינסטלציה וכיבוי אש")
+            .with_standards(&["ГОСТ 356-80"])
+            .with_term(ConceptTerm::new(Language::Hebrew, "לחץ עבודה"))
+            .with_term(ConceptTerm::new(Language::Russian, "рабочее давление"))
+            .with_term(ConceptTerm::new(Language::English, "Working Pressure")),
+    );
 
     db
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_translation_keeps_both_terms() {
+        let mut db = create_initial_terms();
+        db.add_concept(
+            Concept::new("supply-line", "plumbing")
+                .with_term(ConceptTerm::new(Language::Hebrew, "קו אספקה"))
+                .with_term(ConceptTerm::new(Language::Russian, "питающий трубопровод")),
+        );
+
+        let hebrew: Vec<String> = db
+            .lookup("питающий трубопровод", Language::Russian, Language::Hebrew, None)
+            .into_iter()
+            .map(|m| m.text)
+            .collect();
+        assert_eq!(hebrew, vec!["צנרת אספקה".to_string(), "קו אספקה".to_string()]);
+        assert_eq!(db.get_translation("קו אספקה", Language::Hebrew, Language::Russian).as_deref(), Some("питающий трубопровод"));
+    }
+
+    #[test]
+    fn test_domain_scoped_lookup() {
+        let mut db = TermsDatabase::new();
+        db.add_concept(
+            Concept::new("valve-hydraulic", "hydraulics")
+                .with_term(ConceptTerm::new(Language::Hebrew, "ברז"))
+                .with_term(ConceptTerm::new(Language::Russian, "вентиль"))
+                .with_term(ConceptTerm::new(Language::Russian, "запорный кран").with_usage("drawings")),
+        );
+        db.add_concept(
+            Concept::new("tap-plumbing", "plumbing")
+                .with_term(ConceptTerm::new(Language::Hebrew, "ברז"))
+                .with_term(ConceptTerm::new(Language::Russian, "кран")),
+        );
+
+        let all = db.lookup("ברז", Language::Hebrew, Language::Russian, None);
+        assert_eq!(all.len(), 3);
+        assert_eq!(db.get_translation_in("ברז", Language::Hebrew, Language::Russian, Some("plumbing")).as_deref(), Some("кран"));
+
+        let hydraulic = db.lookup("ברז", Language::Hebrew, Language::Russian, Some("hydraulics"));
+        assert_eq!(hydraulic.len(), 2);
+        assert!(hydraulic[0].preferred);
+        assert_eq!(hydraulic[1].usage, vec!["drawings".to_string()]);
+
+        // בכיוון ההפוך
+        assert_eq!(db.get_translation("вентиль", Language::Russian, Language::Hebrew).as_deref(), Some("ברז"));
+    }
+
+    #[test]
+    fn test_replace_concept_updates_index() {
+        let mut db = create_initial_terms();
+        db.add_concept(
+            Concept::new("sprinkler-head", "fire_protection")
+                .with_term(ConceptTerm::new(Language::Hebrew, "ראש ספרינקלר"))
+                .with_term(ConceptTerm::new(Language::Russian, "спринклерный ороситель")),
+        );
+
+        assert!(db.find_term("ороситель/спринклер", Language::Russian).is_none());
+        assert_eq!(db.len(), 4);
+    }
+}
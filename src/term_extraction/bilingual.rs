@@ -0,0 +1,407 @@
+use crate::knowledge_sharing::KnowledgeManager;
+use crate::language::Language;
+use crate::technical_dictionary::{TechnicalDictionary, TechnicalTerm, TermMetadata, UsageStatus};
+use crate::term_usage::UsageStats;
+use crate::term_extraction::patterns::{citation_form, extract_phrases, most_frequent, phrase_key, PhraseCandidate};
+use crate::translation::TranslationOutput;
+use anyhow::{Context, Result};
+use serde::{Serialize, Deserialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+const DEFAULT_MIN_COOCCURRENCE: usize = 2;
+const DEFAULT_MIN_DICE: f64 = 0.3;
+const MAX_EXAMPLES: usize = 3;
+// מונח שחולץ אוטומטית מתחיל בביטחון נמוך, עד לאישור בודק
+const EXTRACTED_CONFIDENCE: f64 = 0.5;
+
+/// זוג מקטעים מיושרים: מקור ותרגומו
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlignedPair {
+    pub source: String,
+    pub target: String,
+}
+
+/// טעינת זוגות מקטעים מקובץ TSV: מקור ותרגום מופרדים בטאב, שורה לכל זוג
+pub fn load_aligned_tsv(path: &Path) -> Result<Vec<AlignedPair>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("קריאת קובץ מקטעים מיושרים נכשלה: {}", path.display()))?;
+
+    let mut pairs = Vec::new();
+    for (line_number, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (source, target) = line
+            .split_once('\t')
+            .with_context(|| format!("שורה {} אינה מכילה טאב: {}", line_number + 1, path.display()))?;
+        pairs.push(AlignedPair {
+            source: source.trim().to_string(),
+            target: target.trim().to_string(),
+        });
+    }
+    Ok(pairs)
+}
+
+/// זוגות מקטעים מתרגומים קודמים (לאחר עריכה), ללא מקטעים שלא תורגמו
+pub fn pairs_from_outputs(outputs: &[TranslationOutput]) -> Vec<AlignedPair> {
+    outputs
+        .iter()
+        .flat_map(|output| output.segments.iter())
+        .filter(|segment| !segment.source.trim().is_empty() && segment.source.trim() != segment.target.trim())
+        .map(|segment| AlignedPair {
+            source: segment.source.clone(),
+            target: segment.target.clone(),
+        })
+        .collect()
+}
+
+/// זוג מונחים מועמד, עם הסטטיסטיקה שהובילה אליו
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TermPairCandidate {
+    pub source: String,
+    pub target: String,
+    pub source_lang: Language,
+    pub target_lang: Language,
+    /// מספר זוגות המקטעים שבהם הופיעו שני הצדדים יחד
+    pub cooccurrences: usize,
+    pub dice: f64,
+    /// יחס הנראות של Dunning (G²)
+    pub log_likelihood: f64,
+    /// דוגמאות מהקורפוס: מקור ותרגום
+    pub examples: Vec<(String, String)>,
+}
+
+impl TermPairCandidate {
+    /// מונח מוצע למילון: לא מאומת, בביטחון נמוך ובמעמד "מותר"
+    pub fn to_proposed_term(&self, domain: &str) -> TechnicalTerm {
+        let english = match (self.source_lang, self.target_lang) {
+            (Language::English, _) => Some(self.source.clone()),
+            (_, Language::English) => Some(self.target.clone()),
+            _ => None,
+        };
+
+        let mut term = TechnicalTerm {
+            source: self.source.clone(),
+            target: self.target.clone(),
+            source_lang: self.source_lang,
+            target_lang: self.target_lang,
+            english,
+            domain: domain.to_string(),
+            context: Vec::new(),
+            usage_examples: self.examples.iter().map(|(source, _)| source.clone()).collect(),
+            variants: Vec::new(),
+            definition: None,
+            notes: Vec::new(),
+            metadata: TermMetadata {
+                confidence_score: EXTRACTED_CONFIDENCE * self.dice.min(1.0),
+                last_updated: chrono::Utc::now(),
+                usage_count: 0,
+                verified: false,
+                source_references: vec!["extraction".to_string()],
+                usage: UsageStats::default(),
+            },
+        };
+        term.set_status(&self.target, self.target_lang, UsageStatus::Admitted);
+        term
+    }
+
+    /// תיאור הראיות לבודק
+    pub fn evidence(&self) -> String {
+        let mut evidence = format!(
+            "חולץ אוטומטית: {} ↔ {}, הופעות משותפות {}, Dice {:.2}, G² {:.1}",
+            self.source, self.target, self.cooccurrences, self.dice, self.log_likelihood
+        );
+        for (source, target) in &self.examples {
+            evidence.push_str(&format!("\n• {} | {}", source, target));
+        }
+        evidence
+    }
+}
+
+/// חילוץ זוגות מונחים מקורפוס מיושר לפי הופעות משותפות של צירופים שמניים
+pub struct BilingualExtractor {
+    source_lang: Language,
+    target_lang: Language,
+    min_cooccurrence: usize,
+    min_dice: f64,
+}
+
+// צירופי מקטע אחד, מאוחדים לפי מפתח למה
+struct SegmentPhrases {
+    keys: HashSet<String>,
+    surfaces: Vec<PhraseCandidate>,
+}
+
+impl BilingualExtractor {
+    pub fn new(source_lang: Language, target_lang: Language) -> Self {
+        Self {
+            source_lang,
+            target_lang,
+            min_cooccurrence: DEFAULT_MIN_COOCCURRENCE,
+            min_dice: DEFAULT_MIN_DICE,
+        }
+    }
+
+    pub fn with_min_cooccurrence(mut self, count: usize) -> Self {
+        self.min_cooccurrence = count.max(1);
+        self
+    }
+
+    pub fn with_min_dice(mut self, dice: f64) -> Self {
+        self.min_dice = dice.clamp(0.0, 1.0);
+        self
+    }
+
+    /// זוגות מועמדים, מדורגים לפי G²; זוגות שכבר במילון משמשים עוגנים ואינם מוצעים
+    pub fn extract(&self, pairs: &[AlignedPair], dictionary: Option<&TechnicalDictionary>) -> Vec<TermPairCandidate> {
+        let mut source_freq: HashMap<String, usize> = HashMap::new();
+        let mut target_freq: HashMap<String, usize> = HashMap::new();
+        let mut joint_freq: HashMap<(String, String), usize> = HashMap::new();
+        let mut source_forms: HashMap<String, HashMap<String, usize>> = HashMap::new();
+        let mut target_forms: HashMap<String, HashMap<String, usize>> = HashMap::new();
+        let mut examples: HashMap<(String, String), Vec<(String, String)>> = HashMap::new();
+
+        for pair in pairs {
+            let mut source = self.segment_phrases(&pair.source, self.source_lang);
+            let mut target = self.segment_phrases(&pair.target, self.target_lang);
+            if let Some(dictionary) = dictionary {
+                self.remove_anchors(&mut source, &mut target, dictionary);
+            }
+
+            for phrase in &source.surfaces {
+                *source_forms.entry(phrase.key.clone()).or_default().entry(phrase.text.clone()).or_default() += 1;
+            }
+            for phrase in &target.surfaces {
+                *target_forms.entry(phrase.key.clone()).or_default().entry(phrase.text.clone()).or_default() += 1;
+            }
+            for key in &source.keys {
+                *source_freq.entry(key.clone()).or_default() += 1;
+            }
+            for key in &target.keys {
+                *target_freq.entry(key.clone()).or_default() += 1;
+            }
+            for source_key in &source.keys {
+                for target_key in &target.keys {
+                    let joint = (source_key.clone(), target_key.clone());
+                    *joint_freq.entry(joint.clone()).or_default() += 1;
+                    let pair_examples = examples.entry(joint).or_default();
+                    if pair_examples.len() < MAX_EXAMPLES {
+                        pair_examples.push((pair.source.clone(), pair.target.clone()));
+                    }
+                }
+            }
+        }
+
+        let total = pairs.len();
+        let mut scored: Vec<(String, String, usize, f64, f64)> = joint_freq
+            .into_iter()
+            .filter(|(_, count)| *count >= self.min_cooccurrence)
+            .filter_map(|((source_key, target_key), count)| {
+                let source_count = source_freq[&source_key];
+                let target_count = target_freq[&target_key];
+                let dice = 2.0 * count as f64 / (source_count + target_count) as f64;
+                if dice < self.min_dice {
+                    return None;
+                }
+                let llr = log_likelihood(count, source_count, target_count, total);
+                Some((source_key, target_key, count, dice, llr))
+            })
+            .collect();
+
+        // קישור תחרותי: כל צירוף משתתף בזוג אחד לכל היותר, החזק ביותר קודם
+        scored.sort_by(|a, b| {
+            b.4.partial_cmp(&a.4)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(b.3.partial_cmp(&a.3).unwrap_or(std::cmp::Ordering::Equal))
+                .then_with(|| a.0.cmp(&b.0))
+                .then_with(|| a.1.cmp(&b.1))
+        });
+
+        let mut linked_sources = HashSet::new();
+        let mut linked_targets = HashSet::new();
+        let mut candidates = Vec::new();
+
+        for (source_key, target_key, count, dice, llr) in scored {
+            if linked_sources.contains(&source_key) || linked_targets.contains(&target_key) {
+                continue;
+            }
+            linked_sources.insert(source_key.clone());
+            linked_targets.insert(target_key.clone());
+
+            let source = citation_form(&most_frequent(&source_forms[&source_key]), self.source_lang);
+            let target = citation_form(&most_frequent(&target_forms[&target_key]), self.target_lang);
+            candidates.push(TermPairCandidate {
+                source,
+                target,
+                source_lang: self.source_lang,
+                target_lang: self.target_lang,
+                cooccurrences: count,
+                dice,
+                log_likelihood: llr,
+                examples: examples.remove(&(source_key, target_key)).unwrap_or_default(),
+            });
+        }
+
+        candidates
+    }
+
+    /// שליחת המועמדים לתור הבדיקה במקום הוספה ישירה למילון; מחזיר את מזהי הבקשות
+    pub fn submit_for_review(
+        &self,
+        candidates: &[TermPairCandidate],
+        manager: &mut KnowledgeManager,
+        domain: &str,
+        requested_by: &str,
+        reviewers: &[String],
+    ) -> Result<Vec<String>> {
+        candidates
+            .iter()
+            .map(|candidate| {
+                manager
+                    .propose_term(
+                        candidate.to_proposed_term(domain),
+                        requested_by.to_string(),
+                        reviewers.to_vec(),
+                        candidate.evidence(),
+                    )
+                    .with_context(|| format!("הגשת המונח {} לבדיקה נכשלה", candidate.source))
+            })
+            .collect()
+    }
+
+    fn segment_phrases(&self, text: &str, lang: Language) -> SegmentPhrases {
+        let surfaces: Vec<PhraseCandidate> = extract_phrases(text, lang)
+            .into_iter()
+            .map(|phrase| PhraseCandidate { text: phrase.text.to_lowercase(), ..phrase })
+            .collect();
+        SegmentPhrases {
+            keys: surfaces.iter().map(|p| p.key.clone()).collect(),
+            surfaces,
+        }
+    }
+
+    // צירוף במקור שתרגומו במילון מופיע ביעד: שני הצדדים כבר ידועים ואינם מועמדים
+    fn remove_anchors(&self, source: &mut SegmentPhrases, target: &mut SegmentPhrases, dictionary: &TechnicalDictionary) {
+        let mut anchored_sources = HashSet::new();
+        let mut anchored_targets = HashSet::new();
+
+        for phrase in &source.surfaces {
+            let known = dictionary
+                .lookup(&phrase.text, self.source_lang, self.target_lang)
+                .or_else(|| dictionary.lookup(&citation_form(&phrase.text, self.source_lang), self.source_lang, self.target_lang));
+            if let Some(translation) = known {
+                anchored_sources.insert(phrase.key.clone());
+                anchored_targets.insert(phrase_key(&translation, self.target_lang));
+            }
+        }
+
+        source.keys.retain(|key| !anchored_sources.contains(key));
+        source.surfaces.retain(|p| !anchored_sources.contains(&p.key));
+        target.keys.retain(|key| !anchored_targets.contains(key));
+        target.surfaces.retain(|p| !anchored_targets.contains(&p.key));
+    }
+}
+
+// G² לטבלת 2x2 של הופעה משותפת
+fn log_likelihood(joint: usize, source: usize, target: usize, total: usize) -> f64 {
+    let total = total.max(source + target - joint) as f64;
+    let (joint, source, target) = (joint as f64, source as f64, target as f64);
+    let cells = [
+        (joint, source, target),
+        (source - joint, source, total - target),
+        (target - joint, total - source, target),
+        (total - source - target + joint, total - source, total - target),
+    ];
+
+    2.0 * cells
+        .iter()
+        .filter(|(observed, _, _)| *observed > 0.0)
+        .map(|(observed, row, column)| {
+            let expected = row * column / total;
+            observed * (observed / expected).ln()
+        })
+        .sum::<f64>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn corpus() -> Vec<AlignedPair> {
+        [
+            ("המגוף סגור", "Задвижка закрыта"),
+            ("המגוף פתוח במלואו", "Задвижка полностью открыта"),
+            ("יש לבדוק את המגוף", "Проверьте задвижку"),
+            ("הפעל את המשאבה", "Включите насос"),
+            ("המשאבה תקינה", "Насос исправен"),
+        ]
+        .iter()
+        .map(|(source, target)| AlignedPair {
+            source: source.to_string(),
+            target: target.to_string(),
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_extract_term_pairs() {
+        let extractor = BilingualExtractor::new(Language::Hebrew, Language::Russian);
+        let candidates = extractor.extract(&corpus(), None);
+
+        let valve = candidates.iter().find(|c| c.source == "מגוף").unwrap();
+        assert_eq!(valve.target, "задвижка");
+        assert_eq!(valve.cooccurrences, 3);
+        assert!(valve.log_likelihood > 0.0);
+        assert!(candidates.iter().any(|c| c.source == "משאבה" && c.target == "насос"));
+        // קישור תחרותי: לכל צירוף זוג אחד
+        assert_eq!(candidates.iter().filter(|c| c.target == "задвижка").count(), 1);
+    }
+
+    #[test]
+    fn test_dictionary_anchors_skip_known_pairs() {
+        let dictionary = TechnicalDictionary::in_memory();
+        let mut known = extractor_term("משאבה", "насос");
+        known.metadata.verified = true;
+        dictionary.insert_terms(vec![known]).unwrap();
+
+        let extractor = BilingualExtractor::new(Language::Hebrew, Language::Russian);
+        let candidates = extractor.extract(&corpus(), Some(&dictionary));
+        assert!(!candidates.iter().any(|c| c.source == "משאבה" || c.target == "насос"));
+        assert!(candidates.iter().any(|c| c.source == "מגוף"));
+    }
+
+    #[test]
+    fn test_candidates_go_to_review_queue() {
+        let extractor = BilingualExtractor::new(Language::Hebrew, Language::Russian);
+        let candidates = extractor.extract(&corpus(), None);
+        let mut manager = KnowledgeManager::new();
+
+        let ids = extractor
+            .submit_for_review(&candidates, &mut manager, "hydraulics", "extractor", &["reviewer".to_string()])
+            .unwrap();
+        assert_eq!(ids.len(), candidates.len());
+
+        let request = manager.get_review_request(&ids[0]).unwrap();
+        let term = request.proposed_term.as_ref().unwrap();
+        assert!(!term.metadata.verified);
+        assert_eq!(term.main_status(term.target_lang), Some(UsageStatus::Admitted));
+        assert!(request.comments[0].content.contains("Dice"));
+        assert_eq!(manager.get_pending_reviews("reviewer").len(), ids.len());
+    }
+
+    fn extractor_term(source: &str, target: &str) -> TechnicalTerm {
+        TermPairCandidate {
+            source: source.to_string(),
+            target: target.to_string(),
+            source_lang: Language::Hebrew,
+            target_lang: Language::Russian,
+            cooccurrences: 0,
+            dice: 1.0,
+            log_likelihood: 0.0,
+            examples: Vec::new(),
+        }
+        .to_proposed_term("general")
+    }
+}
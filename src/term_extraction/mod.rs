@@ -0,0 +1,9 @@
+//! חילוץ מונחים מועמדים מטקסטים, לבדיקה לפני הוספה למילון
+
+pub mod patterns;
+pub mod bilingual;
+pub mod monolingual;
+
+pub use patterns::{extract_phrases, PhraseCandidate, WordClass};
+pub use bilingual::{AlignedPair, BilingualExtractor, TermPairCandidate};
+pub use monolingual::{ExtractedTerm, MonolingualExtractor, TermAnalysis};
@@ -0,0 +1,344 @@
+use crate::language::{Language, Multilingual};
+use crate::technical_dictionary::TechnicalDictionary;
+use crate::technical_terms::TermsDatabase;
+use crate::term_extraction::patterns::{citation_form, extract_phrases, most_frequent, phrase_key};
+use anyhow::{Context, Result};
+use serde::{Serialize, Deserialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+const DEFAULT_MIN_FREQUENCY: usize = 2;
+const DEFAULT_MAX_CANDIDATES: usize = 500;
+const MAX_CONTEXTS: usize = 2;
+
+/// המקור שבו המונח כבר מוכר
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KnownSource {
+    TechnicalDictionary,
+    TermsDatabase,
+}
+
+/// מונח שכבר קיים באחד המילונים, עם תרגומו לשפת היעד
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnownTerm {
+    pub source: KnownSource,
+    pub translation: Option<String>,
+}
+
+/// מונח מועמד שחולץ מהמסמך
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractedTerm {
+    /// צורת המילון של הצירוף
+    pub text: String,
+    pub key: String,
+    pub words: usize,
+    pub frequency: usize,
+    /// ציון C-value: שכיחות משוקללת באורך, בניכוי הופעות בתוך צירופים ארוכים יותר
+    pub termhood: f64,
+    pub known: Option<KnownTerm>,
+    /// משפטים מהמסמך שבהם הופיע המונח
+    pub contexts: Vec<String>,
+}
+
+/// תוצאת ניתוח המונחים של מסמך
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TermAnalysis {
+    pub lang: Language,
+    pub target_lang: Language,
+    pub candidates: Vec<ExtractedTerm>,
+}
+
+impl TermAnalysis {
+    pub fn known(&self) -> impl Iterator<Item = &ExtractedTerm> {
+        self.candidates.iter().filter(|c| c.known.is_some())
+    }
+
+    pub fn unknown(&self) -> impl Iterator<Item = &ExtractedTerm> {
+        self.candidates.iter().filter(|c| c.known.is_none())
+    }
+
+    /// חלק ההופעות של מונחים מועמדים שהמילונים כבר מכסים
+    pub fn coverage(&self) -> f64 {
+        let total: usize = self.candidates.iter().map(|c| c.frequency).sum();
+        if total == 0 {
+            return 1.0;
+        }
+        self.known().map(|c| c.frequency).sum::<usize>() as f64 / total as f64
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} מונחים מועמדים: {} מוכרים, {} חדשים; כיסוי {:.0}%",
+            self.candidates.len(),
+            self.known().count(),
+            self.unknown().count(),
+            self.coverage() * 100.0
+        )
+    }
+
+    /// גיליון עבודה למונחים החסרים: עמודות לתרגום ולתחום ימולאו על ידי המתרגם
+    pub fn save_worksheet_csv(&self, path: &Path) -> Result<()> {
+        let mut writer = csv::Writer::from_path(path)
+            .with_context(|| format!("יצירת גיליון המונחים נכשלה: {}", path.display()))?;
+
+        writer.write_record(&[
+            format!("term_{}", self.lang.code()),
+            format!("translation_{}", self.target_lang.code()),
+            "domain".to_string(),
+            "frequency".to_string(),
+            "termhood".to_string(),
+            "context".to_string(),
+        ])?;
+        for term in self.unknown() {
+            writer.write_record(&[
+                term.text.clone(),
+                String::new(),
+                String::new(),
+                term.frequency.to_string(),
+                format!("{:.2}", term.termhood),
+                term.contexts.first().cloned().unwrap_or_default(),
+            ])?;
+        }
+        writer
+            .flush()
+            .with_context(|| format!("כתיבת גיליון המונחים נכשלה: {}", path.display()))?;
+        Ok(())
+    }
+
+    pub fn save_json(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)
+            .with_context(|| format!("שמירת ניתוח המונחים נכשלה: {}", path.display()))
+    }
+}
+
+/// חילוץ מונחים מועמדים ממסמך בשפה אחת, לפני תחילת פרויקט תרגום
+pub struct MonolingualExtractor {
+    lang: Language,
+    target_lang: Language,
+    min_frequency: usize,
+    max_candidates: usize,
+}
+
+// נתונים מצטברים לכל מפתח למה
+#[derive(Default)]
+struct KeyStats {
+    frequency: usize,
+    words: usize,
+    forms: HashMap<String, usize>,
+    contexts: Vec<String>,
+}
+
+impl MonolingualExtractor {
+    pub fn new(lang: Language, target_lang: Language) -> Self {
+        Self {
+            lang,
+            target_lang,
+            min_frequency: DEFAULT_MIN_FREQUENCY,
+            max_candidates: DEFAULT_MAX_CANDIDATES,
+        }
+    }
+
+    pub fn with_min_frequency(mut self, frequency: usize) -> Self {
+        self.min_frequency = frequency.max(1);
+        self
+    }
+
+    pub fn with_max_candidates(mut self, max: usize) -> Self {
+        self.max_candidates = max;
+        self
+    }
+
+    /// מונחים מועמדים מדורגים לפי C-value, עם סימון מונחים שכבר מוכרים
+    pub fn analyze(
+        &self,
+        text: &str,
+        dictionary: Option<&TechnicalDictionary>,
+        terms_db: Option<&TermsDatabase>,
+    ) -> TermAnalysis {
+        let mut stats: HashMap<String, KeyStats> = HashMap::new();
+
+        for sentence in split_sentences(text) {
+            for phrase in extract_phrases(sentence, self.lang) {
+                let entry = stats.entry(phrase.key).or_default();
+                entry.frequency += 1;
+                entry.words = phrase.words;
+                *entry.forms.entry(phrase.text.to_lowercase()).or_default() += 1;
+                if entry.contexts.len() < MAX_CONTEXTS && !entry.contexts.iter().any(|c| c == sentence) {
+                    entry.contexts.push(sentence.to_string());
+                }
+            }
+        }
+
+        let known = self.known_terms(dictionary, terms_db);
+        let termhood = c_values(&stats, self.min_frequency);
+
+        let mut candidates: Vec<ExtractedTerm> = stats
+            .into_iter()
+            .filter(|(_, s)| s.frequency >= self.min_frequency)
+            .filter_map(|(key, s)| {
+                let score = termhood.get(&key).copied().unwrap_or(0.0);
+                // צירוף שמופיע רק בתוך צירוף ארוך יותר אינו מונח בפני עצמו
+                if score <= 0.0 {
+                    return None;
+                }
+                Some(ExtractedTerm {
+                    text: citation_form(&most_frequent(&s.forms), self.lang),
+                    known: known.get(&key).cloned(),
+                    key,
+                    words: s.words,
+                    frequency: s.frequency,
+                    termhood: score,
+                    contexts: s.contexts,
+                })
+            })
+            .collect();
+
+        candidates.sort_by(|a, b| {
+            b.termhood
+                .partial_cmp(&a.termhood)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(b.frequency.cmp(&a.frequency))
+                .then_with(|| a.text.cmp(&b.text))
+        });
+        candidates.truncate(self.max_candidates);
+
+        TermAnalysis {
+            lang: self.lang,
+            target_lang: self.target_lang,
+            candidates,
+        }
+    }
+
+    // מפתחות הלמה של כל הצורות המוכרות בשפת המסמך; המילון הטכני גובר על מאגר המונחים
+    fn known_terms(
+        &self,
+        dictionary: Option<&TechnicalDictionary>,
+        terms_db: Option<&TermsDatabase>,
+    ) -> HashMap<String, KnownTerm> {
+        let mut known = HashMap::new();
+
+        if let Some(terms_db) = terms_db {
+            for concept in terms_db.concepts() {
+                let translation = concept.text_in(self.target_lang).map(str::to_string);
+                for term in concept.terms_in(self.lang) {
+                    known.entry(phrase_key(&term.text, self.lang)).or_insert_with(|| KnownTerm {
+                        source: KnownSource::TermsDatabase,
+                        translation: translation.clone(),
+                    });
+                }
+            }
+        }
+
+        if let Some(dictionary) = dictionary {
+            for term in dictionary.all_terms() {
+                let translation = term.text_in(self.target_lang).map(str::to_string);
+                let variants = term.variants.iter().filter(|v| v.lang == self.lang).map(|v| v.text.as_str());
+                for form in term.text_in(self.lang).into_iter().chain(variants) {
+                    known.insert(phrase_key(form, self.lang), KnownTerm {
+                        source: KnownSource::TechnicalDictionary,
+                        translation: translation.clone(),
+                    });
+                }
+            }
+        }
+
+        known
+    }
+}
+
+fn split_sentences(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| matches!(c, '.' | '!' | '?' | ';' | '\n'))
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+// C-value (Frantzi ואחרים): log2(אורך+1) * (f(a) - ממוצע השכיחות של המועמדים המכילים את a).
+// כל מועמד נרשם תחת כל תת-צירוף שלו, כך שהחישוב ליניארי במספר המועמדים
+fn c_values(stats: &HashMap<String, KeyStats>, min_frequency: usize) -> HashMap<String, f64> {
+    let frequent: Vec<(&String, &KeyStats)> = stats.iter().filter(|(_, s)| s.frequency >= min_frequency).collect();
+
+    // תת-צירוף -> (סכום השכיחויות, מספר המועמדים המכילים אותו)
+    let mut nested: HashMap<String, (usize, usize)> = HashMap::new();
+    for (key, s) in &frequent {
+        let words: Vec<&str> = key.split(' ').collect();
+        // תת-צירוף שחוזר באותו מועמד נספר פעם אחת
+        let mut seen = HashSet::new();
+        for start in 0..words.len() {
+            for end in start + 1..=words.len() {
+                let sub = words[start..end].join(" ");
+                if end - start < words.len() && seen.insert(sub.clone()) {
+                    let entry = nested.entry(sub).or_default();
+                    entry.0 += s.frequency;
+                    entry.1 += 1;
+                }
+            }
+        }
+    }
+
+    frequent
+        .iter()
+        .map(|(key, s)| {
+            let weight = (s.words as f64 + 1.0).log2();
+            let frequency = s.frequency as f64;
+            let score = match nested.get(key.as_str()) {
+                Some(&(sum, count)) => weight * (frequency - sum as f64 / count as f64),
+                None => weight * frequency,
+            };
+            ((*key).clone(), score)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::technical_terms::create_initial_terms;
+
+    const DOCUMENT: &str = "יש לבדוק את מגוף השליטה הראשי. מגוף השליטה נסגר בסוף העבודה. \
+        לאחר מכן יש לשטוף את צנרת האספקה. צנרת האספקה תיבדק בלחץ.";
+
+    #[test]
+    fn test_ranked_candidates() {
+        let extractor = MonolingualExtractor::new(Language::Hebrew, Language::Russian);
+        let analysis = extractor.analyze(DOCUMENT, None, None);
+
+        let texts: Vec<&str> = analysis.candidates.iter().map(|c| c.text.as_str()).collect();
+        assert!(texts.contains(&"מגוף השליטה"));
+        assert!(texts.contains(&"צנרת האספקה"));
+        // "השליטה" מופיעה רק בתוך "מגוף השליטה"
+        assert!(!texts.contains(&"שליטה"));
+        assert!(analysis.candidates.windows(2).all(|w| w[0].termhood >= w[1].termhood));
+    }
+
+    #[test]
+    fn test_known_terms_marked() {
+        let dictionary = TechnicalDictionary::in_memory();
+        let terms_db = create_initial_terms();
+        let extractor = MonolingualExtractor::new(Language::Hebrew, Language::Russian);
+        let analysis = extractor.analyze(DOCUMENT, Some(&dictionary), Some(&terms_db));
+
+        // "צנרת האספקה" בטקסט מתאימה ל"צנרת אספקה" במאגר
+        let pipe = analysis.candidates.iter().find(|c| c.text == "צנרת האספקה").unwrap();
+        let known = pipe.known.as_ref().unwrap();
+        assert_eq!(known.source, KnownSource::TermsDatabase);
+        assert_eq!(known.translation.as_deref(), Some("питающий трубопровод"));
+        assert!(analysis.coverage() > 0.0);
+    }
+
+    #[test]
+    fn test_worksheet_lists_unknown_terms() {
+        let extractor = MonolingualExtractor::new(Language::Hebrew, Language::Russian);
+        let analysis = extractor.analyze(DOCUMENT, None, None);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("worksheet.csv");
+
+        analysis.save_worksheet_csv(&path).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+
+        assert!(content.starts_with("term_he,translation_ru"));
+        assert!(content.contains("צנרת האספקה"));
+    }
+}
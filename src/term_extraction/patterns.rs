@@ -0,0 +1,279 @@
+use crate::language::Language;
+use crate::morphology::utils::lemma_keys;
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+
+// אורך מרבי של צירוף מועמד, במילים
+pub const MAX_PHRASE_WORDS: usize = 4;
+
+// מילות תפקוד שאינן יכולות להיות חלק ממונח
+const HEBREW_STOPWORDS: &[&str] = &[
+    "של", "את", "עם", "על", "אל", "או", "גם", "כי", "אם", "לא", "יש", "אין", "זה", "זו", "זאת",
+    "הוא", "היא", "הם", "הן", "כל", "בין", "עד", "לפי", "תחת", "מעל", "אשר", "כמו", "רק", "כך",
+    "לכל", "אחרי", "לפני", "בכל", "מול", "ללא", "בלי", "יותר", "פחות", "כאשר", "ידי", "שם", "פה",
+    "מן", "אך", "אבל", "וכן", "כן", "יהיה", "תהיה", "יהיו", "היה", "היתה", "היו", "נדרש", "נדרשת",
+];
+
+// פעלים נפוצים בהוראות עבודה, שאינם פותחים צירוף שמני
+const HEBREW_VERBS: &[&str] = &[
+    "לבדוק", "בדוק", "להתקין", "התקן", "לנקות", "נקה", "לסגור", "סגור", "לפתוח", "להחליף",
+    "החלף", "לוודא", "ודא", "לנתק", "נתק", "לחבר", "לכייל", "כייל", "לשטוף", "שטוף", "להדק",
+    "הדק", "לבצע", "בצע", "להשתמש", "לספק", "להבטיח", "לתקן", "תקן", "להפעיל", "הפעל",
+];
+
+const RUSSIAN_STOPWORDS: &[&str] = &[
+    "и", "в", "во", "на", "с", "со", "по", "для", "не", "что", "это", "к", "ко", "от", "из", "у",
+    "за", "при", "до", "или", "как", "а", "но", "же", "бы", "ли", "о", "об", "под", "над", "без",
+    "через", "после", "между", "также", "все", "всех", "его", "ее", "их", "он", "она", "они", "то",
+    "быть", "должен", "должна", "должны", "может", "который", "которая", "которые", "является",
+];
+
+const ENGLISH_STOPWORDS: &[&str] = &[
+    "the", "a", "an", "of", "and", "or", "in", "on", "at", "to", "for", "with", "by", "from",
+    "is", "are", "be", "must", "shall", "should", "not", "this", "that", "as", "it", "its",
+];
+
+// סיומות של שמות תואר גזורים בעברית: חשמלי, אוטומטית, ראשיים
+const HEBREW_ADJECTIVE_SUFFIXES: &[&str] = &["יים", "יות", "ית", "י"];
+
+// שמות פעולה בסיומת "-וי" דומים לשמות תואר אך הם שמות עצם: כיבוי, גילוי, ניקוי, מילוי
+const HEBREW_VERBAL_NOUN_SUFFIX: &str = "וי";
+
+// סיומות שמות תואר ברוסית
+const RUSSIAN_ADJECTIVE_ENDINGS: &[&str] = &[
+    "ыми", "ими", "ого", "его", "ому", "ему", "ый", "ий", "ой", "ая", "яя", "ое", "ее", "ые",
+    "ие", "ым", "им", "ую", "юю", "ых", "их",
+];
+
+/// תפקיד משוער של מילה בצירוף
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WordClass {
+    Noun,
+    Adjective,
+    /// מילת תפקוד, מספר או סימן - שובר צירוף
+    Other,
+}
+
+/// צירוף מועמד למונח
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhraseCandidate {
+    /// הצורה כפי שהופיעה בטקסט
+    pub text: String,
+    /// מפתח למה של הצירוף, לאיחוד צורות נטויות
+    pub key: String,
+    pub words: usize,
+}
+
+/// סיווג גס של מילה לפי סיומת ורשימות מילות תפקוד, ללא ניתוח מלא
+pub fn classify(word: &str, lang: Language) -> WordClass {
+    let lower = word.to_lowercase();
+    if lower.chars().count() < 2 || !lower.chars().any(char::is_alphabetic) || lower.chars().any(|c| c.is_ascii_digit()) {
+        return WordClass::Other;
+    }
+
+    match lang {
+        Language::Hebrew => {
+            // מילת תפקוד עם אות שימוש: "ואת", "שבין"
+            let without_prefix = lower
+                .strip_prefix(|c: char| "ובהלשכמ".contains(c))
+                .filter(|rest| rest.chars().count() >= 2);
+            let is_stopword = |w: &str| HEBREW_STOPWORDS.contains(&w) || HEBREW_VERBS.contains(&w);
+            if is_stopword(&lower) || without_prefix.map_or(false, is_stopword) {
+                WordClass::Other
+            } else if lower.ends_with(HEBREW_VERBAL_NOUN_SUFFIX) {
+                WordClass::Noun
+            } else if lower.chars().count() > 3
+                && HEBREW_ADJECTIVE_SUFFIXES.iter().any(|suffix| lower.ends_with(suffix))
+            {
+                WordClass::Adjective
+            } else {
+                WordClass::Noun
+            }
+        }
+        Language::Russian => {
+            // שם פועל וציווי: "проверить", "проверьте"
+            // "еть" ו"сть" אינן נכללות: "сеть", "мощность"
+            let is_verb = ["ать", "ять", "ить", "уть", "ыть", "ться", "чь", "йте", "ьте", "ите"]
+                .iter()
+                .any(|ending| lower.ends_with(ending));
+            if RUSSIAN_STOPWORDS.contains(&lower.as_str()) || is_verb {
+                WordClass::Other
+            } else if ["ние", "тие", "ье", "ья"].iter().any(|ending| lower.ends_with(ending)) {
+                // שמות עצם מופשטים שסיומתם דומה לשם תואר: "давление", "отверстие"
+                WordClass::Noun
+            } else if lower.chars().count() > 3
+                && RUSSIAN_ADJECTIVE_ENDINGS.iter().any(|ending| lower.ends_with(ending))
+            {
+                WordClass::Adjective
+            } else {
+                WordClass::Noun
+            }
+        }
+        Language::English => {
+            if ENGLISH_STOPWORDS.contains(&lower.as_str()) {
+                WordClass::Other
+            } else {
+                WordClass::Noun
+            }
+        }
+    }
+}
+
+/// מפתח למה של מילה: בעברית מוסרות רק אותיות השימוש ה/ב/ל/ו,
+/// שכן מ/ש/כ הן לעתים קרובות אותיות שורש
+pub fn word_key(word: &str, lang: Language) -> String {
+    let keys = lemma_keys(word);
+    let strip_prefix = lang == Language::Hebrew
+        && word.chars().next().map_or(false, |c| "הבלו".contains(c));
+    let key = if strip_prefix { keys.last() } else { keys.first() };
+    key.cloned().unwrap_or_else(|| word.to_lowercase())
+}
+
+/// מפתח למה של צירוף שלם, למשל של תרגום מהמילון
+pub fn phrase_key(text: &str, lang: Language) -> String {
+    text.split_whitespace().map(|w| word_key(w, lang)).collect::<Vec<_>>().join(" ")
+}
+
+/// צורת מילון: בעברית מוסרת אות שימוש מהמילה הראשונה ("המגוף" ← "מגוף")
+pub fn citation_form(text: &str, lang: Language) -> String {
+    if lang != Language::Hebrew {
+        return text.to_string();
+    }
+    let mut words: Vec<&str> = text.split_whitespace().collect();
+    if let Some(first) = words.first().copied() {
+        if let Some(rest) = first.strip_prefix(|c: char| "הבלו".contains(c)) {
+            if rest.chars().count() >= 2 {
+                words[0] = rest;
+            }
+        }
+    }
+    words.join(" ")
+}
+
+/// הצורה השכיחה ביותר; בשוויון - הראשונה בסדר מילוני, לתוצאה יציבה
+pub fn most_frequent(forms: &HashMap<String, usize>) -> String {
+    forms
+        .iter()
+        .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+        .map(|(form, _)| form.clone())
+        .unwrap_or_default()
+}
+
+/// מקטעי מילים רצופים ללא סימני פיסוק ביניהם
+fn word_runs(text: &str) -> Vec<Vec<&str>> {
+    let mut runs = Vec::new();
+    let mut current = Vec::new();
+
+    for raw in text.split_whitespace() {
+        let word = raw.trim_matches(|c: char| !c.is_alphanumeric() && c != '-' && c != '"' && c != '\'');
+        let word = word.trim_matches(|c: char| c == '"' || c == '\'');
+        // גרש וגרשיים בתוך מילה (ת"י, מ"מ) נשמרים
+        if !word.is_empty() {
+            current.push(word);
+        }
+        let breaks = raw.ends_with(|c: char| matches!(c, ',' | '.' | ';' | ':' | '!' | '?' | ')' | '('))
+            || raw.starts_with('(');
+        if breaks || word.is_empty() {
+            if !current.is_empty() {
+                runs.push(std::mem::take(&mut current));
+            }
+        }
+    }
+    if !current.is_empty() {
+        runs.push(current);
+    }
+    runs
+}
+
+/// צירופים מועמדים לפי תבניות שם עצם:
+/// עברית - שם עצם ואחריו שמות עצם (סמיכות) ושמות תואר;
+/// רוסית - שמות תואר ושם עצם, ואחריהם שרשרת צירופים בקניין, תמיד מסתיים בשם עצם;
+/// חד-מיליים נכללים כשהם שמות עצם
+pub fn extract_phrases(text: &str, lang: Language) -> Vec<PhraseCandidate> {
+    let mut phrases = Vec::new();
+
+    for run in word_runs(text) {
+        let classes: Vec<WordClass> = run.iter().map(|w| classify(w, lang)).collect();
+
+        for start in 0..run.len() {
+            for end in start + 1..=(start + MAX_PHRASE_WORDS).min(run.len()) {
+                let window = &classes[start..end];
+                if !matches_pattern(window, lang) {
+                    // התבנית נשברה - הרחבה נוספת לא תתקן אותה, מלבד שמות תואר ברוסית שממתינים לשם עצם
+                    if window.contains(&WordClass::Other) {
+                        break;
+                    }
+                    continue;
+                }
+                let words = &run[start..end];
+                phrases.push(PhraseCandidate {
+                    text: words.join(" "),
+                    key: words.iter().map(|w| word_key(w, lang)).collect::<Vec<_>>().join(" "),
+                    words: words.len(),
+                });
+            }
+        }
+    }
+
+    phrases
+}
+
+fn matches_pattern(classes: &[WordClass], lang: Language) -> bool {
+    if classes.iter().any(|c| *c == WordClass::Other) {
+        return false;
+    }
+    match lang {
+        // שם עצם בראש, אחריו שמות עצם ושמות תואר בכל סדר
+        Language::Hebrew => classes[0] == WordClass::Noun,
+        // שם תואר מתאים לשם העצם שאחריו, ולכן הצירוף מסתיים בשם עצם
+        Language::Russian => classes.last() == Some(&WordClass::Noun),
+        // שמות עצם בלבד (Control Valve, Supply Pipe)
+        Language::English => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(text: &str, lang: Language) -> Vec<String> {
+        extract_phrases(text, lang).into_iter().map(|p| p.text).collect()
+    }
+
+    #[test]
+    fn test_hebrew_phrases() {
+        let phrases = texts("יש לבדוק את מגוף השליטה הראשי, ואת צנרת האספקה", Language::Hebrew);
+        assert!(phrases.contains(&"מגוף השליטה הראשי".to_string()));
+        assert!(phrases.contains(&"צנרת האספקה".to_string()));
+        assert!(!phrases.iter().any(|p| p.contains("את")));
+        // הצירוף אינו חוצה פסיק
+        assert!(!phrases.iter().any(|p| p.contains("הראשי ואת")));
+    }
+
+    #[test]
+    fn test_verbal_nouns_start_phrases() {
+        assert_eq!(classify("כיבוי", Language::Hebrew), WordClass::Noun);
+        assert_eq!(classify("גילוי", Language::Hebrew), WordClass::Noun);
+        assert_eq!(classify("חשמלי", Language::Hebrew), WordClass::Adjective);
+
+        let phrases = texts("יש להפעיל את מערכת כיבוי אש. כיבוי אש נעשה במים", Language::Hebrew);
+        assert!(phrases.contains(&"כיבוי אש".to_string()));
+    }
+
+    #[test]
+    fn test_russian_phrases() {
+        let phrases = texts("Проверить давление питающего трубопровода системы", Language::Russian);
+        assert!(phrases.contains(&"питающего трубопровода системы".to_string()));
+        assert!(phrases.contains(&"трубопровода".to_string()));
+        assert!(phrases.contains(&"давление питающего трубопровода".to_string()));
+        assert!(!phrases.contains(&"питающего".to_string()));
+        assert!(!phrases.iter().any(|p| p.contains("Проверить")));
+    }
+
+    #[test]
+    fn test_phrase_keys_merge_inflections() {
+        let singular = extract_phrases("מגוף", Language::Hebrew);
+        let definite = extract_phrases("המגוף", Language::Hebrew);
+        assert_eq!(singular[0].key, definite[0].key);
+    }
+}
@@ -0,0 +1,286 @@
+//! מעקב אחר שימוש במונחים: ציון אמון שמתעדכן לפי קבלה או דחייה בסקירה ודועך עם הזמן,
+//! ודוח מונחים שדורשים בדיקה של המינוחן
+
+use std::path::Path;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::technical_dictionary::{TechnicalTerm, TermMetadata};
+
+/// הציון שאליו דועך האמון במונח שאינו בשימוש
+pub const NEUTRAL_CONFIDENCE: f64 = 0.5;
+/// זמן מחצית החיים של עדויות השימוש, בימים
+pub const CONFIDENCE_HALF_LIFE_DAYS: f64 = 180.0;
+
+// משקל של שימוש בודד בעדכון הציון
+const LEARNING_RATE: f64 = 0.1;
+// דחייה על ידי סוקר שוקלת פי שניים מקבלה
+const REJECTION_WEIGHT: f64 = 2.0;
+
+/// תוצאת שימוש במונח בתרגום שנסקר
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UsageOutcome {
+    /// התרגום מהמילון נשאר בגרסה הסופית
+    Accepted,
+    /// הסוקר החליף את התרגום מהמילון
+    Rejected,
+}
+
+/// מוני השימוש של מונח
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageStats {
+    pub accepted: u64,
+    pub rejected: u64,
+    pub last_used: Option<DateTime<Utc>>,
+}
+
+impl UsageStats {
+    /// שיעור הדחיות מתוך כל השימושים שנסקרו
+    pub fn rejection_rate(&self) -> f64 {
+        let total = self.accepted + self.rejected;
+        if total == 0 {
+            0.0
+        } else {
+            self.rejected as f64 / total as f64
+        }
+    }
+}
+
+impl TermMetadata {
+    /// ציון האמון ברגע נתון: הציון השמור דועך לעבר הציון הניטרלי מאז השימוש האחרון
+    pub fn confidence_at(&self, now: DateTime<Utc>) -> f64 {
+        let since = self.usage.last_used.unwrap_or(self.last_updated);
+        let days = (now - since).num_seconds().max(0) as f64 / 86_400.0;
+        let factor = 0.5_f64.powf(days / CONFIDENCE_HALF_LIFE_DAYS);
+        NEUTRAL_CONFIDENCE + (self.confidence_score - NEUTRAL_CONFIDENCE) * factor
+    }
+
+    /// עדכון הציון והמונים לפי שימוש אחד; הדעיכה עד לרגע השימוש נשמרת בציון
+    pub fn record_usage(&mut self, outcome: UsageOutcome, now: DateTime<Utc>) {
+        let current = self.confidence_at(now);
+        let (target, weight) = match outcome {
+            UsageOutcome::Accepted => {
+                self.usage.accepted += 1;
+                (1.0, LEARNING_RATE)
+            }
+            UsageOutcome::Rejected => {
+                self.usage.rejected += 1;
+                (0.0, LEARNING_RATE * REJECTION_WEIGHT)
+            }
+        };
+        self.confidence_score = (current + weight * (target - current)).clamp(0.0, 1.0);
+        self.usage_count += 1;
+        self.usage.last_used = Some(now);
+    }
+}
+
+/// ספים לדוח השימוש
+#[derive(Debug, Clone)]
+pub struct UsageReportOptions {
+    /// מספר דחיות מינימלי למונח "מתוקן לעתים קרובות"
+    pub min_rejections: u64,
+    /// שיעור דחיות מינימלי למונח "מתוקן לעתים קרובות"
+    pub min_rejection_rate: f64,
+    /// מספר שימושים שמעליו מונח לא מאומת דורש בדיקה
+    pub high_usage: u64,
+}
+
+impl Default for UsageReportOptions {
+    fn default() -> Self {
+        Self {
+            min_rejections: 3,
+            min_rejection_rate: 0.3,
+            high_usage: 20,
+        }
+    }
+}
+
+/// שורה בדוח השימוש
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageReportEntry {
+    pub source: String,
+    pub target: String,
+    pub domain: String,
+    pub usage_count: u64,
+    pub accepted: u64,
+    pub rejected: u64,
+    pub rejection_rate: f64,
+    pub confidence: f64,
+    pub verified: bool,
+    pub last_used: Option<DateTime<Utc>>,
+}
+
+impl UsageReportEntry {
+    fn new(term: &TechnicalTerm, now: DateTime<Utc>) -> Self {
+        let metadata = &term.metadata;
+        Self {
+            source: term.source.clone(),
+            target: term.target.clone(),
+            domain: term.domain.clone(),
+            usage_count: metadata.usage_count,
+            accepted: metadata.usage.accepted,
+            rejected: metadata.usage.rejected,
+            rejection_rate: metadata.usage.rejection_rate(),
+            confidence: metadata.confidence_at(now),
+            verified: metadata.verified,
+            last_used: metadata.usage.last_used,
+        }
+    }
+}
+
+/// מונחים לבדיקת המינוחן
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TermUsageReport {
+    pub generated_at: DateTime<Utc>,
+    /// מונחים שמעולם לא שימשו בתרגום
+    pub never_used: Vec<UsageReportEntry>,
+    /// מונחים שסוקרים מחליפים לעתים קרובות, מהשיעור הגבוה לנמוך
+    pub frequently_rejected: Vec<UsageReportEntry>,
+    /// מונחים לא מאומתים שנמצאים בשימוש רב, מהשימוש הרב לפחות
+    pub unverified_high_usage: Vec<UsageReportEntry>,
+}
+
+impl TermUsageReport {
+    pub fn build(terms: &[TechnicalTerm], options: &UsageReportOptions, now: DateTime<Utc>) -> Self {
+        let mut report = Self {
+            generated_at: now,
+            never_used: Vec::new(),
+            frequently_rejected: Vec::new(),
+            unverified_high_usage: Vec::new(),
+        };
+
+        for term in terms {
+            let metadata = &term.metadata;
+            if metadata.usage_count == 0 {
+                report.never_used.push(UsageReportEntry::new(term, now));
+                continue;
+            }
+            if metadata.usage.rejected >= options.min_rejections
+                && metadata.usage.rejection_rate() >= options.min_rejection_rate
+            {
+                report.frequently_rejected.push(UsageReportEntry::new(term, now));
+            }
+            if !metadata.verified && metadata.usage_count >= options.high_usage {
+                report.unverified_high_usage.push(UsageReportEntry::new(term, now));
+            }
+        }
+
+        report.never_used.sort_by(|a, b| a.source.cmp(&b.source));
+        report.frequently_rejected.sort_by(|a, b| {
+            b.rejection_rate
+                .partial_cmp(&a.rejection_rate)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| b.rejected.cmp(&a.rejected))
+        });
+        report.unverified_high_usage.sort_by(|a, b| b.usage_count.cmp(&a.usage_count));
+        report
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.never_used.is_empty() && self.frequently_rejected.is_empty() && self.unverified_high_usage.is_empty()
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} מונחים ללא שימוש, {} מונחים שמתוקנים לעתים קרובות, {} מונחים לא מאומתים בשימוש רב",
+            self.never_used.len(),
+            self.frequently_rejected.len(),
+            self.unverified_high_usage.len()
+        )
+    }
+
+    /// גיליון אחד לכל הקטגוריות, עם עמודת קטגוריה לסינון
+    pub fn save_csv(&self, path: &Path) -> Result<()> {
+        let mut writer = csv::Writer::from_path(path)
+            .with_context(|| format!("יצירת דוח השימוש נכשלה: {}", path.display()))?;
+
+        writer.write_record([
+            "category", "source", "target", "domain", "usage_count",
+            "accepted", "rejected", "confidence", "verified", "last_used",
+        ])?;
+        let sections = [
+            ("never_used", &self.never_used),
+            ("frequently_rejected", &self.frequently_rejected),
+            ("unverified_high_usage", &self.unverified_high_usage),
+        ];
+        for (category, entries) in sections {
+            for entry in entries {
+                writer.write_record(&[
+                    category.to_string(),
+                    entry.source.clone(),
+                    entry.target.clone(),
+                    entry.domain.clone(),
+                    entry.usage_count.to_string(),
+                    entry.accepted.to_string(),
+                    entry.rejected.to_string(),
+                    format!("{:.2}", entry.confidence),
+                    entry.verified.to_string(),
+                    entry.last_used.map(|time| time.to_rfc3339()).unwrap_or_default(),
+                ])?;
+            }
+        }
+        writer
+            .flush()
+            .with_context(|| format!("כתיבת דוח השימוש נכשלה: {}", path.display()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn term(source: &str, verified: bool, last_updated: DateTime<Utc>) -> TechnicalTerm {
+        let mut term = TechnicalTerm::for_test(source, &format!("{}-ru", source), "general");
+        term.metadata.verified = verified;
+        term.metadata.last_updated = last_updated;
+        term
+    }
+
+    #[test]
+    fn test_confidence_decays_toward_neutral() {
+        let now = Utc::now();
+        let metadata = term("מגוף", true, now).metadata;
+        assert!((metadata.confidence_at(now) - 0.9).abs() < 1e-9);
+
+        let later = now + Duration::days(CONFIDENCE_HALF_LIFE_DAYS as i64);
+        assert!((metadata.confidence_at(later) - 0.7).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_rejections_outweigh_acceptances() {
+        let now = Utc::now();
+        let mut metadata = term("מגוף", true, now).metadata;
+        metadata.record_usage(UsageOutcome::Accepted, now);
+        let accepted = metadata.confidence_score;
+        assert!(accepted > 0.9);
+
+        metadata.record_usage(UsageOutcome::Rejected, now);
+        assert!(metadata.confidence_score < 0.9);
+        assert_eq!(metadata.usage_count, 2);
+        assert_eq!((metadata.usage.accepted, metadata.usage.rejected), (1, 1));
+    }
+
+    #[test]
+    fn test_usage_report_categories() {
+        let now = Utc::now();
+        let unused = term("ברז", true, now);
+
+        let mut rejected = term("מגוף", true, now);
+        for outcome in [UsageOutcome::Rejected, UsageOutcome::Accepted, UsageOutcome::Rejected, UsageOutcome::Rejected] {
+            rejected.metadata.record_usage(outcome, now);
+        }
+
+        let mut popular = term("צנרת", false, now);
+        for _ in 0..25 {
+            popular.metadata.record_usage(UsageOutcome::Accepted, now);
+        }
+
+        let report = TermUsageReport::build(&[unused, rejected, popular], &UsageReportOptions::default(), now);
+        assert_eq!(report.never_used[0].source, "ברז");
+        assert_eq!(report.frequently_rejected.len(), 1);
+        assert_eq!(report.frequently_rejected[0].rejected, 3);
+        assert_eq!(report.unverified_high_usage[0].source, "צנרת");
+    }
+}
@@ -0,0 +1,369 @@
+use crate::language::Language;
+use crate::morphology::utils::{is_hebrew_prefix, strip_niqqud, MAX_HEBREW_PREFIXES};
+use crate::translation::{SegmentTranslation, TranslationOrigin, PostEdit};
+use anyhow::{Result, Context};
+use serde::{Serialize, Deserialize};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// שלב בעיבוד של מילה בודדת מטקסט המקור
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TraceStep {
+    /// נרמול: הסרת פיסוק וניקוד, אותיות קטנות
+    Normalized { before: String, after: String },
+    /// הניתוח המורפולוגי שנבחר: תחיליות שהוסרו וצורת הבסיס
+    Morphology { prefixes: String, stem: String, candidates: Vec<String> },
+    /// המילה נמצאת בתוך קטע מוגן
+    Protected { rule: String, span: String },
+    /// חיפוש בשכבת מילון או כללים, עם הצורה שנבדקה
+    Lookup { layer: String, form: String, result: Option<String> },
+    /// תרגום המילה שנמצא בפלט
+    Aligned { target: String },
+    /// בחירת משמעות של מונח רב-משמעי לפי תחום
+    Sense { term: String, chosen: String, domain: String },
+    /// תרגום עקיף: הטקסט בשפת הציר
+    Pivot { via: Language, text: String },
+    /// פועל הוראה שתורגם בדרך הניסוח של המסמך
+    Instruction { verb: String, target: String, mood: String },
+}
+
+/// מעקב אחר מילה אחת במקטע
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenTrace {
+    pub index: usize,
+    pub token: String,
+    /// מיקום בבתים בתוך המקטע
+    pub start: usize,
+    pub end: usize,
+    pub steps: Vec<TraceStep>,
+}
+
+impl TokenTrace {
+    /// האם נמצא תרגום כלשהו למילה באחת השכבות
+    pub fn has_hit(&self) -> bool {
+        self.steps
+            .iter()
+            .any(|step| matches!(step, TraceStep::Lookup { result: Some(_), .. }))
+    }
+}
+
+/// מאפייני היצירה של מקטע, כגון שפת ציר ודרך ניסוח
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenerationFeature {
+    pub name: String,
+    pub value: String,
+}
+
+/// מעקב אחר מקטע מתורגם
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentTrace {
+    pub index: usize,
+    pub source: String,
+    pub target: String,
+    pub origin: TranslationOrigin,
+    pub tokens: Vec<TokenTrace>,
+    /// החלטות שלב ההעברה, כפי שנרשמו בזמן התרגום
+    pub decisions: Vec<TraceStep>,
+    pub generation: Vec<GenerationFeature>,
+    pub post_edits: Vec<PostEdit>,
+}
+
+/// מעקב מלא אחר תרגום, לניפוי כללים על ידי בלשנים
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationTrace {
+    pub source_lang: Language,
+    pub target_lang: Language,
+    pub segments: Vec<SegmentTrace>,
+}
+
+impl TranslationTrace {
+    pub fn new(source_lang: Language, target_lang: Language) -> Self {
+        Self {
+            source_lang,
+            target_lang,
+            segments: Vec::new(),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("המרת המעקב ל-JSON נכשלה")
+    }
+
+    pub fn save_json<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        fs::write(&path, self.to_json()?)
+            .with_context(|| format!("שמירת המעקב נכשלה: {}", path.as_ref().display()))
+    }
+
+    /// מילים שלא נמצא להן תרגום באף שכבה
+    pub fn unmatched_tokens(&self) -> Vec<(usize, &TokenTrace)> {
+        self.segments
+            .iter()
+            .flat_map(|segment| {
+                segment
+                    .tokens
+                    .iter()
+                    .filter(|token| !token.has_hit())
+                    .map(move |token| (segment.index, token))
+            })
+            .collect()
+    }
+
+    /// דוח קריא: מקטע אחר מקטע, מילה אחר מילה
+    pub fn render_report(&self) -> String {
+        let mut report = String::new();
+        let _ = writeln!(report, "מעקב תרגום {} -> {}", self.source_lang, self.target_lang);
+
+        for segment in &self.segments {
+            let _ = writeln!(report);
+            let _ = writeln!(report, "מקטע {}: {}", segment.index + 1, segment.source);
+            let _ = writeln!(report, "  תרגום: {}", segment.target);
+            let _ = writeln!(report, "  מקור: {}", describe_origin(&segment.origin));
+
+            for step in &segment.decisions {
+                let _ = writeln!(report, "  החלטה: {}", describe_step(step));
+            }
+            for token in &segment.tokens {
+                let _ = writeln!(report, "  [{}] {} ({}..{})", token.index, token.token, token.start, token.end);
+                for step in &token.steps {
+                    let _ = writeln!(report, "      {}", describe_step(step));
+                }
+            }
+
+            for feature in &segment.generation {
+                let _ = writeln!(report, "  יצירה: {} = {}", feature.name, feature.value);
+            }
+            for edit in &segment.post_edits {
+                let _ = writeln!(report, "  עריכה ({}): {} -> {}", edit.stage, edit.before, edit.after);
+            }
+        }
+
+        report
+    }
+}
+
+fn describe_origin(origin: &TranslationOrigin) -> String {
+    match origin {
+        TranslationOrigin::Glossary { name } => format!("מילון פרויקט {}", name),
+        TranslationOrigin::TechnicalDictionary => "מילון טכני".to_string(),
+        TranslationOrigin::TermsDatabase => "מסד מונחים".to_string(),
+        TranslationOrigin::Standard => "תקן".to_string(),
+        TranslationOrigin::Rule => "כלל ביטוי".to_string(),
+        TranslationOrigin::Protected => "קטע מוגן".to_string(),
+        TranslationOrigin::Sense { domain } => format!("בחירת משמעות לפי תחום {}", domain),
+        TranslationOrigin::Pivot { via, first, second } => format!(
+            "דרך {} ({} / {})",
+            via,
+            describe_origin(first),
+            describe_origin(second)
+        ),
+        TranslationOrigin::Untranslated => "לא תורגם".to_string(),
+    }
+}
+
+fn describe_step(step: &TraceStep) -> String {
+    match step {
+        TraceStep::Normalized { before, after } => format!("נרמול: {} -> {}", before, after),
+        TraceStep::Morphology { prefixes, stem, candidates } => format!(
+            "מורפולוגיה: תחיליות '{}' בסיס '{}' (אפשרויות: {})",
+            prefixes,
+            stem,
+            candidates.join(", ")
+        ),
+        TraceStep::Protected { rule, span } => format!("מוגן ({}): {}", rule, span),
+        TraceStep::Lookup { layer, form, result } => match result {
+            Some(result) => format!("חיפוש {} '{}': {}", layer, form, result),
+            None => format!("חיפוש {} '{}': לא נמצא", layer, form),
+        },
+        TraceStep::Aligned { target } => format!("בפלט: {}", target),
+        TraceStep::Sense { term, chosen, domain } => format!("משמעות '{}': {} (תחום {})", term, chosen, domain),
+        TraceStep::Pivot { via, text } => format!("ציר {}: {}", via, text),
+        TraceStep::Instruction { verb, target, mood } => format!("פועל הוראה {} -> {} ({})", verb, target, mood),
+    }
+}
+
+/// פיצול מקטע למילים עם מיקומן
+pub fn tokenize(text: &str) -> Vec<(usize, &str)> {
+    text.split_whitespace()
+        .map(|word| (word.as_ptr() as usize - text.as_ptr() as usize, word))
+        .collect()
+}
+
+/// נרמול מילה לחיפוש: הסרת פיסוק מהקצוות, ניקוד וטעמים, ואותיות קטנות
+pub fn normalize_token(token: &str) -> String {
+    strip_niqqud(token.trim_matches(|c: char| !c.is_alphanumeric())).to_lowercase()
+}
+
+/// ניתוחים אפשריים של מילה עברית: (תחיליות, בסיס), מהבסיס הארוך לקצר
+pub fn hebrew_analyses(word: &str) -> Vec<(String, String)> {
+    let mut analyses = vec![(String::new(), word.to_string())];
+    let mut prefixes = String::new();
+
+    for (count, c) in word.chars().enumerate() {
+        if count >= MAX_HEBREW_PREFIXES || !is_hebrew_prefix(c) {
+            break;
+        }
+        prefixes.push(c);
+        let stem = &word[prefixes.len()..];
+        // בסיס של אות אחת אינו ניתוח סביר
+        if stem.chars().count() < 2 {
+            break;
+        }
+        analyses.push((prefixes.clone(), stem.to_string()));
+    }
+
+    analyses
+}
+
+/// בניית מעקב למקטע מתוך ההחלטות שנרשמו בזמן תרגומו.
+/// לכל מילה מצורפים החיפושים שנעשו על צורה שהמילה (או אחד מניתוחיה) היא חלק ממנה
+pub fn trace_segment(
+    index: usize,
+    segment: &SegmentTranslation,
+    source: Language,
+    generation: Vec<GenerationFeature>,
+) -> SegmentTrace {
+    let lookups: Vec<(&TraceStep, Vec<String>)> = segment
+        .decisions
+        .iter()
+        .filter_map(|step| match step {
+            TraceStep::Lookup { form, .. } => Some((step, form.split_whitespace().map(normalize_token).collect())),
+            _ => None,
+        })
+        .collect();
+    let mut tokens = Vec::new();
+
+    for (token_index, (start, token)) in tokenize(&segment.source).into_iter().enumerate() {
+        let end = start + token.len();
+        let mut steps = Vec::new();
+
+        let normalized = normalize_token(token);
+        if normalized != token {
+            steps.push(TraceStep::Normalized {
+                before: token.to_string(),
+                after: normalized.clone(),
+            });
+        }
+
+        // קטעים מוגנים נשמרים כלשונם ואינם נבדקים במילונים
+        if let Some(span) = segment.protected_spans.iter().find(|s| s.start < end && start < s.end) {
+            steps.push(TraceStep::Protected {
+                rule: span.rule.clone(),
+                span: span.text.clone(),
+            });
+            tokens.push(TokenTrace { index: token_index, token: token.to_string(), start, end, steps });
+            continue;
+        }
+
+        // הניתוח הנבחר הוא הראשון שהופיע בחיפוש שהצליח, אחרת הראשון שנבדק בכלל
+        let analyses = if source == Language::Hebrew {
+            hebrew_analyses(&normalized)
+        } else {
+            vec![(String::new(), normalized.clone())]
+        };
+        let searched = |stem: &str, hits_only: bool| {
+            lookups.iter().any(|(step, words)| {
+                words.iter().any(|w| w == stem)
+                    && (!hits_only || matches!(step, TraceStep::Lookup { result: Some(_), .. }))
+            })
+        };
+        let (prefixes, stem) = analyses
+            .iter()
+            .find(|(_, stem)| searched(stem, true))
+            .or_else(|| analyses.iter().find(|(_, stem)| searched(stem, false)))
+            .cloned()
+            .unwrap_or_else(|| (String::new(), normalized.clone()));
+
+        if analyses.len() > 1 {
+            steps.push(TraceStep::Morphology {
+                prefixes,
+                stem: stem.clone(),
+                candidates: analyses.iter().map(|(_, stem)| stem.clone()).collect(),
+            });
+        }
+
+        for (step, words) in &lookups {
+            if !words.contains(&stem) {
+                continue;
+            }
+            if let TraceStep::Lookup { result: Some(found), .. } = step {
+                if segment.target.contains(found.as_str())
+                    && !steps.iter().any(|s| matches!(s, TraceStep::Aligned { .. }))
+                {
+                    steps.push(TraceStep::Aligned { target: found.clone() });
+                }
+            }
+            steps.push((*step).clone());
+        }
+
+        tokens.push(TokenTrace { index: token_index, token: token.to_string(), start, end, steps });
+    }
+
+    // עריכות של אכיפת מונחים נרשמות יחד עם עריכות שלב היצירה
+    let mut post_edits = Vec::new();
+    for issue in &segment.glossary_issues {
+        if let crate::glossary::GlossaryIssueKind::Enforced { replaced } = &issue.kind {
+            post_edits.push(PostEdit {
+                stage: format!("glossary:{}", issue.glossary),
+                before: replaced.clone(),
+                after: issue.required_target.clone(),
+            });
+        }
+    }
+    post_edits.extend(segment.post_edits.iter().cloned());
+
+    SegmentTrace {
+        index,
+        source: segment.source.clone(),
+        target: segment.target.clone(),
+        origin: segment.origin.clone(),
+        tokens,
+        decisions: segment.decisions.clone(),
+        generation,
+        post_edits,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_and_analyses() {
+        assert_eq!(normalize_token("\"הַלחץ,"), "הלחץ");
+        assert_eq!(normalize_token("Давление."), "давление");
+
+        let stems: Vec<String> = hebrew_analyses("בצנרת").into_iter().map(|(_, stem)| stem).collect();
+        assert_eq!(stems, vec!["בצנרת", "צנרת"]);
+    }
+
+    #[test]
+    fn test_trace_segment() {
+        let segment = SegmentTranslation {
+            source: "בדיקת הלחץ".to_string(),
+            target: "проверка давления".to_string(),
+            origin: TranslationOrigin::Rule,
+            pivot_text: None,
+            glossary_issues: Vec::new(),
+            protected_spans: Vec::new(),
+            post_edits: Vec::new(),
+            sense_decisions: Vec::new(),
+            standard_warnings: Vec::new(),
+            footnotes: Vec::new(),
+            decisions: vec![
+                TraceStep::Lookup { layer: "rules".to_string(), form: "בדיקת הלחץ".to_string(), result: None },
+                TraceStep::Lookup { layer: "rules".to_string(), form: "לחץ".to_string(), result: Some("давления".to_string()) },
+            ],
+        };
+        let trace = trace_segment(0, &segment, Language::Hebrew, Vec::new());
+
+        let token = &trace.tokens[1];
+        assert!(token.has_hit());
+        assert!(token.steps.contains(&TraceStep::Aligned { target: "давления".to_string() }));
+        assert!(matches!(&token.steps[0], TraceStep::Morphology { prefixes, .. } if prefixes == "ה"));
+        // המילה הראשונה נבדקה רק כחלק מהמקטע כולו, ללא הצלחה
+        assert!(!trace.tokens[0].has_hit());
+        assert_eq!(trace.tokens[0].steps.len(), 1);
+        assert_eq!(trace.decisions.len(), 2);
+    }
+}
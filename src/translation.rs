@@ -1,16 +1,190 @@
 use crate::technical_terms::TermsDatabase;
-use crate::standards::StandardsDatabase;
+use crate::standards::{EditionWarning, StandardsDatabase};
+use crate::clause_references::{self, TitleMode};
 use crate::technical_dictionary::TechnicalDictionary;
-use whatlang::detect;
+use crate::language::Language;
+use crate::glossary::{GlossaryStack, GlossaryIssue};
+use crate::protection::{ProtectionRules, ProtectedSpan};
+use crate::translation_models::{self, TranslationContext, MoodNormalizer};
+use crate::sense::{SenseDisambiguator, SenseCandidate, SenseContext, SenseDecision};
+use crate::trace::{self, TranslationTrace, TraceStep, GenerationFeature};
+use crate::quality_control::{QualityControl, ValidationReport};
 use anyhow::{Result, Context};
-use rust_bert::pipelines::translation::{Language, TranslationModelBuilder};
+use rust_bert::pipelines::translation::TranslationModelBuilder;
+use serde::{Serialize, Deserialize};
 use std::sync::Arc;
 use std::sync::Mutex;
 
+// כללי תרגום לביטויים נפוצים: עברית, רוסית, אנגלית
+const PHRASE_RULES: &[(&str, &str, &str)] = &[
+    ("תקין", "исправен", "serviceable"),
+    ("לא נמצאו", "не обнаружены", "none found"),
+    ("נדרש ניקוי שנתי", "требуется ежегодная очистка", "annual cleaning required"),
+    ("בוצע כיול שנתי", "выполнена ежегодная калибровка", "annual calibration performed"),
+    ("צביעה מחדש נדרשת בעוד כשנה", "перекраска требуется через год", "repainting required in about a year"),
+    ("מערכת ספרינקלרים אוטומטית", "автоматическая спринклерная система", "automatic sprinkler system"),
+    ("תל אביב", "Тель-Авив", "Tel Aviv"),
+    ("ישראל ישראלי", "Исраэль Исраэли", "Israel Israeli"),
+];
+
+/// מקור התרגום של מקטע
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TranslationOrigin {
+    /// מילון פרויקט, לפי שמו
+    Glossary { name: String },
+    TechnicalDictionary,
+    TermsDatabase,
+    Standard,
+    Rule,
+    /// המקטע כולו מוגן ועבר ללא שינוי
+    Protected,
+    /// מונח רב-משמעי; המשמעות נבחרה לפי התחום
+    Sense { domain: String },
+    /// תרגום עקיף דרך שפת ציר, עם מקור כל אחד משני השלבים
+    Pivot {
+        via: Language,
+        first: Box<TranslationOrigin>,
+        second: Box<TranslationOrigin>,
+    },
+    /// לא נמצא תרגום והטקסט הוחזר כמות שהוא
+    Untranslated,
+}
+
+/// תרגום של מקטע בודד יחד עם מקורו
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentTranslation {
+    pub source: String,
+    pub target: String,
+    pub origin: TranslationOrigin,
+    /// הטקסט בשפת הציר, כאשר התרגום עקיף
+    pub pivot_text: Option<String>,
+    /// אכיפת מונחים מחייבים ממילוני הפרויקט
+    #[serde(default)]
+    pub glossary_issues: Vec<GlossaryIssue>,
+    /// קטעים שסומנו כמוגנים ועברו כלשונם
+    #[serde(default)]
+    pub protected_spans: Vec<ProtectedSpan>,
+    /// עריכות שבוצעו על התרגום בשלב היצירה
+    #[serde(default)]
+    pub post_edits: Vec<PostEdit>,
+    /// החלטות בחירת משמעות למונחים רב-משמעיים
+    #[serde(default)]
+    pub sense_decisions: Vec<SenseDecision>,
+    /// ציוני מהדורות תקן שהוחלפו או בוטלו
+    #[serde(default)]
+    pub standard_warnings: Vec<EditionWarning>,
+    /// הערות שוליים עם כותרות הסעיפים שאליהם מפנה המקטע
+    #[serde(default)]
+    pub footnotes: Vec<String>,
+    /// החלטות שלב ההעברה לפי סדרן: חיפושים בכל שכבה, קטעים מוגנים, בחירת משמעות וציר
+    #[serde(default)]
+    pub decisions: Vec<TraceStep>,
+}
+
+/// עריכה שבוצעה על מקטע לאחר שלב ההעברה
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostEdit {
+    pub stage: String,
+    pub before: String,
+    pub after: String,
+}
+
+impl SegmentTranslation {
+    fn untranslated(text: &str) -> Self {
+        Self {
+            source: text.to_string(),
+            target: text.to_string(),
+            origin: TranslationOrigin::Untranslated,
+            pivot_text: None,
+            glossary_issues: Vec::new(),
+            protected_spans: Vec::new(),
+            post_edits: Vec::new(),
+            sense_decisions: Vec::new(),
+            standard_warnings: Vec::new(),
+            footnotes: Vec::new(),
+            decisions: Vec::new(),
+        }
+    }
+}
+
+/// תוצאת תרגום מלאה עם מקור לכל מקטע
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationOutput {
+    pub text: String,
+    pub source_lang: Language,
+    pub target_lang: Language,
+    pub segments: Vec<SegmentTranslation>,
+    /// כותרות הסעיפים שהוצגו כהערות שוליים, ממוספרות ברצף לאורך המסמך
+    #[serde(default)]
+    pub footnotes: Vec<String>,
+}
+
+impl TranslationOutput {
+    pub fn new(source_lang: Language, target_lang: Language) -> Self {
+        Self {
+            text: String::new(),
+            source_lang,
+            target_lang,
+            segments: Vec::new(),
+            footnotes: Vec::new(),
+        }
+    }
+
+    /// מוסיף מקטע מתורגם כמשפט בסוף הטקסט המצטבר
+    pub fn push_sentence(&mut self, mut segment: SegmentTranslation) {
+        self.number_footnotes(&mut segment);
+        if !self.text.is_empty() {
+            self.text.push(' ');
+        }
+        self.text.push_str(&segment.target);
+        self.text.push('.');
+        self.segments.push(segment);
+    }
+
+    // סימוני ההערות במקטע ממוספרים מחדש אחרי ההערות של המקטעים הקודמים
+    fn number_footnotes(&mut self, segment: &mut SegmentTranslation) {
+        if segment.footnotes.is_empty() {
+            return;
+        }
+        segment.target = clause_references::renumber_footnotes(&segment.target, segment.footnotes.len(), self.footnotes.len());
+        self.footnotes.extend(segment.footnotes.iter().cloned());
+    }
+
+    /// כל מקרי האכיפה של מונחים מחייבים, עם מספר המקטע
+    pub fn glossary_issues(&self) -> Vec<(usize, &GlossaryIssue)> {
+        self.segments
+            .iter()
+            .enumerate()
+            .flat_map(|(index, segment)| segment.glossary_issues.iter().map(move |issue| (index, issue)))
+            .collect()
+    }
+
+    /// אזהרות על מהדורות תקן שאינן בתוקף, עם מספר המקטע
+    pub fn standard_warnings(&self) -> Vec<(usize, &EditionWarning)> {
+        self.segments
+            .iter()
+            .enumerate()
+            .flat_map(|(index, segment)| segment.standard_warnings.iter().map(move |warning| (index, warning)))
+            .collect()
+    }
+
+    /// האם מקטע כלשהו תורגם דרך שפת ציר
+    pub fn used_pivot(&self) -> bool {
+        self.segments
+            .iter()
+            .any(|s| matches!(s.origin, TranslationOrigin::Pivot { .. }))
+    }
+}
+
 pub struct Translator {
     terms_db: Arc<TermsDatabase>,
     standards_db: Arc<StandardsDatabase>,
     technical_dictionary: Arc<Mutex<TechnicalDictionary>>,
+    glossaries: GlossaryStack,
+    protection: ProtectionRules,
+    mood: Option<MoodNormalizer>,
+    senses: SenseDisambiguator,
+    clause_titles: TitleMode,
     model: Option<TranslationModelBuilder>,
 }
 
@@ -24,20 +198,27 @@ impl Translator {
             terms_db,
             standards_db,
             technical_dictionary,
+            glossaries: GlossaryStack::new(),
+            protection: ProtectionRules::with_builtin_patterns(),
+            mood: None,
+            senses: SenseDisambiguator::new(),
+            clause_titles: TitleMode::default(),
             model: None,
         }
     }
 
+    /// מילוני פרויקט שגוברים על המילון הכללי
+    pub fn with_glossaries(mut self, glossaries: GlossaryStack) -> Self {
+        self.glossaries = glossaries;
+        self
+    }
+
     pub fn translate(&self, text: &str) -> Result<String> {
         // זיהוי שפת המקור
-        let lang_info = detect(text)
+        let source = Language::detect(text)
             .context("לא ניתן לזהות את שפת המקור")?;
-        
-        match lang_info.lang().code() {
-            "he" => self.translate_with_languages(text, "he", "ru"),
-            "ru" => self.translate_with_languages(text, "ru", "he"),
-            _ => anyhow::bail!("שפה לא נתמכת: {}", lang_info.lang().code()),
-        }
+
+        self.translate_with_languages(text, source.code(), source.default_target().code())
     }
 
     pub fn translate_with_languages(
@@ -46,95 +227,537 @@ impl Translator {
         source_lang: &str,
         target_lang: &str,
     ) -> Result<String> {
-        // בדיקה במילון הטכני
-        if let Ok(dict) = self.technical_dictionary.lock() {
-            let translated = dict.translate(text, source_lang, target_lang);
-            if translated != text {
-                return Ok(translated);
-            }
+        Ok(self.translate_detailed(text, source_lang, target_lang)?.text)
+    }
+
+    /// תרגום עם פירוט מקור התרגום של כל מקטע
+    pub fn translate_detailed(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+    ) -> Result<TranslationOutput> {
+        let (source, target) = Self::resolve_languages(source_lang, target_lang)?;
+        let mut output = TranslationOutput::new(source, target);
+        let document_context = self.sense_context(text);
+
+        // בדיקה אם הטקסט כולו הוא מונח, תקן או ביטוי מוכר
+        let whole = document_context.with_neighbours(&[text], 0);
+        let mut segment = self.translate_masked(text.trim(), source, target, &whole);
+        if segment.origin != TranslationOrigin::Untranslated {
+            self.finish_segment(&mut segment, source, target);
+            output.number_footnotes(&mut segment);
+            output.text = segment.target.clone();
+            output.segments.push(segment);
+            return Ok(output);
         }
-        
-        // בדיקה אם מדובר במונח טכני ממסד הנתונים הקבוע
-        if let Some(term) = self.terms_db.get_translation(text) {
-            return Ok(term.to_string());
+
+        let sentences = self.split_segments(text);
+        for (index, sentence) in sentences.iter().enumerate() {
+            let context = document_context.with_neighbours(&sentences, index);
+            let segment = self.translate_segment_in(sentence, source, target, &context);
+            output.push_sentence(segment);
         }
-        
-        // בדיקה אם מדובר בתקן
-        if let Some(standard) = self.standards_db.get_standard_by_code(text) {
-            return Ok(standard.name.to_string());
-        }
-        
-        // פיצול הטקסט למשפטים
-        let sentences: Vec<&str> = text.split(['.', '!', '?', '\n'])
-            .filter(|s| !s.trim().is_empty())
-            .collect();
-        
-        let mut translated = String::new();
-        
-        for sentence in sentences {
-            // ניקוי רווחים מיותרים
-            let sentence = sentence.trim();
-            if sentence.is_empty() {
+
+        Ok(output)
+    }
+
+    /// תרגום במצב מעקב: לכל מילה במקור נרשמים הנרמול, הניתוח, ההגנה והחיפושים בכל שכבה
+    pub fn translate_traced(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+    ) -> Result<(TranslationOutput, TranslationTrace)> {
+        let output = self.translate_detailed(text, source_lang, target_lang)?;
+        let (source, target) = (output.source_lang, output.target_lang);
+        let mut trace = TranslationTrace::new(source, target);
+
+        for (index, segment) in output.segments.iter().enumerate() {
+            let generation = self.generation_features(segment, target);
+            trace.segments.push(trace::trace_segment(index, segment, source, generation));
+        }
+
+        Ok((output, trace))
+    }
+
+    // מאפייני שלב היצירה של מקטע, לצורך המעקב
+    fn generation_features(&self, segment: &SegmentTranslation, target: Language) -> Vec<GenerationFeature> {
+        let mut features = vec![GenerationFeature {
+            name: "direction".to_string(),
+            value: if target.is_rtl() { "rtl" } else { "ltr" }.to_string(),
+        }];
+        if let Some(pivot) = &segment.pivot_text {
+            features.push(GenerationFeature {
+                name: "pivot_text".to_string(),
+                value: pivot.clone(),
+            });
+        }
+        if let Some(mood) = self.mood.as_ref().and_then(|m| m.policy().mood_for(target)) {
+            features.push(GenerationFeature {
+                name: "instruction_mood".to_string(),
+                value: format!("{:?}", mood),
+            });
+        }
+        for decision in &segment.sense_decisions {
+            features.push(GenerationFeature {
+                name: format!("sense:{}", decision.term),
+                value: format!("{} ({})", decision.chosen, decision.evidence.join("; ")),
+            });
+        }
+        features
+    }
+
+    /// בקרת איכות לתרגום שהושלם: בדיקות הטקסט המתורגם, ובנוסף קטעים מוגנים,
+    /// מונחים מיושנים או אסורים וציון מהדורות תקן מול טקסט המקור
+    pub async fn quality_check(
+        &self,
+        quality_control: &QualityControl,
+        source_text: &str,
+        output: &TranslationOutput,
+    ) -> Result<ValidationReport> {
+        let mut report = quality_control.validate_deep(&output.text).await?;
+        quality_control.validate_protected_spans(source_text, &output.text, &self.protection, &mut report);
+        if let Ok(dictionary) = self.technical_dictionary.lock() {
+            quality_control.validate_term_usage(
+                source_text,
+                &output.text,
+                output.source_lang,
+                output.target_lang,
+                &dictionary,
+                &mut report,
+            );
+        }
+        quality_control.validate_standard_citations(source_text, &self.standards_db, &mut report);
+        Ok(report)
+    }
+
+    /// רשימות "לא לתרגם" וכללי הגנה של הפרויקט
+    pub fn with_protection(mut self, protection: ProtectionRules) -> Self {
+        self.protection = protection;
+        self
+    }
+
+    pub fn protection(&self) -> &ProtectionRules {
+        &self.protection
+    }
+
+    /// הקשר התרגום של המסמך: פורמליות וסגנון קובעים דרך ניסוח אחידה להוראות
+    pub fn with_context(mut self, context: &TranslationContext) -> Self {
+        self.mood = Some(MoodNormalizer::from_context(context));
+        self
+    }
+
+    /// הצגת כותרות הסעיפים מתוך מסד התקנים בהפניות שבתרגום
+    pub fn with_clause_titles(mut self, mode: TitleMode) -> Self {
+        self.clause_titles = mode;
+        self
+    }
+
+    /// המרת קודי שפה לזוג שפות נתמך
+    pub fn resolve_languages(source_lang: &str, target_lang: &str) -> Result<(Language, Language)> {
+        let source = Language::from_code(source_lang)
+            .with_context(|| format!("שפה לא נתמכת: {}", source_lang))?;
+        let target = Language::from_code(target_lang)
+            .with_context(|| format!("שפה לא נתמכת: {}", target_lang))?;
+        if source == target {
+            anyhow::bail!("צמד השפות לא נתמך: {} -> {}", source_lang, target_lang);
+        }
+        Ok((source, target))
+    }
+
+    /// פיצול הטקסט למקטעי תרגום (משפטים), ללא פיצול בתוך מספרים עשרוניים או קטעים מוגנים
+    pub fn split_segments<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        let spans = self.protection.find_spans(text);
+        let chars: Vec<(usize, char)> = text.char_indices().collect();
+        let mut segments = Vec::new();
+        let mut start = 0;
+
+        for (i, &(position, c)) in chars.iter().enumerate() {
+            if !matches!(c, '.' | '!' | '?' | '\n') {
                 continue;
             }
-            
-            // תרגום המשפט
-            let translated_sentence = match (source_lang, target_lang) {
-                ("he", "ru") => self.translate_he_to_ru(sentence)?,
-                ("ru", "he") => self.translate_ru_to_he(sentence)?,
-                _ => anyhow::bail!("צמד השפות לא נתמך: {} -> {}", source_lang, target_lang),
-            };
-            
-            // הוספת המשפט המתורגם
-            if !translated.is_empty() {
-                translated.push(' ');
+            let between_digits = c == '.'
+                && i > 0
+                && chars[i - 1].1.is_ascii_digit()
+                && chars.get(i + 1).map_or(false, |(_, next)| next.is_ascii_digit());
+            if between_digits || ProtectionRules::is_protected(&spans, position) {
+                continue;
+            }
+            segments.push(&text[start..position]);
+            start = position + c.len_utf8();
+        }
+        segments.push(&text[start..]);
+
+        segments
+            .into_iter()
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
+    /// ציוני התחום של המסמך כולו, לבחירת משמעות של מונחים רב-משמעיים
+    pub fn sense_context(&self, document: &str) -> SenseContext {
+        self.senses.document_context(document)
+    }
+
+    /// תרגום מקטע בודד; במקרה של טקסט לא מוכר, נחזיר את המקור
+    pub fn translate_segment(&self, text: &str, source: Language, target: Language) -> SegmentTranslation {
+        let context = self.sense_context(text).with_neighbours(&[text], 0);
+        self.translate_segment_in(text, source, target, &context)
+    }
+
+    /// תרגום מקטע בהקשר המסמך שבו הוא מופיע
+    pub fn translate_segment_in(
+        &self,
+        text: &str,
+        source: Language,
+        target: Language,
+        context: &SenseContext,
+    ) -> SegmentTranslation {
+        let mut segment = self.translate_masked(text, source, target, context);
+        self.finish_segment(&mut segment, source, target);
+        segment
+    }
+
+    // שלב היצירה: אכיפת מונחים ודרך ניסוח אחידה
+    fn finish_segment(&self, segment: &mut SegmentTranslation, source: Language, target: Language) {
+        self.enforce_glossaries(segment, source, target);
+        segment.standard_warnings = self.standards_db.edition_warnings(&segment.source);
+
+        // הפניות לסעיפים שנותרו בשפת המקור מוצגות בצורתן בשפת היעד
+        let localized = clause_references::localize_references(
+            &segment.target,
+            source,
+            target,
+            &self.standards_db,
+            self.clause_titles,
+        );
+        if localized.text != segment.target {
+            segment.post_edits.push(PostEdit {
+                stage: "clause_references".to_string(),
+                before: segment.target.clone(),
+                after: localized.text.clone(),
+            });
+            segment.target = localized.text;
+            segment.footnotes = localized.footnotes;
+        }
+
+        if let Some(mood) = &self.mood {
+            let (text, change) = mood.apply(&segment.target, target);
+            if let Some(change) = change {
+                segment.post_edits.push(PostEdit {
+                    stage: "instruction_mood".to_string(),
+                    before: change.before,
+                    after: change.after,
+                });
+                segment.target = text;
+            }
+        }
+    }
+
+    // הקטעים המוגנים מוחלפים בממלאי מקום לפני כל חיפוש, ומוחזרים כלשונם אחרי התרגום
+    fn translate_masked(
+        &self,
+        text: &str,
+        source: Language,
+        target: Language,
+        context: &SenseContext,
+    ) -> SegmentTranslation {
+        let mut log = Vec::new();
+        let masked = self.protection.mask(text);
+        if masked.spans.is_empty() {
+            let mut segment = self.translate_unit(text, source, target, context, &mut log)
+                .unwrap_or_else(|| SegmentTranslation::untranslated(text));
+            segment.decisions = log;
+            return segment;
+        }
+
+        for span in &masked.spans {
+            log.push(TraceStep::Protected { rule: span.rule.clone(), span: span.text.clone() });
+        }
+        let mut segment = if masked.is_fully_protected() {
+            let mut segment = SegmentTranslation::untranslated(text);
+            segment.origin = TranslationOrigin::Protected;
+            segment
+        } else {
+            let mut segment = self.translate_unit(&masked.text, source, target, context, &mut log)
+                .unwrap_or_else(|| SegmentTranslation::untranslated(&masked.text));
+            segment.source = text.to_string();
+            segment.target = masked.restore(&segment.target);
+            segment
+        };
+        segment.protected_spans = masked.spans;
+        segment.decisions = log;
+        segment
+    }
+
+    // במצב מונחים מחייבים: החלפת תרגומי המילון הכללי ודיווח על מונחים חסרים
+    fn enforce_glossaries(&self, segment: &mut SegmentTranslation, source: Language, target: Language) {
+        // מקטע שלא תורגם נשאר בשפת המקור ואין בו מה לאכוף
+        if self.glossaries.is_empty() || segment.origin == TranslationOrigin::Untranslated {
+            return;
+        }
+        segment.glossary_issues = self.glossaries.enforce(
+            &segment.source,
+            &mut segment.target,
+            source,
+            target,
+            |term| self.lookup_general(term, source, target, None, &mut Vec::new()).map(|(translated, _)| translated),
+        );
+    }
+
+    // כל החלטה נרשמת ב-`log` ברגע שהיא מתקבלת, כולל חיפושים שלא נמצאו, לצורך המעקב
+    fn translate_unit(
+        &self,
+        text: &str,
+        source: Language,
+        target: Language,
+        context: &SenseContext,
+        log: &mut Vec<TraceStep>,
+    ) -> Option<SegmentTranslation> {
+        // מונח רב-משמעי: בחירת משמעות לפי התחום, אלא אם מילון הפרויקט קובע אחרת
+        if self.glossaries.lookup(text, source, target).is_none() {
+            let candidates = self.sense_candidates(text, source, target);
+            if let Some(decision) = self.senses.disambiguate(text, &candidates, context) {
+                log.push(TraceStep::Sense {
+                    term: decision.term.clone(),
+                    chosen: decision.chosen.clone(),
+                    domain: decision.domain.clone(),
+                });
+                let mut segment = SegmentTranslation::untranslated(text);
+                segment.target = decision.chosen.clone();
+                segment.origin = TranslationOrigin::Sense { domain: decision.domain.clone() };
+                segment.sense_decisions.push(decision);
+                return Some(segment);
+            }
+        }
+
+        // מסד המונחים מעדיף את התרגום בתחום הבולט של המסמך
+        let domain = context.document_domain();
+        if let Some((translated, origin)) = self.lookup_direct(text, source, target, domain, log) {
+            let mut segment = SegmentTranslation::untranslated(text);
+            segment.target = translated;
+            segment.origin = origin;
+            return Some(segment);
+        }
+
+        // משפט הוראה: הפועל מתורגם בדרך הניסוח של המסמך והמושא בנפרד
+        if let Some(segment) = self.translate_instruction(text, source, target, context, log) {
+            return Some(segment);
+        }
+
+        // תרגום עקיף דרך אנגלית, רק כשאין מונח או כלל ישיר
+        if source == Language::PIVOT || target == Language::PIVOT {
+            return None;
+        }
+        let (pivot_text, first) = self.lookup_direct(text, source, Language::PIVOT, domain, log)?;
+        log.push(TraceStep::Pivot { via: Language::PIVOT, text: pivot_text.clone() });
+        let (translated, second) = self.lookup_direct(&pivot_text, Language::PIVOT, target, domain, log)?;
+
+        let mut segment = SegmentTranslation::untranslated(text);
+        segment.target = translated;
+        segment.origin = TranslationOrigin::Pivot {
+            via: Language::PIVOT,
+            first: Box::new(first),
+            second: Box::new(second),
+        };
+        segment.pivot_text = Some(pivot_text);
+        Some(segment)
+    }
+
+    // משמעויות אפשריות לפי תחום: מהמילון הטכני של המשתמש, ורק אם אין בו את המונח - ממסד המונחים.
+    // מונח שלמשתמש יש לו תרגום אחד אינו נחשב רב-משמעי
+    fn sense_candidates(&self, text: &str, source: Language, target: Language) -> Vec<SenseCandidate> {
+        let from_dictionary = self.technical_dictionary
+            .lock()
+            .map(|dict| dict.domain_translations(text, source, target))
+            .unwrap_or_default();
+
+        let translations: Vec<(String, String)> = if from_dictionary.is_empty() {
+            self.senses
+                .domains()
+                .filter_map(|domain| {
+                    self.terms_db
+                        .lookup(text, source, target, Some(domain))
+                        .into_iter()
+                        .find(|found| found.domain == domain)
+                        .map(|found| (found.text, found.domain))
+                })
+                .collect()
+        } else {
+            from_dictionary
+        };
+
+        let mut candidates: Vec<SenseCandidate> = Vec::new();
+        for (translated, domain) in translations {
+            if !candidates.iter().any(|c| c.target == translated) {
+                candidates.push(self.senses.candidate(&translated, &domain));
+            }
+        }
+        candidates
+    }
+
+    // שלב ההעברה של משפט הוראה: פועל ההוראה נבחר ישירות בדרך הניסוח של שפת היעד,
+    // ולא מתוקן אחרי היצירה
+    fn translate_instruction(
+        &self,
+        text: &str,
+        source: Language,
+        target: Language,
+        context: &SenseContext,
+        log: &mut Vec<TraceStep>,
+    ) -> Option<SegmentTranslation> {
+        let clause = translation_models::split_instruction(text, source)?;
+        let mood = self.mood
+            .as_ref()
+            .and_then(|mood| mood.policy().mood_for(target))
+            .unwrap_or(clause.mood);
+        let verb = clause.verb_in(target, mood)?;
+        log.push(TraceStep::Instruction {
+            verb: clause.verb.to_string(),
+            target: verb.to_string(),
+            mood: format!("{:?}", mood),
+        });
+
+        let rest = clause.rest.trim();
+        let object = match source {
+            Language::Hebrew => rest.strip_prefix("את ").unwrap_or(rest).trim(),
+            _ => rest,
+        };
+        let mut segment = if object.is_empty() {
+            let mut segment = SegmentTranslation::untranslated(text);
+            segment.origin = TranslationOrigin::Rule;
+            segment.target = String::new();
+            segment
+        } else {
+            let mut found = None;
+            for form in object_forms(object, source) {
+                found = self.translate_unit(&form, source, target, context, log);
+                if found.is_some() {
+                    break;
+                }
             }
-            translated.push_str(&translated_sentence);
-            translated.push('.');
-        }
-        
-        Ok(translated)
-    }
-
-    fn translate_he_to_ru(&self, text: &str) -> Result<String> {
-        // כאן יש להוסיף את הלוגיקה הספציפית לתרגום מעברית לרוסית
-        // לדוגמה, שימוש במודל נוירונים או שירות תרגום חיצוני
-        
-        // לצורך הדוגמה, נשתמש בתרגום פשוט
-        let translated = match text {
-            "תקין" => "исправен",
-            "לא נמצאו" => "не обнаружены",
-            "נדרש ניקוי שנתי" => "требуется ежегодная очистка",
-            "בוצע כיול שנתי" => "выполнена ежегодная калибровка",
-            "צביעה מחדש נדרשת בעוד כשנה" => "перекраска требуется через год",
-            "מערכת ספרינקלרים אוטומטית" => "автоматическая спринклерная система",
-            "תל אביב" => "Тель-Авив",
-            "ישראל ישראלי" => "Исраэль Исраэли",
-            _ => text, // במקרה של טקסט לא מוכר, נחזיר את המקור
+            found?
         };
-        
-        Ok(translated.to_string())
-    }
-
-    fn translate_ru_to_he(&self, text: &str) -> Result<String> {
-        // כאן יש להוסיף את הלוגיקה הספציפית לתרגום מרוסית לעברית
-        // לדוגמה, שימוש במודל נוירונים או שירות תרגום חיצוני
-        
-        // לצורך הדוגמה, נשתמש בתרגום פשוט
-        let translated = match text {
-            "исправен" => "תקין",
-            "не обнаружены" => "לא נמצאו",
-            "требуется ежегодная очистка" => "נדרש ניקוי שנתי",
-            "выполнена ежегодная калибровка" => "בוצע כיול שנתי",
-            "перекраска требуется через год" => "צביעה מחדש נדרשת בעוד כשנה",
-            "автоматическая спринклерная система" => "מערכת ספרינקלרים אוטומטית",
-            "Тель-Авив" => "תל אביב",
-            "Исраэль Исраэли" => "ישראל ישראלי",
-            _ => text, // במקרה של טקסט לא מוכר, נחזיר את המקור
+
+        segment.target = match (target, segment.target.is_empty()) {
+            (Language::Russian, true) => translation_models::capitalize(verb),
+            (Language::Russian, false) => format!("{} {}", translation_models::capitalize(verb), segment.target),
+            (_, true) => verb.to_string(),
+            (Language::Hebrew, false) if is_definite_object(&segment.target) => format!("{} את {}", verb, segment.target),
+            (_, false) => format!("{} {}", verb, segment.target),
         };
-        
-        Ok(translated.to_string())
+        segment.target.insert_str(0, clause.prefix);
+        segment.source = text.to_string();
+        Some(segment)
     }
+
+    fn lookup_direct(
+        &self,
+        text: &str,
+        source: Language,
+        target: Language,
+        domain: Option<&str>,
+        log: &mut Vec<TraceStep>,
+    ) -> Option<(String, TranslationOrigin)> {
+        // מילוני הפרויקט גוברים על המילון הכללי, לפי העדיפות
+        for glossary in self.glossaries.glossaries() {
+            let result = glossary.lookup(text, source, target).map(str::to_string);
+            log.push(lookup_step(&format!("glossary:{}", glossary.name), text, result.clone()));
+            if let Some(translated) = result {
+                return Some((translated, TranslationOrigin::Glossary { name: glossary.name.clone() }));
+            }
+        }
+
+        self.lookup_general(text, source, target, domain, log)
+    }
+
+    fn lookup_general(
+        &self,
+        text: &str,
+        source: Language,
+        target: Language,
+        domain: Option<&str>,
+        log: &mut Vec<TraceStep>,
+    ) -> Option<(String, TranslationOrigin)> {
+        // בדיקה במילון הטכני
+        if let Ok(dict) = self.technical_dictionary.lock() {
+            let result = dict.lookup(text, source, target);
+            log.push(lookup_step("technical_dictionary", text, result.clone()));
+            if let Some(translated) = result {
+                return Some((translated, TranslationOrigin::TechnicalDictionary));
+            }
+        }
+
+        // בדיקה אם מדובר במונח טכני ממסד הנתונים הקבוע
+        let term = self.terms_db.get_translation_in(text, source, target, domain);
+        log.push(lookup_step("terms_database", text, term.clone()));
+        if let Some(term) = term {
+            return Some((term, TranslationOrigin::TermsDatabase));
+        }
+
+        // בדיקה אם מדובר בתקן
+        let standard = self.standards_db.get_standard(text).and_then(|standard| match target {
+            Language::Hebrew => Some(standard.name_he.clone()),
+            Language::Russian => Some(standard.name_ru.clone()),
+            Language::English => None,
+        });
+        log.push(lookup_step("standards", text, standard.clone()));
+        if let Some(name) = standard {
+            return Some((name, TranslationOrigin::Standard));
+        }
+
+        // כללי תרגום לביטויים
+        let rule = lookup_rule(text, source, target).map(str::to_string);
+        log.push(lookup_step("rules", text, rule.clone()));
+        rule.map(|translated| (translated, TranslationOrigin::Rule))
+    }
+}
+
+fn lookup_step(layer: &str, form: &str, result: Option<String>) -> TraceStep {
+    TraceStep::Lookup {
+        layer: layer.to_string(),
+        form: form.to_string(),
+        result,
+    }
+}
+
+// צורות המושא לחיפוש: כפי שהוא, ובעברית גם ללא ה' הידיעה - בתחילתו או בסמיכות ("לחץ העבודה")
+fn object_forms(object: &str, lang: Language) -> Vec<String> {
+    let mut forms = vec![object.to_string()];
+    if lang != Language::Hebrew {
+        return forms;
+    }
+    if let Some(stripped) = object.strip_prefix('ה').filter(|s| s.chars().count() > 1) {
+        forms.push(stripped.to_string());
+    }
+    if let Some((head, last)) = object.rsplit_once(' ') {
+        if let Some(stripped) = last.strip_prefix('ה').filter(|s| s.chars().count() > 1) {
+            forms.push(format!("{} {}", head, stripped));
+        }
+    }
+    forms
+}
+
+// "את" באה רק לפני מושא מיודע: מילה בה' הידיעה או שם פרטי, כמו שם מותג בכתב לועזי
+fn is_definite_object(object: &str) -> bool {
+    object.chars().next().map_or(false, |c| c == 'ה' || c.is_uppercase())
+}
+
+fn rule_column(rule: &(&'static str, &'static str, &'static str), lang: Language) -> &'static str {
+    match lang {
+        Language::Hebrew => rule.0,
+        Language::Russian => rule.1,
+        Language::English => rule.2,
+    }
+}
+
+fn lookup_rule(text: &str, source: Language, target: Language) -> Option<&'static str> {
+    PHRASE_RULES
+        .iter()
+        .find(|rule| rule_column(rule, source) == text)
+        .map(|rule| rule_column(rule, target))
 }
 
 #[cfg(test)]
@@ -143,11 +766,17 @@ mod tests {
     use crate::technical_terms::create_initial_terms;
     use crate::standards::create_initial_standards;
 
+    fn create_translator() -> Translator {
+        Translator::new(
+            Arc::new(create_initial_terms()),
+            Arc::new(create_initial_standards()),
+            Arc::new(Mutex::new(TechnicalDictionary::in_memory())),
+        )
+    }
+
     #[test]
     fn test_translation() {
-        let terms_db = create_initial_terms();
-        let standards_db = create_initial_standards();
-        let translator = Translator::new(terms_db, standards_db);
+        let translator = create_translator();
 
         // בדיקת תרגום מעברית לרוסית
         let hebrew_text = "תקין";
@@ -161,4 +790,218 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "תקין");
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_english_translation() {
+        let translator = create_translator();
+
+        let result = translator.translate_detailed("Working Pressure", "en", "ru").unwrap();
+        assert_eq!(result.text, "рабочее давление");
+        assert_eq!(result.segments[0].origin, TranslationOrigin::TermsDatabase);
+
+        let result = translator.translate_with_languages("ראש ספרינקלר", "he", "en").unwrap();
+        assert_eq!(result, "Sprinkler Head");
+
+        // תרגום ישיר קיים ולכן אין שימוש בשפת ציר
+        let result = translator.translate_detailed("לחץ עבודה", "he", "ru").unwrap();
+        assert!(!result.used_pivot());
+    }
+
+    #[test]
+    fn test_glossary_overrides_dictionary() {
+        use crate::glossary::{ProjectGlossary, GlossaryEntry, GlossaryEnforcement};
+
+        let mut glossary = ProjectGlossary::new("customer".to_string(), 10, GlossaryEnforcement::Forced);
+        glossary.add_entry(GlossaryEntry {
+            source: "לחץ עבודה".to_string(),
+            target: "давление рабочее".to_string(),
+            source_lang: Language::Hebrew,
+            target_lang: Language::Russian,
+            note: None,
+        });
+        let mut stack = GlossaryStack::new();
+        stack.push(glossary);
+        let translator = create_translator().with_glossaries(stack);
+
+        let result = translator.translate_detailed("לחץ עבודה", "he", "ru").unwrap();
+        assert_eq!(result.text, "давление рабочее");
+        assert_eq!(
+            result.segments[0].origin,
+            TranslationOrigin::Glossary { name: "customer".to_string() }
+        );
+        assert!(result.glossary_issues().is_empty());
+    }
+
+    #[test]
+    fn test_pivot_through_english() {
+        use crate::glossary::{ProjectGlossary, GlossaryEntry, GlossaryEnforcement};
+
+        // למונח אין תרגום ישיר בין עברית לרוסית, רק דרך אנגלית
+        let mut glossary = ProjectGlossary::new("pivot".to_string(), 10, GlossaryEnforcement::Preferred);
+        for (source, target, source_lang, target_lang) in [
+            ("מסיט", "deflector", Language::Hebrew, Language::English),
+            ("deflector", "дефлектор", Language::English, Language::Russian),
+        ] {
+            glossary.add_entry(GlossaryEntry {
+                source: source.to_string(),
+                target: target.to_string(),
+                source_lang,
+                target_lang,
+                note: None,
+            });
+        }
+        let mut stack = GlossaryStack::new();
+        stack.push(glossary);
+        let translator = create_translator().with_glossaries(stack);
+
+        let glossary_origin = || Box::new(TranslationOrigin::Glossary { name: "pivot".to_string() });
+        let expected = TranslationOrigin::Pivot {
+            via: Language::English,
+            first: glossary_origin(),
+            second: glossary_origin(),
+        };
+
+        let result = translator.translate_detailed("מסיט", "he", "ru").unwrap();
+        assert_eq!(result.text, "дефлектор");
+        assert!(result.used_pivot());
+        assert_eq!(result.segments[0].origin, expected);
+        assert_eq!(result.segments[0].pivot_text.as_deref(), Some("deflector"));
+
+        let result = translator.translate_detailed("дефлектор", "ru", "he").unwrap();
+        assert_eq!(result.text, "מסיט");
+        assert_eq!(result.segments[0].origin, expected);
+    }
+
+    #[test]
+    fn test_instruction_mood_from_context() {
+        use crate::translation_models::{Domain, Style, Formality};
+        use std::collections::HashMap;
+
+        let context = TranslationContext {
+            domain: Domain::Technical,
+            style: Style::Professional,
+            formality: Formality::Medium,
+            instruction_mood: None,
+            metadata: HashMap::new(),
+        };
+        let translator = create_translator().with_context(&context);
+
+        // הפועל נבחר בשלב ההעברה ולכן אין צורך בעריכה אחרי היצירה
+        let result = translator.translate_detailed("לבדוק את לחץ העבודה", "he", "ru").unwrap();
+        assert_eq!(result.text, "Проверьте рабочее давление");
+        assert_eq!(result.segments[0].origin, TranslationOrigin::TermsDatabase);
+        assert!(result.segments[0].post_edits.is_empty());
+
+        // מושא לא מיודע בעברית בא בלי "את"
+        let result = translator.translate_detailed("Проверьте рабочее давление", "ru", "he").unwrap();
+        assert!(result.text.ends_with("לחץ עבודה"));
+        assert!(!result.text.contains(" את "));
+        assert!(is_definite_object("הלחץ") && is_definite_object("Viking"));
+        assert!(!is_definite_object("לחץ עבודה"));
+
+        // "בדוק" בלי מושא הוא תואר ולא הוראה
+        let result = translator.translate_detailed("בדוק", "he", "ru").unwrap();
+        assert_eq!(result.segments[0].origin, TranslationOrigin::Untranslated);
+    }
+
+    #[test]
+    fn test_sense_from_document_domain() {
+        use crate::technical_terms::{Concept, ConceptTerm};
+
+        // "לחץ" מתורגם אחרת בכל תחום במסד המונחים
+        let mut terms = create_initial_terms();
+        for (id, domain, target) in [("pressure", "hydraulics", "давление"), ("stress", "structural", "напряжение")] {
+            terms.add_concept(
+                Concept::new(id, domain)
+                    .with_term(ConceptTerm::new(Language::Hebrew, "לחץ"))
+                    .with_term(ConceptTerm::new(Language::Russian, target)),
+            );
+        }
+        let terms = Arc::new(terms);
+        let translator = Translator::new(
+            terms.clone(),
+            Arc::new(create_initial_standards()),
+            Arc::new(Mutex::new(TechnicalDictionary::in_memory())),
+        );
+
+        let text = "העומס על הקורה גבוה. לחץ. יש לבדוק את הבטון";
+        let result = translator.translate_detailed(text, "he", "ru").unwrap();
+        let segment = &result.segments[1];
+        assert_eq!(segment.target, "напряжение");
+        assert_eq!(segment.origin, TranslationOrigin::Sense { domain: "structural".to_string() });
+        assert_eq!(segment.sense_decisions.len(), 1);
+
+        // תרגום יחיד במילון של המשתמש גובר על בחירת המשמעות
+        let dictionary = TechnicalDictionary::in_memory();
+        dictionary
+            .insert_terms(vec![crate::technical_dictionary::TechnicalTerm::for_test("לחץ", "давление", "hydraulics")])
+            .unwrap();
+        let translator = Translator::new(terms, Arc::new(create_initial_standards()), Arc::new(Mutex::new(dictionary)));
+        let result = translator.translate_detailed(text, "he", "ru").unwrap();
+        assert_eq!(result.segments[1].target, "давление");
+        assert_eq!(result.segments[1].origin, TranslationOrigin::TechnicalDictionary);
+    }
+
+    #[test]
+    fn test_translate_traced() {
+        let translator = create_translator();
+
+        let (output, trace) = translator.translate_traced("נדרש ניקוי שנתי. TY3251", "he", "ru").unwrap();
+        assert_eq!(trace.segments.len(), output.segments.len());
+        assert!(trace.segments[1].tokens[0]
+            .steps
+            .iter()
+            .any(|step| matches!(step, trace::TraceStep::Protected { .. })));
+        assert!(trace.render_report().contains("TY3251"));
+        assert!(trace.to_json().unwrap().contains("rules"));
+    }
+
+    #[tokio::test]
+    async fn test_quality_check_runs_source_checks() {
+        use crate::quality_control::IssueType;
+
+        let translator = create_translator();
+        let source = "ההתקנה לפי NFPA 13:2019. ראש TY3251";
+        let mut output = translator.translate_detailed(source, "he", "ru").unwrap();
+        // קטע מוגן שאבד בתרגום
+        output.text = output.text.replace("TY3251", "TY 3251");
+
+        let report = translator.quality_check(&QualityControl::new(), source, &output).await.unwrap();
+        assert!(report.issues.iter().any(|i| i.issue_type == IssueType::ProtectedSpan));
+        assert!(report.issues.iter().any(|i| i.issue_type == IssueType::StandardReference));
+    }
+
+    #[test]
+    fn test_superseded_edition_warning() {
+        let translator = create_translator();
+
+        let output = translator.translate_detailed("ההתקנה לפי NFPA 13:2019. תקין", "he", "ru").unwrap();
+        let warnings = output.standard_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].0, 0);
+        assert_eq!(warnings[0].1.replacement.as_ref().unwrap().year, Some(2025));
+    }
+
+    #[test]
+    fn test_clause_reference_rendered() {
+        let translator = create_translator();
+
+        let output = translator.translate_detailed("סעיף 5.2.1 בתקן NFPA 13", "he", "ru").unwrap();
+        assert_eq!(output.segments[0].target, "п. 5.2.1 NFPA 13");
+        assert_eq!(output.segments[0].post_edits[0].stage, "clause_references");
+    }
+
+    #[test]
+    fn test_footnotes_numbered_across_document() {
+        let mut output = TranslationOutput::new(Language::Hebrew, Language::Russian);
+        for (target, title) in [("п. 5.2.1[1]", "Общие требования"), ("табл. 3[1]", "Расход воды")] {
+            let mut segment = SegmentTranslation::untranslated(target);
+            segment.footnotes = vec![title.to_string()];
+            output.push_sentence(segment);
+        }
+
+        assert_eq!(output.text, "п. 5.2.1[1]. табл. 3[2].");
+        assert_eq!(output.segments[1].target, "табл. 3[2]");
+        assert_eq!(output.footnotes, vec!["Общие требования".to_string(), "Расход воды".to_string()]);
+    }
+}
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use crate::quality_control::find_term_occurrences;
 use crate::translation_models::{Domain, TranslationError};
 
 /// מודל זיהוי תחום
@@ -50,24 +51,48 @@ impl DomainModel {
         model
     }
 
+    /// מודל הכולל גם תחומי משנה הנדסיים (דיסציפלינות)
+    pub fn with_disciplines() -> Self {
+        let mut model = Self::new();
+
+        let disciplines: [(&str, &[&str]); 5] = [
+            ("hydraulics", &["צנרת", "משאבה", "ספיקה", "מגוף", "בר", "מים", "ספרינקלר"]),
+            ("structural", &["קורה", "בטון", "פלדה", "עומס", "חתך", "מתיחה", "כפיפה", "עמוד"]),
+            ("plumbing", &["כיור", "ניקוז", "ביוב", "אינסטלציה", "מטבח", "אמבטיה", "דוד"]),
+            ("fire_protection", &["כיבוי", "אש", "הידרנט", "זרנוק", "גלאי", "מתזים"]),
+            ("electrical", &["חשמל", "כבל", "מתח", "לוח", "הארקה", "מפסק"]),
+        ];
+        for (name, terms) in disciplines {
+            let domain = Domain::Custom(name.to_string());
+            model.domain_terms.insert(domain.clone(), terms.iter().map(|t| t.to_string()).collect());
+            model.domain_weights.insert(domain, 1.0);
+        }
+
+        model
+    }
+
+    /// ציון לכל תחום עבור הטקסט
+    pub fn scores(&self, text: &str) -> HashMap<Domain, f64> {
+        self.domain_terms
+            .iter()
+            .map(|(domain, terms)| (domain.clone(), self.calculate_domain_score(text, terms)))
+            .collect()
+    }
+
     /// מזהה תחום
     pub fn detect(&self, text: &str) -> Result<Domain, TranslationError> {
-        let mut scores = HashMap::new();
-        
         // חישוב ציון לכל תחום
-        for (domain, terms) in &self.domain_terms {
-            let score = self.calculate_domain_score(text, terms);
-            scores.insert(domain, score);
-        }
+        let scores = self.scores(text);
         
         // בחירת התחום עם הציון הגבוה ביותר
         let domain = scores
-            .iter()
-            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
-            .map(|(domain, _)| *domain)
-            .unwrap_or(&Domain::General);
+            .into_iter()
+            .filter(|(_, score)| *score > 0.0)
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .map(|(domain, _)| domain)
+            .unwrap_or(Domain::General);
             
-        Ok(domain.clone())
+        Ok(domain)
     }
 
     /// מחשב ציון לתחום
@@ -75,11 +100,9 @@ impl DomainModel {
         let mut score = 0.0;
         let text = text.to_lowercase();
         
-        // חישוב מספר המופעים של כל מונח
+        // חישוב מספר המופעים של כל מונח כמילה שלמה, כך ש"בר" אינו נספר בתוך "עבר"
         for term in terms {
-            let term = term.to_lowercase();
-            let count = text.matches(&term).count();
-            score += count as f64;
+            score += find_term_occurrences(&text, term).len() as f64;
         }
         
         // נרמול הציון לפי אורך הטקסט
@@ -1,8 +1,10 @@
 mod domain;
 mod style;
+mod mood;
 
 pub use domain::DomainModel;
 pub use style::StyleModel;
+pub use mood::{InstructionMood, InstructionClause, MoodPolicy, MoodNormalizer, MoodChange, split_instruction, capitalize};
 
 use chrono::{DateTime, Utc};
 use thiserror::Error;
@@ -17,6 +19,8 @@ pub struct TranslationContext {
     pub style: Style,
     /// רמת פורמליות
     pub formality: Formality,
+    /// דרך ניסוח קבועה להוראות; אם חסרה - נגזרת מהפורמליות והסגנון
+    pub instruction_mood: Option<MoodPolicy>,
     pub metadata: HashMap<String, String>,
 }
 
@@ -135,6 +139,7 @@ impl TranslationCache {
                 domain: Domain::Technical,
                 style: Style::Formal,
                 formality: Formality::High,
+                instruction_mood: None,
                 metadata: HashMap::new(),
             },
             quality_score: 0.0,
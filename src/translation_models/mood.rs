@@ -0,0 +1,253 @@
+use serde::{Serialize, Deserialize};
+use crate::language::Language;
+use crate::translation_models::{TranslationContext, Formality, Style};
+
+/// דרך הניסוח של הוראות עבודה
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InstructionMood {
+    /// "לבדוק את הלחץ" / "Проверить давление"
+    Infinitive,
+    /// "בדוק את הלחץ" / "Проверьте давление"
+    Imperative,
+    /// "תבדוק את הלחץ" - בעברית בלבד
+    Future,
+}
+
+/// בחירת דרך הניסוח לכל שפת יעד, אחידה לכל המסמך
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoodPolicy {
+    pub hebrew: InstructionMood,
+    pub russian: InstructionMood,
+}
+
+impl MoodPolicy {
+    /// גזירת המדיניות מרמת הפורמליות והסגנון שבהקשר
+    pub fn from_context(context: &TranslationContext) -> Self {
+        if let Some(policy) = context.instruction_mood {
+            return policy;
+        }
+
+        match (&context.formality, &context.style) {
+            (_, Style::Casual) | (_, Style::Informal) | (Formality::Low, _) => Self {
+                hebrew: InstructionMood::Imperative,
+                russian: InstructionMood::Imperative,
+            },
+            (Formality::Medium, _) | (_, Style::Professional) => Self {
+                hebrew: InstructionMood::Infinitive,
+                russian: InstructionMood::Imperative,
+            },
+            _ => Self {
+                hebrew: InstructionMood::Infinitive,
+                russian: InstructionMood::Infinitive,
+            },
+        }
+    }
+
+    pub fn mood_for(&self, lang: Language) -> Option<InstructionMood> {
+        match lang {
+            Language::Hebrew => Some(self.hebrew),
+            Language::Russian => Some(match self.russian {
+                // אין הבחנה בין עתיד לציווי בהוראות ברוסית
+                InstructionMood::Future => InstructionMood::Imperative,
+                mood => mood,
+            }),
+            Language::English => None,
+        }
+    }
+}
+
+// פעלי הוראה נפוצים: בעברית שם פועל, ציווי ועתיד; ברוסית שם פועל וציווי בפנייה מנומסת
+const INSTRUCTION_VERBS: &[([&str; 3], [&str; 2])] = &[
+    (["לבדוק", "בדוק", "תבדוק"], ["проверить", "проверьте"]),
+    (["להתקין", "התקן", "תתקין"], ["установить", "установите"]),
+    (["לנקות", "נקה", "תנקה"], ["очистить", "очистите"]),
+    (["לסגור", "סגור", "תסגור"], ["закрыть", "закройте"]),
+    (["לפתוח", "פתח", "תפתח"], ["открыть", "откройте"]),
+    (["להחליף", "החלף", "תחליף"], ["заменить", "замените"]),
+    (["לוודא", "ודא", "תוודא"], ["убедиться", "убедитесь"]),
+    (["לחבר", "חבר", "תחבר"], ["подключить", "подключите"]),
+    (["לנתק", "נתק", "תנתק"], ["отключить", "отключите"]),
+    (["לכייל", "כייל", "תכייל"], ["откалибровать", "откалибруйте"]),
+    (["לשטוף", "שטוף", "תשטוף"], ["промыть", "промойте"]),
+    (["להדק", "הדק", "תהדק"], ["затянуть", "затяните"]),
+];
+
+// צורות ציווי שהן גם תואר או שם עצם ("בדוק" - נבדק, "פתח" - פתח):
+// נחשבות לפועל רק כשאחריהן מושא - "את" או שם מיודע
+const AMBIGUOUS_HEBREW_IMPERATIVES: &[&str] = &["בדוק", "סגור", "פתח", "חבר", "שטוף", "הדק"];
+
+/// שינוי דרך הניסוח שבוצע במקטע
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MoodChange {
+    pub before: String,
+    pub after: String,
+    pub mood: InstructionMood,
+}
+
+/// משפט הוראה שמתחיל בפועל הוראה מוכר
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstructionClause<'a> {
+    /// סימני רשימה לפני הפועל
+    pub prefix: &'a str,
+    pub verb: &'a str,
+    pub mood: InstructionMood,
+    /// המושא וכל מה שאחרי הפועל
+    pub rest: &'a str,
+    index: usize,
+}
+
+impl InstructionClause<'_> {
+    /// צורת הפועל בשפה ובדרך הניסוח המבוקשות
+    pub fn verb_in(&self, lang: Language, mood: InstructionMood) -> Option<&'static str> {
+        let (hebrew, russian) = &INSTRUCTION_VERBS[self.index];
+        match (lang, mood) {
+            (Language::Hebrew, InstructionMood::Infinitive) => Some(hebrew[0]),
+            (Language::Hebrew, InstructionMood::Imperative) => Some(hebrew[1]),
+            (Language::Hebrew, InstructionMood::Future) => Some(hebrew[2]),
+            (Language::Russian, InstructionMood::Infinitive) => Some(russian[0]),
+            (Language::Russian, _) => Some(russian[1]),
+            (Language::English, _) => None,
+        }
+    }
+}
+
+/// זיהוי פועל הוראה בתחילת משפט, לאחר סימני רשימה: "1.", "-", "•"
+pub fn split_instruction(sentence: &str, lang: Language) -> Option<InstructionClause<'_>> {
+    let body_start = sentence
+        .char_indices()
+        .find(|(_, c)| c.is_alphabetic())
+        .map(|(i, _)| i)
+        .unwrap_or(sentence.len());
+    let (prefix, body) = sentence.split_at(body_start);
+    let word_end = body
+        .char_indices()
+        .find(|(_, c)| !c.is_alphabetic() && *c != '-')
+        .map(|(i, _)| i)
+        .unwrap_or(body.len());
+    let (verb, rest) = body.split_at(word_end);
+
+    let (index, mood) = match lang {
+        Language::Hebrew => {
+            let found = INSTRUCTION_VERBS.iter().enumerate().find_map(|(index, (forms, _))| {
+                let mood = match forms.iter().position(|form| *form == verb)? {
+                    0 => InstructionMood::Infinitive,
+                    1 => InstructionMood::Imperative,
+                    _ => InstructionMood::Future,
+                };
+                Some((index, mood))
+            })?;
+            if AMBIGUOUS_HEBREW_IMPERATIVES.contains(&verb) && !starts_with_object(rest) {
+                return None;
+            }
+            found
+        }
+        Language::Russian => {
+            let lower = verb.to_lowercase();
+            INSTRUCTION_VERBS.iter().enumerate().find_map(|(index, (_, forms))| {
+                match forms.iter().position(|form| *form == lower)? {
+                    0 => Some((index, InstructionMood::Infinitive)),
+                    _ => Some((index, InstructionMood::Imperative)),
+                }
+            })?
+        }
+        Language::English => return None,
+    };
+
+    Some(InstructionClause { prefix, verb, mood, rest, index })
+}
+
+// מושא ישיר מיודע: "את ..." או מילה בה' הידיעה
+fn starts_with_object(rest: &str) -> bool {
+    rest.split_whitespace()
+        .next()
+        .map_or(false, |word| word == "את" || (word.starts_with('ה') && word.chars().count() > 2))
+}
+
+/// מיישם דרך ניסוח אחידה על משפטי הוראה בשלב היצירה
+#[derive(Debug, Clone)]
+pub struct MoodNormalizer {
+    policy: MoodPolicy,
+}
+
+impl MoodNormalizer {
+    pub fn new(policy: MoodPolicy) -> Self {
+        Self { policy }
+    }
+
+    pub fn from_context(context: &TranslationContext) -> Self {
+        Self::new(MoodPolicy::from_context(context))
+    }
+
+    pub fn policy(&self) -> MoodPolicy {
+        self.policy
+    }
+
+    /// התאמת פועל ההוראה בתחילת המשפט לדרך הניסוח הנבחרת
+    pub fn apply(&self, sentence: &str, lang: Language) -> (String, Option<MoodChange>) {
+        let (Some(mood), Some(clause)) = (self.policy.mood_for(lang), split_instruction(sentence, lang)) else {
+            return (sentence.to_string(), None);
+        };
+
+        // שמירה על אות ראשונה גדולה בתחילת משפט
+        let after = match clause.verb_in(lang, mood) {
+            Some(form) if clause.verb.chars().next().map_or(false, char::is_uppercase) => capitalize(form),
+            Some(form) => form.to_string(),
+            None => return (sentence.to_string(), None),
+        };
+
+        if after == clause.verb {
+            return (sentence.to_string(), None);
+        }
+        (
+            format!("{}{}{}", clause.prefix, after, clause.rest),
+            Some(MoodChange {
+                before: clause.verb.to_string(),
+                after,
+                mood,
+            }),
+        )
+    }
+}
+
+/// אות ראשונה גדולה, לפועל שפותח משפט
+pub fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hebrew_moods() {
+        let normalizer = MoodNormalizer::new(MoodPolicy {
+            hebrew: InstructionMood::Future,
+            russian: InstructionMood::Infinitive,
+        });
+        let (text, change) = normalizer.apply("1. בדוק את הלחץ", Language::Hebrew);
+        assert_eq!(text, "1. תבדוק את הלחץ");
+        assert_eq!(change.unwrap().before, "בדוק");
+
+        // "סגור" ו"בדוק" כתואר אינם פועל הוראה
+        let (text, change) = normalizer.apply("סגור לחלוטין", Language::Hebrew);
+        assert_eq!(text, "סגור לחלוטין");
+        assert!(change.is_none());
+        assert!(split_instruction("בדוק ותקין", Language::Hebrew).is_none());
+        assert!(split_instruction("סגור הברז", Language::Hebrew).is_some());
+    }
+
+    #[test]
+    fn test_russian_moods() {
+        let normalizer = MoodNormalizer::new(MoodPolicy {
+            hebrew: InstructionMood::Infinitive,
+            russian: InstructionMood::Infinitive,
+        });
+        let (text, _) = normalizer.apply("- Проверьте давление", Language::Russian);
+        assert_eq!(text, "- Проверить давление");
+
+        let (text, change) = normalizer.apply("Давление в норме", Language::Russian);
+        assert_eq!(text, "Давление в норме");
+        assert!(change.is_none());
+    }
+}
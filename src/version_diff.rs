@@ -0,0 +1,304 @@
+//! דוחות הבדלים בין גרסאות מילון, ב-Markdown וב-HTML, עם ערך לפני ואחרי לכל שדה שהשתנה.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write as _;
+use std::path::Path;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::dictionary_merge::{term_field, MERGE_FIELDS};
+use crate::knowledge_sharing::DictionaryVersion;
+use crate::technical_dictionary::TechnicalTerm;
+
+/// שדה שהשתנה בין הגרסאות
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldDiff {
+    pub field: String,
+    pub before: Value,
+    pub after: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TermDiff {
+    pub term_id: String,
+    pub fields: Vec<FieldDiff>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionDiff {
+    pub from_version: String,
+    pub to_version: String,
+    pub generated_at: DateTime<Utc>,
+    pub added: Vec<TechnicalTerm>,
+    pub removed: Vec<TechnicalTerm>,
+    pub modified: Vec<TermDiff>,
+}
+
+impl VersionDiff {
+    pub fn between(from: &DictionaryVersion, to: &DictionaryVersion) -> Result<Self> {
+        Self::between_terms(&from.version_id, &from.terms, &to.version_id, &to.terms)
+    }
+
+    /// השוואת שני אוספי מונחים לפי מזהה; רק שדות התוכן של המונח נבדקים
+    pub fn between_terms(
+        from_version: &str,
+        from: &HashMap<String, TechnicalTerm>,
+        to_version: &str,
+        to: &HashMap<String, TechnicalTerm>,
+    ) -> Result<Self> {
+        let mut diff = Self {
+            from_version: from_version.to_string(),
+            to_version: to_version.to_string(),
+            generated_at: Utc::now(),
+            added: Vec::new(),
+            removed: Vec::new(),
+            modified: Vec::new(),
+        };
+
+        let ids: BTreeSet<&String> = from.keys().chain(to.keys()).collect();
+        for id in ids {
+            match (from.get(id), to.get(id)) {
+                (None, Some(term)) => diff.added.push(term.clone()),
+                (Some(term), None) => diff.removed.push(term.clone()),
+                (Some(before), Some(after)) => {
+                    let mut fields = Vec::new();
+                    for field in MERGE_FIELDS {
+                        let (old, new) = (term_field(before, field)?, term_field(after, field)?);
+                        if old != new {
+                            fields.push(FieldDiff { field: field.to_string(), before: old, after: new });
+                        }
+                    }
+                    if !fields.is_empty() {
+                        diff.modified.push(TermDiff { term_id: id.clone(), fields });
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+        Ok(diff)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} -> {}: {} נוספו, {} נמחקו, {} שונו",
+            self.from_version,
+            self.to_version,
+            self.added.len(),
+            self.removed.len(),
+            self.modified.len()
+        )
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# הבדלים בין {} ל-{}\n", self.from_version, self.to_version);
+        let _ = writeln!(out, "{}\n", self.summary());
+
+        if !self.added.is_empty() {
+            let _ = writeln!(out, "## מונחים שנוספו\n\n| מונח | תרגום | תחום |\n|---|---|---|");
+            for term in &self.added {
+                let _ = writeln!(out, "| {} | {} | {} |", md_cell(&term.source), md_cell(&term.target), md_cell(&term.domain));
+            }
+            out.push('\n');
+        }
+        if !self.removed.is_empty() {
+            let _ = writeln!(out, "## מונחים שנמחקו\n\n| מונח | תרגום | תחום |\n|---|---|---|");
+            for term in &self.removed {
+                let _ = writeln!(out, "| {} | {} | {} |", md_cell(&term.source), md_cell(&term.target), md_cell(&term.domain));
+            }
+            out.push('\n');
+        }
+        if !self.modified.is_empty() {
+            let _ = writeln!(out, "## מונחים ששונו\n");
+            for term in &self.modified {
+                let _ = writeln!(out, "### {}\n\n| שדה | לפני | אחרי |\n|---|---|---|", term.term_id);
+                for field in &term.fields {
+                    let _ = writeln!(
+                        out,
+                        "| {} | {} | {} |",
+                        field.field,
+                        md_cell(&display_value(&field.before)),
+                        md_cell(&display_value(&field.after))
+                    );
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "<!DOCTYPE html>\n<html dir=\"rtl\" lang=\"he\">\n<head>\n<meta charset=\"utf-8\">");
+        let _ = writeln!(out, "<title>{} - {}</title>", html_escape(&self.from_version), html_escape(&self.to_version));
+        let _ = writeln!(
+            out,
+            "<style>table{{border-collapse:collapse}}td,th{{border:1px solid #ccc;padding:4px 8px}}\
+             .before{{background:#fdecea}}.after{{background:#e8f5e9}}</style>\n</head>\n<body>"
+        );
+        let _ = writeln!(
+            out,
+            "<h1>הבדלים בין {} ל-{}</h1>\n<p>{}</p>",
+            html_escape(&self.from_version),
+            html_escape(&self.to_version),
+            html_escape(&self.summary())
+        );
+
+        for (title, terms) in [("מונחים שנוספו", &self.added), ("מונחים שנמחקו", &self.removed)] {
+            if terms.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "<h2>{}</h2>\n<table>\n<tr><th>מונח</th><th>תרגום</th><th>תחום</th></tr>", title);
+            for term in terms {
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    html_escape(&term.source),
+                    html_escape(&term.target),
+                    html_escape(&term.domain)
+                );
+            }
+            let _ = writeln!(out, "</table>");
+        }
+
+        if !self.modified.is_empty() {
+            let _ = writeln!(out, "<h2>מונחים ששונו</h2>");
+            for term in &self.modified {
+                let _ = writeln!(
+                    out,
+                    "<h3>{}</h3>\n<table>\n<tr><th>שדה</th><th>לפני</th><th>אחרי</th></tr>",
+                    html_escape(&term.term_id)
+                );
+                for field in &term.fields {
+                    let _ = writeln!(
+                        out,
+                        "<tr><td>{}</td><td class=\"before\">{}</td><td class=\"after\">{}</td></tr>",
+                        html_escape(&field.field),
+                        html_escape(&display_value(&field.before)),
+                        html_escape(&display_value(&field.after))
+                    );
+                }
+                let _ = writeln!(out, "</table>");
+            }
+        }
+        let _ = writeln!(out, "</body>\n</html>");
+        out
+    }
+
+    /// שמירת הדוח לפי סיומת הקובץ: ‎.html או ‎.htm ל-HTML, אחרת Markdown
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let html = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map_or(false, |ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"));
+        let content = if html { self.to_html() } else { self.to_markdown() };
+        std::fs::write(path, content).with_context(|| format!("כתיבת דוח ההבדלים נכשלה: {}", path.display()))
+    }
+}
+
+// רשימות מוצגות כפריטים מופרדים בפסיקים, מחרוזות כפי שהן, וריק כ"—"
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::Null => "—".to_string(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) if items.is_empty() => "—".to_string(),
+        Value::Array(items) => items.iter().map(display_value).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
+
+fn md_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::technical_dictionary::TechnicalDictionary;
+    use crate::knowledge_sharing::KnowledgeManager;
+
+    fn term(source: &str, target: &str) -> TechnicalTerm {
+        let mut term = TechnicalTerm::for_test(source, target, "hydraulics");
+        term.metadata.verified = true;
+        term
+    }
+
+    fn terms(list: &[TechnicalTerm]) -> HashMap<String, TechnicalTerm> {
+        list.iter().map(|term| (term.source.clone(), term.clone())).collect()
+    }
+
+    #[test]
+    fn test_field_level_diff() {
+        let mut changed = term("מגוף", "задвижка");
+        changed.target = "запорный клапан".to_string();
+        changed.notes = vec!["ГОСТ 5762".to_string()];
+        changed.metadata.usage_count = 12;
+
+        let diff = VersionDiff::between_terms(
+            "v1",
+            &terms(&[term("מגוף", "задвижка"), term("ברז", "кран")]),
+            "v2",
+            &terms(&[changed, term("צנרת", "трубопровод")]),
+        )
+        .unwrap();
+
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.removed[0].source, "ברז");
+        let fields: Vec<&str> = diff.modified[0].fields.iter().map(|f| f.field.as_str()).collect();
+        assert_eq!(fields, vec!["target", "notes"]);
+    }
+
+    #[test]
+    fn test_reports_escape_content() {
+        let mut changed = term("מגוף", "задвижка");
+        changed.definition = Some("<b>אביזר</b> | סגירה".to_string());
+        let diff = VersionDiff::between_terms("v1", &terms(&[term("מגוף", "задвижка")]), "v2", &terms(&[changed])).unwrap();
+
+        let html = diff.to_html();
+        assert!(html.contains("&lt;b&gt;אביזר&lt;/b&gt;"));
+        assert!(html.contains("class=\"before\">—"));
+        let markdown = diff.to_markdown();
+        assert!(markdown.contains("| definition | — | <b>אביזר</b> \\| סגירה |"));
+    }
+
+    #[test]
+    fn test_rollback_creates_new_version() {
+        let dictionary = TechnicalDictionary::in_memory();
+        let mut manager = KnowledgeManager::new();
+        dictionary.insert_terms(vec![term("מגוף", "задвижка")]).unwrap();
+        manager.create_version(&dictionary, "v1".to_string(), "editor".to_string(), "בסיס".to_string()).unwrap();
+
+        let mut changed = term("מגוף", "шибер");
+        changed.domain = "fire".to_string();
+        changed.metadata.usage_count = 7;
+        dictionary.insert_terms(vec![changed, term("צנרת", "трубопровод")]).unwrap();
+
+        let diff = manager.rollback_to_version(&dictionary, "v1", "v3", "admin").unwrap();
+        assert_eq!(diff.removed.len(), 1);
+        let restored_term = dictionary.get_term("מגוף").unwrap();
+        assert_eq!(restored_term.target, "задвижка");
+        // נתוני השימוש אינם חלק מהשחזור
+        assert_eq!(restored_term.metadata.usage_count, 7);
+        assert!(dictionary.get_term("צנרת").is_none());
+
+        let restored = manager.get_version("v3").unwrap();
+        assert_eq!(restored.terms.len(), 1);
+        assert!(manager.diff_versions("v1", "v3").unwrap().is_empty());
+
+        let history = manager.get_term_history("מגוף").unwrap();
+        assert!(history.changes.iter().any(|c| c.field == "target" && c.new_value.as_deref() == Some("задвижка")));
+        assert!(manager.get_term_history("צנרת").unwrap().changes.iter().any(|c| c.new_value.is_none()));
+    }
+}